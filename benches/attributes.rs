@@ -0,0 +1,80 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use proc_macro2::{Ident, Span};
+use proc_macro_roids::{append_tag, DeriveInputExt, DeriveInputStructExt, FieldExt};
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Path};
+
+/// Number of fields in the benchmarked struct.
+const FIELD_COUNT: usize = 200;
+
+/// Returns a struct with many fields, each with several attributes.
+fn struct_with_many_fields() -> DeriveInput {
+    let fields = (0..FIELD_COUNT).map(|n| {
+        let field_ident = Ident::new(&format!("field_{}", n), Span::call_site());
+        quote! {
+            #[doc = "Field documentation."]
+            #[serde(rename = "renamed", default = "Default::default")]
+            #[my::derive(with = "path::to::function", rename = "other")]
+            #[my::derive(skip_if = "Option::is_none")]
+            #[other(a, b(c, d), e = 1)]
+            pub #field_ident: Option<Vec<u32>>
+        }
+    });
+
+    parse_quote! {
+        #[derive(Clone, Debug)]
+        #[my::derive(transparent)]
+        pub struct Struct {
+            #(#fields,)*
+        }
+    }
+}
+
+fn contains_tag(c: &mut Criterion) {
+    let ast = struct_with_many_fields();
+    let namespace: Path = parse_quote!(my::derive);
+    let tag: Path = parse_quote!(skip);
+
+    c.bench_function("contains_tag", |b| {
+        b.iter(|| {
+            ast.fields()
+                .iter()
+                .filter(|field| black_box(field).contains_tag(&namespace, &tag))
+                .count()
+        })
+    });
+}
+
+fn append_derives(c: &mut Criterion) {
+    let ast = struct_with_many_fields();
+
+    c.bench_function("append_derives", |b| {
+        b.iter_batched(
+            || ast.clone(),
+            |mut ast| {
+                ast.append_derives(parse_quote!(PartialEq, Eq));
+                ast
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn append_tag_to_struct(c: &mut Criterion) {
+    let ast = struct_with_many_fields();
+    let namespace: Path = parse_quote!(new::derive);
+
+    c.bench_function("append_tag", |b| {
+        b.iter_batched(
+            || ast.clone(),
+            |mut ast| {
+                append_tag(&mut ast.attrs, &namespace, parse_quote!(skip));
+                ast
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, contains_tag, append_derives, append_tag_to_struct);
+criterion_main!(benches);
@@ -0,0 +1,186 @@
+use quote::quote;
+use syn::{Attribute, Meta, Path};
+
+use crate::util;
+
+/// Chainable editor to modify the nested metas of an `#[namespace(..)]`
+/// attribute.
+///
+/// The attribute's nested metas are parsed once, edited in order, and written
+/// back by [`finish`]. The attribute's path, style, and delimiter are
+/// preserved.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::AttrEditor;
+/// use syn::{parse_quote, Attribute};
+///
+/// let attr: Attribute = parse_quote!(#![my::derive(a, b = 1, c)]);
+///
+/// let attr = AttrEditor::new(attr)?
+///     .remove(&parse_quote!(a))
+///     .replace(&parse_quote!(b), parse_quote!(b = 2))
+///     .push(parse_quote!(d(e)))
+///     .finish();
+///
+/// let attr_expected: Attribute = parse_quote!(#![my::derive(b = 2, c, d(e))]);
+/// assert_eq!(attr_expected, attr);
+/// # Ok::<(), syn::Error>(())
+/// ```
+///
+/// [`finish`]: Self::finish
+#[derive(Debug)]
+pub struct AttrEditor {
+    /// The attribute being edited.
+    attr: Attribute,
+    /// The nested metas of the attribute.
+    nested_metas: Vec<Meta>,
+}
+
+impl AttrEditor {
+    /// Returns a new `AttrEditor` for the given attribute.
+    ///
+    /// Both `#[namespace]` and `#[namespace(..)]` attributes may be edited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attribute is in the `#[namespace = ..]` form,
+    /// or if its arguments cannot be parsed as metas. See
+    /// [`parse_nested_metas`](crate::parse_nested_metas).
+    pub fn new(attr: Attribute) -> syn::Result<Self> {
+        let nested_metas = util::parse_nested_metas(&attr)?;
+
+        Ok(Self { attr, nested_metas })
+    }
+
+    /// Returns the nested metas of the attribute, with edits applied.
+    pub fn nested_metas(&self) -> &[Meta] {
+        &self.nested_metas
+    }
+
+    /// Appends a nested meta.
+    pub fn push(mut self, meta: Meta) -> Self {
+        self.nested_metas.push(meta);
+        self
+    }
+
+    /// Removes nested metas whose path is `path`.
+    ///
+    /// Metas of any form are removed, i.e. `path`, `path(..)`, and
+    /// `path = ..`.
+    pub fn remove(self, path: &Path) -> Self {
+        self.retain(|meta| meta.path() != path)
+    }
+
+    /// Replaces each nested meta whose path is `path` with `meta`.
+    ///
+    /// Nothing is added if there is no nested meta with the given path.
+    pub fn replace(mut self, path: &Path, meta: Meta) -> Self {
+        self.nested_metas
+            .iter_mut()
+            .filter(|nested_meta| nested_meta.path() == path)
+            .for_each(|nested_meta| *nested_meta = meta.clone());
+        self
+    }
+
+    /// Retains only the nested metas for which `f` returns `true`.
+    pub fn retain<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&Meta) -> bool,
+    {
+        self.nested_metas.retain(f);
+        self
+    }
+
+    /// Returns the attribute with the edited nested metas.
+    ///
+    /// A `#[namespace]` attribute with no nested metas is left as is.
+    pub fn finish(self) -> Attribute {
+        let Self {
+            mut attr,
+            nested_metas,
+        } = self;
+
+        let tokens = quote!(#(#nested_metas),*);
+        match &mut attr.meta {
+            Meta::List(meta_list) => meta_list.tokens = tokens,
+            Meta::Path(path) => {
+                if !nested_metas.is_empty() {
+                    attr.meta = util::meta_list(path.clone(), tokens);
+                }
+            }
+            // `AttrEditor::new` rejects name-value attributes.
+            Meta::NameValue(_) => {}
+        }
+
+        attr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Attribute, Meta};
+
+    use super::AttrEditor;
+
+    #[test]
+    fn new_returns_error_for_name_value_attribute() {
+        let attr: Attribute = parse_quote!(#[my::derive = "value"]);
+
+        assert!(AttrEditor::new(attr).is_err());
+    }
+
+    #[test]
+    fn finish_returns_attribute_unchanged_when_not_edited() {
+        let attr: Attribute = parse_quote!(#[my::derive(a, b = 1)]);
+
+        let attr_edited = AttrEditor::new(attr.clone())
+            .expect("Expected attribute to be parsed.")
+            .finish();
+
+        let attr_expected: Attribute = parse_quote!(#[my::derive(a, b = 1)]);
+        assert_eq!(attr_expected, attr_edited);
+    }
+
+    #[test]
+    fn push_converts_path_attribute_to_list() {
+        let attr: Attribute = parse_quote!(#[my::derive]);
+
+        let attr = AttrEditor::new(attr)
+            .expect("Expected attribute to be parsed.")
+            .push(parse_quote!(a))
+            .finish();
+
+        let attr_expected: Attribute = parse_quote!(#[my::derive(a)]);
+        assert_eq!(attr_expected, attr);
+    }
+
+    #[test]
+    fn finish_retains_path_attribute_when_empty() {
+        let attr: Attribute = parse_quote!(#[my::derive]);
+
+        let attr = AttrEditor::new(attr)
+            .expect("Expected attribute to be parsed.")
+            .finish();
+
+        let attr_expected: Attribute = parse_quote!(#[my::derive]);
+        assert_eq!(attr_expected, attr);
+    }
+
+    #[test]
+    fn retain_keeps_matching_metas_and_preserves_delimiter() {
+        let attr: Attribute = parse_quote!(#[my::derive[a, b(c), d = 1]]);
+
+        let attr_editor = AttrEditor::new(attr)
+            .expect("Expected attribute to be parsed.")
+            .retain(|meta| !matches!(meta, Meta::Path(_)));
+
+        let meta_b: Meta = parse_quote!(b(c));
+        let meta_d: Meta = parse_quote!(d = 1);
+        assert_eq!(&[meta_b, meta_d], attr_editor.nested_metas());
+
+        let attr_expected: Attribute = parse_quote!(#[my::derive[b(c), d = 1]]);
+        assert_eq!(attr_expected, attr_editor.finish());
+    }
+}
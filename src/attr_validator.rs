@@ -0,0 +1,311 @@
+use syn::{Attribute, Data, DataEnum, DeriveInput, Error, Path};
+
+use crate::{field_context, util, Diagnostics};
+
+/// Validates that `#[namespace(..)]` attributes only use known parameters.
+///
+/// The allowed parameters are declared separately for the item, its variants,
+/// and its fields. Validation walks each of these, and reports every unknown
+/// parameter together. When an unknown parameter is similar to an allowed one,
+/// the error suggests the allowed parameter.
+///
+/// Only the top level parameters are checked, i.e. `rename` in
+/// `#[namespace(rename = "a")]` and `bound` in `#[namespace(bound(Clone))]`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::AttrValidator;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(rename_all = "snake_case")]
+///     pub struct MyStruct {
+///         #[my::derive(renme = "b")]
+///         a: u32,
+///     }
+/// };
+///
+/// let attr_validator = AttrValidator::new(parse_quote!(my::derive))
+///     .item_keys(&["rename_all"])
+///     .field_keys(&["skip", "rename", "default"]);
+///
+/// let error = attr_validator.validate(&ast).unwrap_err();
+/// assert_eq!(
+///     "Unknown parameter `renme` for `#[my::derive(..)]`, did you mean `rename`?",
+///     error.to_string()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct AttrValidator {
+    /// The namespace of the attributes to validate.
+    namespace: Path,
+    /// Parameters allowed on the item.
+    item_keys: Vec<String>,
+    /// Parameters allowed on enum variants.
+    variant_keys: Vec<String>,
+    /// Parameters allowed on fields.
+    field_keys: Vec<String>,
+}
+
+impl AttrValidator {
+    /// Returns a new `AttrValidator` that allows no parameters.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the attributes to validate.
+    pub fn new(namespace: Path) -> Self {
+        Self {
+            namespace,
+            item_keys: Vec::new(),
+            variant_keys: Vec::new(),
+            field_keys: Vec::new(),
+        }
+    }
+
+    /// Allows the given parameters on the item.
+    pub fn item_keys(mut self, keys: &[&str]) -> Self {
+        self.item_keys
+            .extend(keys.iter().map(|key| String::from(*key)));
+        self
+    }
+
+    /// Allows the given parameters on enum variants.
+    pub fn variant_keys(mut self, keys: &[&str]) -> Self {
+        self.variant_keys
+            .extend(keys.iter().map(|key| String::from(*key)));
+        self
+    }
+
+    /// Allows the given parameters on fields, including fields of enum
+    /// variants.
+    pub fn field_keys(mut self, keys: &[&str]) -> Self {
+        self.field_keys
+            .extend(keys.iter().map(|key| String::from(*key)));
+        self
+    }
+
+    /// Returns an error for each unknown parameter in the item's attributes.
+    ///
+    /// Attributes that cannot be parsed are also reported.
+    ///
+    /// # Parameters
+    ///
+    /// * `ast`: The item to validate.
+    ///
+    /// # Errors
+    ///
+    /// Returns the combined errors for every unknown parameter and malformed
+    /// attribute, each spanned at the offending parameter or attribute.
+    pub fn validate(&self, ast: &DeriveInput) -> syn::Result<()> {
+        let mut diagnostics = Diagnostics::new();
+
+        self.validate_attrs(&ast.attrs, &self.item_keys, &mut diagnostics);
+        if let Data::Enum(DataEnum { variants, .. }) = &ast.data {
+            variants.iter().for_each(|variant| {
+                self.validate_attrs(&variant.attrs, &self.variant_keys, &mut diagnostics)
+            });
+        }
+        field_context::field_contexts(&ast.data).for_each(|field_context| {
+            self.validate_attrs(
+                &field_context.field.attrs,
+                &self.field_keys,
+                &mut diagnostics,
+            )
+        });
+
+        diagnostics.error_or(())
+    }
+
+    /// Adds an error for each unknown parameter in the attributes.
+    fn validate_attrs(
+        &self,
+        attrs: &[Attribute],
+        allowed_keys: &[String],
+        diagnostics: &mut Diagnostics,
+    ) {
+        let nested_metas = attrs
+            .iter()
+            .filter(|attr| attr.path() == &self.namespace)
+            .filter_map(|attr| diagnostics.check(util::parse_nested_metas(attr)))
+            .flatten()
+            .collect::<Vec<_>>();
+
+        nested_metas
+            .iter()
+            .filter(|meta| !allowed_keys.contains(&util::format_path(meta.path())))
+            .for_each(|meta| {
+                diagnostics.push(unknown_parameter_error(
+                    meta.path(),
+                    &format!("#[{}(..)]", util::format_path(&self.namespace)),
+                    allowed_keys,
+                ))
+            });
+    }
+}
+
+/// Returns an error for a parameter that is not one of the allowed keys.
+///
+/// The error suggests the closest allowed key, if any is similar enough.
+///
+/// # Parameters
+///
+/// * `key`: Path of the unknown parameter.
+/// * `attribute`: The attribute the parameter is in, e.g. `#[namespace(..)]`.
+/// * `allowed_keys`: The allowed parameters.
+pub(crate) fn unknown_parameter_error<S>(key: &Path, attribute: &str, allowed_keys: &[S]) -> Error
+where
+    S: AsRef<str>,
+{
+    let key_string = util::format_path(key);
+    let message = match closest_match(&key_string, allowed_keys) {
+        Some(suggestion) => format!(
+            "Unknown parameter `{key_string}` for `{attribute}`, did you mean \
+             `{suggestion}`?"
+        ),
+        None if allowed_keys.is_empty() => format!(
+            "Unknown parameter `{key_string}` for `{attribute}`, no parameters are \
+             supported here."
+        ),
+        None => {
+            let expected = allowed_keys
+                .iter()
+                .map(|allowed_key| format!("`{}`", allowed_key.as_ref()))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "Unknown parameter `{key_string}` for `{attribute}`, expected one of: \
+                 {expected}."
+            )
+        }
+    };
+
+    Error::new_spanned(key, message)
+}
+
+/// Returns the candidate most similar to `key`, if any is similar enough to be
+/// a likely typo.
+fn closest_match<'c, S>(key: &str, candidates: &'c [S]) -> Option<&'c str>
+where
+    S: AsRef<str>,
+{
+    candidates
+        .iter()
+        .map(AsRef::as_ref)
+        .map(|candidate| (candidate, edit_distance(key, candidate)))
+        .filter(|(candidate, distance)| *distance <= std::cmp::max(1, candidate.len() / 3))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars = b.chars().collect::<Vec<char>>();
+    let mut distances = (0..=b_chars.len()).collect::<Vec<usize>>();
+
+    a.chars().enumerate().for_each(|(a_index, a_char)| {
+        let mut distance_diagonal = distances[0];
+        distances[0] = a_index + 1;
+
+        b_chars.iter().enumerate().for_each(|(b_index, b_char)| {
+            let distance_above = distances[b_index + 1];
+            let substitution_cost = usize::from(a_char != *b_char);
+            distances[b_index + 1] = (distance_diagonal + substitution_cost)
+                .min(distance_above + 1)
+                .min(distances[b_index] + 1);
+            distance_diagonal = distance_above;
+        });
+    });
+
+    distances[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::{edit_distance, AttrValidator};
+
+    fn messages(error: syn::Error) -> Vec<String> {
+        error.into_iter().map(|error| error.to_string()).collect()
+    }
+
+    #[test]
+    fn validate_returns_ok_when_all_parameters_known() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive(rename_all = "snake_case")]
+            enum MyEnum {
+                #[my::derive(skip)]
+                A {
+                    #[my::derive(default, rename = "b")]
+                    a: u32,
+                },
+            }
+        };
+        let attr_validator = AttrValidator::new(parse_quote!(my::derive))
+            .item_keys(&["rename_all"])
+            .variant_keys(&["skip"])
+            .field_keys(&["default", "rename"]);
+
+        assert!(attr_validator.validate(&ast).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_every_unknown_parameter() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive(skip)]
+            enum MyEnum {
+                #[my::derive(skp)]
+                A(#[my::derive(unknown)] u32),
+                #[other(skp)]
+                B,
+            }
+        };
+        let attr_validator = AttrValidator::new(parse_quote!(my::derive))
+            .variant_keys(&["skip"])
+            .field_keys(&["default", "rename"]);
+
+        let error = attr_validator
+            .validate(&ast)
+            .expect_err("Expected unknown parameters to be reported.");
+
+        assert_eq!(
+            vec![
+                "Unknown parameter `skip` for `#[my::derive(..)]`, no parameters are supported \
+                 here.",
+                "Unknown parameter `skp` for `#[my::derive(..)]`, did you mean `skip`?",
+                "Unknown parameter `unknown` for `#[my::derive(..)]`, expected one of: \
+                 `default`, `rename`.",
+            ],
+            messages(error)
+        );
+    }
+
+    #[test]
+    fn validate_reports_malformed_attributes() {
+        let ast: DeriveInput = parse_quote! {
+            struct MyStruct {
+                #[my::derive(rename = )]
+                a: u32,
+            }
+        };
+        let attr_validator = AttrValidator::new(parse_quote!(my::derive)).field_keys(&["rename"]);
+
+        let error = attr_validator
+            .validate(&ast)
+            .expect_err("Expected malformed attribute to be reported.");
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse `#[my::derive(..)]` arguments:"));
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(0, edit_distance("rename", "rename"));
+        assert_eq!(1, edit_distance("renme", "rename"));
+        assert_eq!(1, edit_distance("skip", "skp"));
+        assert_eq!(1, edit_distance("skip", "slip"));
+        assert_eq!(6, edit_distance("", "rename"));
+    }
+}
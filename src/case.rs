@@ -0,0 +1,223 @@
+/// Letter case conventions for converting identifiers.
+///
+/// The words of the input are split on `_`, `-`, and lowercase to uppercase
+/// transitions, so `my_struct`, `my-struct`, and `MyStruct` each have the
+/// words `my` and `struct`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::Case;
+///
+/// assert_eq!("my_http_server", Case::Snake.convert("MyHTTPServer"));
+/// assert_eq!("MY-VARIANT", Case::ScreamingKebab.convert("MyVariant"));
+/// assert_eq!("myVariant", Case::Camel.convert("my_variant"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// `lowercase`, words joined without a separator.
+    Lower,
+    /// `UPPERCASE`, words joined without a separator.
+    Upper,
+    /// `PascalCase`.
+    Pascal,
+    /// `camelCase`.
+    Camel,
+    /// `snake_case`.
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`.
+    ScreamingSnake,
+    /// `kebab-case`.
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`.
+    ScreamingKebab,
+}
+
+/// Each case with its `rename_all` rule, as spelt by `serde`.
+const RULES: [(&str, Case); 8] = [
+    ("lowercase", Case::Lower),
+    ("UPPERCASE", Case::Upper),
+    ("PascalCase", Case::Pascal),
+    ("camelCase", Case::Camel),
+    ("snake_case", Case::Snake),
+    ("SCREAMING_SNAKE_CASE", Case::ScreamingSnake),
+    ("kebab-case", Case::Kebab),
+    ("SCREAMING-KEBAB-CASE", Case::ScreamingKebab),
+];
+
+impl Case {
+    /// Returns the case for a `rename_all` rule, e.g. `"snake_case"`.
+    ///
+    /// The rules are spelt the same way as `serde`'s, which is the name of the
+    /// case in that case.
+    ///
+    /// # Parameters
+    ///
+    /// * `rule`: The rule, e.g. `"SCREAMING-KEBAB-CASE"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::Case;
+    ///
+    /// assert_eq!(Some(Case::Kebab), Case::from_rule("kebab-case"));
+    /// assert_eq!(None, Case::from_rule("Kebab"));
+    /// ```
+    pub fn from_rule(rule: &str) -> Option<Case> {
+        RULES
+            .iter()
+            .find(|(rule_candidate, _)| *rule_candidate == rule)
+            .map(|(_, case)| *case)
+    }
+
+    /// Returns the `rename_all` rule for this case, e.g. `"snake_case"`.
+    pub fn rule(self) -> &'static str {
+        RULES
+            .iter()
+            .find(|(_, case)| *case == self)
+            .map(|(rule, _)| *rule)
+            .expect("Expected every case to have a rule.")
+    }
+
+    /// Returns the `rename_all` rules for all cases.
+    pub fn rules() -> impl Iterator<Item = &'static str> {
+        RULES.iter().map(|(rule, _)| *rule)
+    }
+
+    /// Returns the input converted to this case.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The string to convert, e.g. an identifier.
+    pub fn convert(self, input: &str) -> String {
+        let words = words(input);
+
+        match self {
+            Case::Lower => words.concat(),
+            Case::Upper => words.concat().to_uppercase(),
+            Case::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Case::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Case::Snake => words.join("_"),
+            Case::ScreamingSnake => words.join("_").to_uppercase(),
+            Case::Kebab => words.join("-"),
+            Case::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+/// Returns the lowercase words of the input.
+fn words(input: &str) -> Vec<String> {
+    let chars = input.chars().collect::<Vec<char>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    chars.iter().enumerate().for_each(|(index, c)| {
+        if *c == '_' || *c == '-' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            return;
+        }
+
+        if c.is_uppercase() && !word.is_empty() {
+            let prev_is_lower_or_digit = chars
+                .get(index.wrapping_sub(1))
+                .map(|prev| prev.is_lowercase() || prev.is_ascii_digit())
+                .unwrap_or(false);
+            // The last uppercase letter of an acronym starts the next word, e.g.
+            // `HTTPServer` is `HTTP` and `Server`.
+            let next_is_lower = chars
+                .get(index + 1)
+                .map(|next| next.is_lowercase())
+                .unwrap_or(false);
+            if prev_is_lower_or_digit || next_is_lower {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+
+        word.extend(c.to_lowercase());
+    });
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Returns the word with its first letter in uppercase.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Case;
+
+    #[test]
+    fn convert_splits_words_from_each_input_case() {
+        [
+            "my_variant",
+            "my-variant",
+            "MyVariant",
+            "myVariant",
+            "MY_VARIANT",
+        ]
+        .iter()
+        .for_each(|input| assert_eq!("my_variant", Case::Snake.convert(input), "{input}"));
+    }
+
+    #[test]
+    fn convert_splits_acronyms_and_digits() {
+        assert_eq!("http_server", Case::Snake.convert("HTTPServer"));
+        assert_eq!("utf8_string", Case::Snake.convert("Utf8String"));
+        assert_eq!("io", Case::Snake.convert("IO"));
+    }
+
+    #[test]
+    fn convert_returns_each_case() {
+        let cases = [
+            (Case::Lower, "myvariant"),
+            (Case::Upper, "MYVARIANT"),
+            (Case::Pascal, "MyVariant"),
+            (Case::Camel, "myVariant"),
+            (Case::Snake, "my_variant"),
+            (Case::ScreamingSnake, "MY_VARIANT"),
+            (Case::Kebab, "my-variant"),
+            (Case::ScreamingKebab, "MY-VARIANT"),
+        ];
+
+        cases
+            .iter()
+            .for_each(|(case, expected)| assert_eq!(*expected, case.convert("MyVariant")));
+    }
+
+    #[test]
+    fn from_rule_returns_case_for_each_rule() {
+        Case::rules().for_each(|rule| {
+            let case = Case::from_rule(rule).expect("Expected rule to be recognized.");
+            assert_eq!(rule, case.rule());
+            assert_eq!(rule, case.convert(rule), "{rule}");
+        });
+    }
+
+    #[test]
+    fn from_rule_returns_none_for_unknown_rule() {
+        assert_eq!(None, Case::from_rule("snake"));
+        assert_eq!(None, Case::from_rule("Snake_Case"));
+    }
+}
@@ -0,0 +1,18 @@
+/// How to obtain field values from an existing instance.
+///
+/// Used by [`FieldsExt::construction_form_from`].
+///
+/// [`FieldsExt::construction_form_from`]: crate::FieldsExt::construction_form_from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloneStrategy {
+    /// Clones each field, e.g. `source.field.clone()`.
+    Clone,
+    /// Copies each field, e.g. `source.field`.
+    ///
+    /// The fields must be `Copy`, but the source may be borrowed.
+    Copy,
+    /// Moves each field out of the source, e.g. `source.field`.
+    ///
+    /// The source must be owned.
+    Move,
+}
@@ -0,0 +1,537 @@
+//! Recognizers for commonly used helper attribute tags.
+//!
+//! These follow the de-facto standard vocabulary used by crates such as
+//! `serde`, so that derives can support the following without defining their
+//! own grammar:
+//!
+//! * `#[namespace(skip)]`
+//! * `#[namespace(rename = "name")]`
+//! * `#[namespace(rename_all = "snake_case")]`
+//! * `#[namespace(with = "path::to::function")]`
+//! * `#[namespace(transparent)]`
+
+use syn::{
+    ext::IdentExt, parse_quote, DataEnum, DeriveInput, Error, Expr, ExprLit, Field, Ident, Lit,
+    LitStr, Meta, Path, Variant,
+};
+
+use crate::{util, Case, DeriveInputExt, Diagnostics, FieldExt};
+
+/// Returns whether the field is tagged with `#[namespace(skip)]`.
+///
+/// # Parameters
+///
+/// * `field`: The field to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+pub fn is_skipped(field: &Field, namespace: &Path) -> bool {
+    field.contains_tag(namespace, &parse_quote!(skip))
+}
+
+/// Returns the ident from `#[namespace(rename = "name")]`.
+///
+/// The unquoted form `#[namespace(rename = name)]` is also supported. A
+/// keyword such as `#[namespace(rename = "type")]` is returned as a raw
+/// identifier, `r#type`.
+///
+/// Returns `Ok(None)` if there is no `rename` parameter.
+///
+/// # Parameters
+///
+/// * `field`: The field to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// * If a `#[namespace]` attribute cannot be parsed, see
+///   [`namespace_nested_metas_checked`].
+/// * If the value is not an identifier, the error is spanned at the parameter.
+/// * If `rename` is specified more than once.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{conventions, DeriveInputStructExt};
+/// use syn::{parse_quote, DeriveInput, Ident};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct {
+///         #[my::derive(rename = "b")]
+///         a: u32,
+///     }
+/// };
+/// let field = ast
+///     .fields()
+///     .iter()
+///     .next()
+///     .expect("Expected field to exist.");
+///
+/// let ident_expected: Ident = parse_quote!(b);
+/// assert_eq!(
+///     Some(ident_expected),
+///     conventions::renamed_ident(field, &parse_quote!(my::derive))?,
+/// );
+/// # Ok::<(), syn::Error>(())
+/// ```
+///
+/// [`namespace_nested_metas_checked`]: crate::namespace_nested_metas_checked
+pub fn renamed_ident(field: &Field, namespace: &Path) -> syn::Result<Option<Ident>> {
+    let rename_metas = util::namespace_nested_metas_checked(&field.attrs, namespace)?
+        .into_iter()
+        .filter(|meta| meta.path().is_ident("rename"));
+    let rename_meta = util::at_most_one(rename_metas, || {
+        format!(
+            "Expected at most one `rename` parameter for `#[{}(..)]`",
+            util::format_path(namespace)
+        )
+    })?;
+
+    rename_meta
+        .map(|rename_meta| match rename_meta {
+            Meta::NameValue(meta_name_value) => match meta_name_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) => lit_str_ident(&lit_str),
+                Expr::Path(expr_path) => expr_path
+                    .path
+                    .get_ident()
+                    .cloned()
+                    .ok_or_else(|| Error::new_spanned(expr_path, "Expected an identifier.")),
+                value => Err(Error::new_spanned(
+                    value,
+                    "Expected an identifier, e.g. `rename = \"name\"`.",
+                )),
+            },
+            meta => Err(Error::new_spanned(meta, "Expected `rename = \"name\"`.")),
+        })
+        .transpose()
+}
+
+/// Returns the identifier in a string literal, as a raw identifier if it is a
+/// keyword.
+fn lit_str_ident(lit_str: &LitStr) -> syn::Result<Ident> {
+    let ident = lit_str.parse_with(Ident::parse_any)?;
+    if lit_str.parse::<Ident>().is_ok() {
+        return Ok(ident);
+    }
+
+    match ident.to_string().as_str() {
+        "_" | "crate" | "self" | "Self" | "super" => Err(Error::new_spanned(
+            lit_str,
+            format!("Expected an identifier, found keyword `{ident}`."),
+        )),
+        name => Ok(Ident::new_raw(name, ident.span())),
+    }
+}
+
+/// Returns the function path from `#[namespace(with = "path::to::function")]`.
+///
+/// See [`FieldExt::tag_fn_path`] for the supported forms.
+///
+/// # Parameters
+///
+/// * `field`: The field to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the parameter if the value is not a path, or if
+/// `with` is specified more than once.
+pub fn with_fn(field: &Field, namespace: &Path) -> syn::Result<Option<Path>> {
+    field.tag_fn_path(namespace, &parse_quote!(with))
+}
+
+/// Returns the case from `#[namespace(rename_all = "rule")]`.
+///
+/// The rules are spelt the same way as `serde`'s, see [`Case::from_rule`].
+///
+/// Returns `Ok(None)` if there is no `rename_all` parameter.
+///
+/// # Parameters
+///
+/// * `input`: The type to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the parameter if the value is not a known rule,
+/// or if `rename_all` is specified more than once.
+pub fn rename_all(input: &DeriveInput, namespace: &Path) -> syn::Result<Option<Case>> {
+    let rename_all_metas = util::namespace_nested_metas_checked(&input.attrs, namespace)?
+        .into_iter()
+        .filter(|meta| meta.path().is_ident("rename_all"));
+    let rename_all_meta = util::at_most_one(rename_all_metas, || {
+        format!(
+            "Expected at most one `rename_all` parameter for `#[{}(..)]`",
+            util::format_path(namespace)
+        )
+    })?;
+
+    rename_all_meta
+        .map(|rename_all_meta| {
+            let rule = util::expect_lit_str(&rename_all_meta)?;
+            Case::from_rule(&rule.value()).ok_or_else(|| {
+                let expected = Case::rules()
+                    .map(|rule| format!("`\"{rule}\"`"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                Error::new_spanned(
+                    &rule,
+                    format!(
+                        "Unknown `rename_all` rule `\"{}\"`, expected one of: {expected}.",
+                        rule.value()
+                    ),
+                )
+            })
+        })
+        .transpose()
+}
+
+/// Returns the name from `#[namespace(rename = "name")]` on a variant.
+///
+/// Unlike [`renamed_ident`], the name may be any string, as variant names are
+/// usually used as string values.
+///
+/// Returns `Ok(None)` if there is no `rename` parameter.
+///
+/// # Parameters
+///
+/// * `variant`: The variant to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the parameter if the value is not a string
+/// literal, or if `rename` is specified more than once.
+pub fn variant_rename(variant: &Variant, namespace: &Path) -> syn::Result<Option<String>> {
+    let rename_metas = util::namespace_nested_metas_checked(&variant.attrs, namespace)?
+        .into_iter()
+        .filter(|meta| meta.path().is_ident("rename"));
+    let rename_meta = util::at_most_one(rename_metas, || {
+        format!(
+            "Expected at most one `rename` parameter for `#[{}(..)]`",
+            util::format_path(namespace)
+        )
+    })?;
+
+    rename_meta
+        .map(|rename_meta| util::expect_lit_str(&rename_meta).map(|lit_str| lit_str.value()))
+        .transpose()
+}
+
+/// Returns each variant with its effective name.
+///
+/// The name is the variant's `#[namespace(rename = "name")]` if present,
+/// otherwise the variant's ident converted to `case`, otherwise the variant's
+/// ident.
+///
+/// # Parameters
+///
+/// * `data_enum`: The enum to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `case`: The case to convert variant idents to, usually from
+///   [`rename_all`].
+///
+/// # Errors
+///
+/// Returns the errors from [`variant_rename`] for every variant, combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::conventions;
+/// use syn::{parse_quote, Data, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(rename_all = "kebab-case")]
+///     enum MyEnum {
+///         FirstVariant,
+///         #[my::derive(rename = "other")]
+///         SecondVariant,
+///     }
+/// };
+///
+/// let ns: Path = parse_quote!(my::derive);
+/// let case = conventions::rename_all(&ast, &ns)?;
+/// if let Data::Enum(data_enum) = &ast.data {
+///     let names = conventions::variant_names(data_enum, &ns, case)?
+///         .into_iter()
+///         .map(|(_variant, name)| name)
+///         .collect::<Vec<String>>();
+///
+///     assert_eq!(vec!["first-variant", "other"], names);
+/// }
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn variant_names<'v>(
+    data_enum: &'v DataEnum,
+    namespace: &Path,
+    case: Option<Case>,
+) -> syn::Result<Vec<(&'v Variant, String)>> {
+    let mut diagnostics = Diagnostics::new();
+
+    let variant_names = data_enum
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            diagnostics
+                .check(variant_rename(variant, namespace))
+                .map(|rename| {
+                    let name = rename.unwrap_or_else(|| {
+                        let ident = variant.ident.to_string();
+                        match case {
+                            Some(case) => case.convert(&ident),
+                            None => ident,
+                        }
+                    });
+                    (variant, name)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    diagnostics.error_or(variant_names)
+}
+
+/// Returns whether the type is tagged with `#[namespace(transparent)]`.
+///
+/// # Parameters
+///
+/// * `input`: The type to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+pub fn transparent(input: &DeriveInput, namespace: &Path) -> bool {
+    input.contains_tag(namespace, &parse_quote!(transparent))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Data, DataEnum, DeriveInput, Fields, FieldsNamed, Ident, Path};
+
+    use super::{
+        is_skipped, rename_all, renamed_ident, transparent, variant_names, variant_rename, with_fn,
+    };
+    use crate::Case;
+
+    fn data_enum(ast: DeriveInput) -> DataEnum {
+        match ast.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => panic!("Expected an enum."),
+        }
+    }
+
+    #[test]
+    fn is_skipped_returns_whether_field_has_skip_tag() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(skip)]
+            pub a: u32,
+            #[my::derive(other)]
+            pub b: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let skipped = fields
+            .iter()
+            .map(|field| is_skipped(field, &parse_quote!(my::derive)))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![true, false], skipped);
+    }
+
+    #[test]
+    fn renamed_ident_returns_ident_for_string_and_ident_forms() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(rename = "renamed")]
+            pub a: u32,
+            #[my::derive(rename = renamed)]
+            pub b: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let ident_expected: Ident = parse_quote!(renamed);
+        fields.iter().try_for_each(|field| {
+            assert_eq!(
+                Some(&ident_expected),
+                renamed_ident(field, &parse_quote!(my::derive))?.as_ref()
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn renamed_ident_returns_raw_ident_for_keyword() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(rename = "type")]
+            pub a: u32,
+            #[my::derive(rename = "r#type")]
+            pub b: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let ident_expected: Ident = parse_quote!(r#type);
+        fields.iter().try_for_each(|field| {
+            assert_eq!(
+                Some(&ident_expected),
+                renamed_ident(field, &parse_quote!(my::derive))?.as_ref()
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn renamed_ident_returns_error_when_value_is_not_ident() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(rename = "not an ident")]
+            pub a: u32,
+            #[my::derive(rename = 1)]
+            pub b: u32,
+            #[my::derive(rename)]
+            pub c: u32,
+            #[my::derive(rename = "a", rename = "b")]
+            pub d: u32,
+            #[my::derive(rename = "self")]
+            pub e: u32,
+            #[my::derive(rename = , skip)]
+            pub f: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        fields.iter().for_each(|field| {
+            let result = renamed_ident(field, &parse_quote!(my::derive));
+            assert!(
+                result.is_err(),
+                "Expected error for field: {:?}",
+                field.ident
+            );
+        });
+    }
+
+    #[test]
+    fn with_fn_returns_path() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(with = "path::to::function")]
+            pub a: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let path_expected: Path = parse_quote!(path::to::function);
+        assert_eq!(
+            Some(path_expected),
+            with_fn(field, &parse_quote!(my::derive))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn transparent_returns_whether_type_has_transparent_tag() {
+        let ast_transparent: DeriveInput = parse_quote! {
+            #[my::derive(transparent)]
+            struct Struct(u32);
+        };
+        let ast_other: DeriveInput = parse_quote! {
+            #[my::derive(other)]
+            struct Struct(u32);
+        };
+
+        assert!(transparent(&ast_transparent, &parse_quote!(my::derive)));
+        assert!(!transparent(&ast_other, &parse_quote!(my::derive)));
+    }
+
+    #[test]
+    fn rename_all_returns_case_for_rule() -> syn::Result<()> {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive(rename_all = "SCREAMING_SNAKE_CASE")]
+            enum MyEnum {}
+        };
+        let ast_none: DeriveInput = parse_quote! {
+            #[my::derive(skip)]
+            enum MyEnum {}
+        };
+
+        assert_eq!(
+            Some(Case::ScreamingSnake),
+            rename_all(&ast, &parse_quote!(my::derive))?
+        );
+        assert_eq!(None, rename_all(&ast_none, &parse_quote!(my::derive))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rename_all_returns_error_for_unknown_rule() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive(rename_all = "snake")]
+            enum MyEnum {}
+        };
+
+        let error = rename_all(&ast, &parse_quote!(my::derive))
+            .expect_err("Expected an error for an unknown rule.");
+
+        assert_eq!(
+            "Unknown `rename_all` rule `\"snake\"`, expected one of: `\"lowercase\"`, \
+             `\"UPPERCASE\"`, `\"PascalCase\"`, `\"camelCase\"`, `\"snake_case\"`, \
+             `\"SCREAMING_SNAKE_CASE\"`, `\"kebab-case\"`, `\"SCREAMING-KEBAB-CASE\"`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn variant_rename_returns_error_when_specified_more_than_once() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[my::derive(rename = "a")]
+                #[my::derive(rename = "b")]
+                A,
+            }
+        });
+        let variant = data_enum
+            .variants
+            .first()
+            .expect("Expected variant to exist.");
+
+        let error = variant_rename(variant, &parse_quote!(my::derive))
+            .expect_err("Expected an error for a repeated `rename`.");
+
+        assert_eq!(
+            "Expected at most one `rename` parameter for `#[my::derive(..)]`, found `rename = \"a\", \
+             rename = \"b\"`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn variant_names_uses_ident_when_no_case() -> syn::Result<()> {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                FirstVariant,
+                #[my::derive(rename = "second variant")]
+                SecondVariant,
+            }
+        });
+
+        let names = variant_names(&data_enum, &parse_quote!(my::derive), None)?
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect::<Vec<String>>();
+
+        assert_eq!(vec!["FirstVariant", "second variant"], names);
+        Ok(())
+    }
+
+    #[test]
+    fn variant_names_reports_every_invalid_rename() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[my::derive(rename = 1)]
+                A,
+                B,
+                #[my::derive(rename = other)]
+                C,
+            }
+        });
+
+        let error = variant_names(&data_enum, &parse_quote!(my::derive), Some(Case::Snake))
+            .expect_err("Expected an error for non-string renames.");
+
+        assert_eq!(2, error.into_iter().count());
+    }
+}
@@ -0,0 +1,229 @@
+//! Conversions between namespace / tag parameters and `darling` inputs.
+//!
+//! This allows crates that use `darling` for typed options to share attribute
+//! parsing with the raw manipulation functions in this crate:
+//!
+//! * [`namespace_nested_metas`] and [`tag_nested_metas`] return the parameters
+//!   of `#[namespace(..)]` and `#[namespace(tag(..))]` as `NestedMeta`s, which
+//!   can be passed to `darling::FromMeta::from_list`.
+//! * [`to_metas`] and [`to_attribute`] convert `NestedMeta`s back.
+//!
+//! This module is only available with the `darling` feature.
+
+use darling::ast::NestedMeta;
+use syn::{parse_quote, Attribute, Meta, Path};
+
+use crate::Diagnostics;
+
+/// Returns the parameters of all `#[namespace(..)]` attributes.
+///
+/// Unlike [`namespace_nested_metas_iter`], literal parameters such as
+/// `#[namespace("value")]` are also returned.
+///
+/// [`namespace_nested_metas_iter`]: crate::namespace_nested_metas_iter
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error if a `#[namespace]` attribute is not a list, or its
+/// parameters cannot be parsed.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use darling::FromMeta;
+/// use proc_macro_roids::darling_interop;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// #[derive(Debug, FromMeta, PartialEq)]
+/// struct Options {
+///     #[darling(default)]
+///     skip: bool,
+///     rename: Option<String>,
+/// }
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(skip)]
+///     #[my::derive(rename = "b")]
+///     struct Struct;
+/// };
+///
+/// let nested_metas =
+///     darling_interop::namespace_nested_metas(&ast.attrs, &parse_quote!(my::derive)).unwrap();
+/// let options = Options::from_list(&nested_metas).unwrap();
+///
+/// let options_expected = Options {
+///     skip: true,
+///     rename: Some(String::from("b")),
+/// };
+/// assert_eq!(options_expected, options);
+/// ```
+pub fn namespace_nested_metas(
+    attrs: &[Attribute],
+    namespace: &Path,
+) -> syn::Result<Vec<NestedMeta>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path() == namespace)
+        .try_fold(Vec::new(), |mut nested_metas, attr| {
+            let meta_list = attr.meta.require_list()?;
+            nested_metas.extend(NestedMeta::parse_meta_list(meta_list.tokens.clone())?);
+            Ok(nested_metas)
+        })
+}
+
+/// Returns the parameters of all `#[namespace(tag(..))]` parameters.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// Returns an error if a `#[namespace]` attribute or `tag` parameter is not a
+/// list, or its parameters cannot be parsed.
+pub fn tag_nested_metas(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<Vec<NestedMeta>> {
+    namespace_nested_metas(attrs, namespace)?
+        .into_iter()
+        .filter_map(|nested_meta| match nested_meta {
+            NestedMeta::Meta(meta) if meta.path() == tag => Some(meta),
+            NestedMeta::Meta(_) | NestedMeta::Lit(_) => None,
+        })
+        .try_fold(Vec::new(), |mut nested_metas, meta| {
+            let meta_list = meta.require_list()?;
+            nested_metas.extend(NestedMeta::parse_meta_list(meta_list.tokens.clone())?);
+            Ok(nested_metas)
+        })
+}
+
+/// Returns the `Meta`s of the given `NestedMeta`s.
+///
+/// # Parameters
+///
+/// * `nested_metas`: The `NestedMeta`s to convert.
+///
+/// # Errors
+///
+/// Returns an error spanned at each literal, as literals are not `Meta`s.
+pub fn to_metas(nested_metas: &[NestedMeta]) -> syn::Result<Vec<Meta>> {
+    let mut diagnostics = Diagnostics::new();
+    let metas = nested_metas
+        .iter()
+        .filter_map(|nested_meta| match nested_meta {
+            NestedMeta::Meta(meta) => Some(meta.clone()),
+            NestedMeta::Lit(lit) => {
+                diagnostics.push_spanned(lit, "Expected a meta item, found a literal.");
+                None
+            }
+        })
+        .collect::<Vec<Meta>>();
+
+    diagnostics.error_or(metas)
+}
+
+/// Returns a `#[namespace(..)]` attribute with the given `NestedMeta`s.
+///
+/// # Parameters
+///
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `nested_metas`: The parameters of the attribute.
+pub fn to_attribute(namespace: &Path, nested_metas: &[NestedMeta]) -> Attribute {
+    parse_quote!(#[#namespace(#(#nested_metas),*)])
+}
+
+#[cfg(test)]
+mod tests {
+    use darling::ast::NestedMeta;
+    use quote::quote;
+    use syn::{parse_quote, Attribute, DeriveInput, Meta};
+
+    use super::{namespace_nested_metas, tag_nested_metas, to_attribute, to_metas};
+
+    #[test]
+    fn namespace_nested_metas_includes_literals() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive("value", skip)]
+            #[other(a)]
+            #[my::derive(rename = "b")]
+            struct Struct;
+        };
+
+        let nested_metas = namespace_nested_metas(&ast.attrs, &parse_quote!(my::derive))
+            .expect("Expected attributes to be parsed.");
+
+        assert_eq!(
+            quote!("value" skip rename = "b").to_string(),
+            quote!(#(#nested_metas)*).to_string()
+        );
+    }
+
+    #[test]
+    fn namespace_nested_metas_returns_error_for_non_list_attribute() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive]
+            struct Struct;
+        };
+
+        assert!(namespace_nested_metas(&ast.attrs, &parse_quote!(my::derive)).is_err());
+    }
+
+    #[test]
+    fn tag_nested_metas_returns_tag_parameters() {
+        let ast: DeriveInput = parse_quote! {
+            #[my::derive(tag(a, "b"), other(c))]
+            #[my::derive(tag(d = 1))]
+            struct Struct;
+        };
+
+        let nested_metas =
+            tag_nested_metas(&ast.attrs, &parse_quote!(my::derive), &parse_quote!(tag))
+                .expect("Expected attributes to be parsed.");
+
+        assert_eq!(
+            quote!(a "b" d = 1).to_string(),
+            quote!(#(#nested_metas)*).to_string()
+        );
+    }
+
+    #[test]
+    fn to_metas_returns_error_for_literals() {
+        let nested_metas = NestedMeta::parse_meta_list(quote!(a, "b", c = 1, 2))
+            .expect("Expected nested metas to be parsed.");
+
+        let error = to_metas(&nested_metas).expect_err("Expected literals to be reported.");
+        assert_eq!(2, error.into_iter().count());
+
+        let nested_metas = NestedMeta::parse_meta_list(quote!(a, c = 1))
+            .expect("Expected nested metas to be parsed.");
+        let metas_expected: Vec<Meta> = vec![parse_quote!(a), parse_quote!(c = 1)];
+        assert_eq!(
+            metas_expected,
+            to_metas(&nested_metas).expect("Expected metas to be returned.")
+        );
+    }
+
+    #[test]
+    fn to_attribute_round_trips_nested_metas() {
+        let attr_expected: Attribute = parse_quote!(#[my::derive(a, "b", c = 1)]);
+        let nested_metas = namespace_nested_metas(
+            std::slice::from_ref(&attr_expected),
+            &parse_quote!(my::derive),
+        )
+        .expect("Expected attribute to be parsed.");
+
+        assert_eq!(
+            attr_expected,
+            to_attribute(&parse_quote!(my::derive), &nested_metas)
+        );
+    }
+}
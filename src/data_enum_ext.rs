@@ -0,0 +1,1135 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, spanned::Spanned, DataEnum, Error, Expr, ExprLit, ExprUnary, Fields, Ident, Lit,
+    LitInt, LitStr, Path, UnOp, Variant,
+};
+
+use crate::{conventions, util, Case, Diagnostics, ErrorBuilder, FieldsExt, VariantExt};
+
+/// Functions to make it ergonomic to work with enum variants.
+pub trait DataEnumExt {
+    /// Returns the variant tagged with `#[namespace(tag)]`, if any.
+    ///
+    /// This is for optional singleton markers, such as a
+    /// `#[my::derive(default)]` variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if more than one variant is tagged. The error is
+    /// spanned at the second tagged variant's tag, with a note at each other
+    /// tagged variant's tag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A,
+    ///         #[my::derive(default)]
+    ///         B,
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let variant = data_enum
+    ///         .at_most_one_with_tag(&parse_quote!(my::derive), &parse_quote!(default))?
+    ///         .expect("Expected a tagged variant.");
+    ///     assert_eq!("B", variant.ident.to_string());
+    /// }
+    /// # Ok::<(), syn::Error>(())
+    /// ```
+    fn at_most_one_with_tag(&self, namespace: &Path, tag: &Path) -> syn::Result<Option<&Variant>>;
+
+    /// Returns an iterator over the variants tagged with `#[namespace(tag)]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use syn::{parse_quote, Data, DeriveInput, Path, Variant};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A,
+    ///         #[my::derive(skip)]
+    ///         B,
+    ///         C,
+    ///     }
+    /// };
+    ///
+    /// let ns: Path = parse_quote!(my::derive);
+    /// let skip: Path = parse_quote!(skip);
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let variant_names = |variants: Vec<&Variant>| {
+    ///         variants
+    ///             .iter()
+    ///             .map(|variant| variant.ident.to_string())
+    ///             .collect::<Vec<String>>()
+    ///     };
+    ///
+    ///     let skipped = data_enum.variants_with_tag(&ns, &skip).collect();
+    ///     let not_skipped = data_enum.variants_without_tag(&ns, &skip).collect();
+    ///     assert_eq!(vec!["B"], variant_names(skipped));
+    ///     assert_eq!(vec!["A", "C"], variant_names(not_skipped));
+    /// }
+    /// ```
+    fn variants_with_tag<'f>(
+        &'f self,
+        namespace: &'f Path,
+        tag: &'f Path,
+    ) -> impl Iterator<Item = &'f Variant> + 'f;
+
+    /// Returns an iterator over the variants not tagged with
+    /// `#[namespace(tag)]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    fn variants_without_tag<'f>(
+        &'f self,
+        namespace: &'f Path,
+        tag: &'f Path,
+    ) -> impl Iterator<Item = &'f Variant> + 'f;
+
+    /// Returns true if every variant is a unit variant, i.e. the enum is
+    /// C-like.
+    ///
+    /// This is also true for an enum with no variants.
+    fn is_fieldless(&self) -> bool;
+
+    /// Returns true if any variant has fields.
+    fn has_data_variants(&self) -> bool;
+
+    /// Returns an iterator over the unit variants, in order.
+    fn unit_variants(&self) -> impl Iterator<Item = &Variant> + '_;
+
+    /// Returns an error if any variant has fields.
+    ///
+    /// This is for derives that only support C-like enums, such as `FromStr`
+    /// or `repr` conversions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for each variant with fields, spanned at its fields.
+    /// All errors are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A,
+    ///         B(u32),
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let error = data_enum.check_fieldless().unwrap_err();
+    ///     assert_eq!(
+    ///         "Fields are not supported on variant `B`.",
+    ///         error.to_string()
+    ///     );
+    /// }
+    /// ```
+    fn check_fieldless(&self) -> syn::Result<()>;
+
+    /// Returns each variant's name and discriminant expression.
+    ///
+    /// Variants without an explicit discriminant are numbered from the
+    /// previous variant, the same way as the compiler:
+    ///
+    /// * If there is no previous explicit discriminant: `0`, `1`, etc.
+    /// * If the previous explicit discriminant is an integer literal: the next
+    ///   integer literal, keeping the literal's suffix.
+    /// * Otherwise: `(#previous) + 1`, `(#previous) + 2`, etc.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use syn::{parse_quote, Data, DeriveInput, Expr};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A,
+    ///         B = 5,
+    ///         C,
+    ///         D = OFFSET,
+    ///         E,
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let discriminants = data_enum
+    ///         .variant_discriminants()
+    ///         .into_iter()
+    ///         .map(|(_ident, expr)| expr)
+    ///         .collect::<Vec<Expr>>();
+    ///
+    ///     let expected: Vec<Expr> = vec![
+    ///         parse_quote!(0),
+    ///         parse_quote!(5),
+    ///         parse_quote!(6),
+    ///         parse_quote!(OFFSET),
+    ///         parse_quote!((OFFSET) + 1),
+    ///     ];
+    ///     assert_eq!(expected, discriminants);
+    /// }
+    /// ```
+    fn variant_discriminants(&self) -> Vec<(Ident, Expr)>;
+
+    /// Returns each variant's name and discriminant value.
+    ///
+    /// This is for discriminants that are integer literals, e.g. `3`, `-1`, or
+    /// `0x10u8`. Variants without an explicit discriminant are numbered the
+    /// same way as [`variant_discriminants`](Self::variant_discriminants).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at each explicit discriminant that is not an
+    /// integer literal. All errors are combined.
+    fn discriminant_values(&self) -> syn::Result<Vec<(Ident, i128)>>;
+
+    /// Returns an error if any two variants have the same discriminant value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at each variant that reuses a discriminant,
+    /// with a note at the variant that first used it. Errors from
+    /// [`discriminant_values`](Self::discriminant_values) are also returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A = 1,
+    ///         B = 0,
+    ///         C,
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let error = data_enum.assert_unique_discriminants().unwrap_err();
+    ///     assert_eq!(
+    ///         "Discriminant `1` of variant `C` is already used by variant `A`.",
+    ///         error.to_string()
+    ///     );
+    /// }
+    /// ```
+    fn assert_unique_discriminants(&self) -> syn::Result<()>;
+
+    /// Returns true if the discriminant values do not form a contiguous range.
+    ///
+    /// For example, `A = 0, B = 1, C = 3` has a gap at `2`. Derives may use
+    /// this to decide whether a `TryFrom` conversion can use a range check.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors from
+    /// [`discriminant_values`](Self::discriminant_values).
+    fn has_discriminant_gaps(&self) -> syn::Result<bool>;
+
+    /// Returns match arms over all variants, with each variant's fields bound
+    /// to variables.
+    ///
+    /// Each arm is `#construction_form => { #body }`, where the pattern is the
+    /// variant's [`VariantExt::construction_form`]:
+    ///
+    /// * Unit variants: `MyEnum::Variant`
+    /// * Tuple variants: `MyEnum::Variant(_0, _1,)`
+    /// * Named variants: `MyEnum::Variant { a, b, }`
+    ///
+    /// # Parameters
+    ///
+    /// * `enum_path`: Path to the enum, e.g. `Self` or `MyEnum`.
+    /// * `body_fn`: Returns the arm body, given the variant, its fields, and
+    ///   the variables its fields are bound to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DataEnumExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         A,
+    ///         B(u32, u32),
+    ///         C { c: u32 },
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let match_arms = data_enum.match_arms(&parse_quote!(Self), |_, _, binding_idents| {
+    ///         quote!(0 #(+ #binding_idents)*)
+    ///     });
+    ///
+    ///     let expected = quote! {
+    ///         Self::A => { 0 }
+    ///         Self::B(_0, _1,) => { 0 + _0 + _1 }
+    ///         Self::C { c, } => { 0 + c }
+    ///     };
+    ///     assert_eq!(expected.to_string(), match_arms.to_string());
+    /// }
+    /// ```
+    ///
+    /// [`VariantExt::construction_form`]: crate::VariantExt::construction_form
+    fn match_arms<F>(&self, enum_path: &Path, body_fn: F) -> TokenStream
+    where
+        F: FnMut(&Variant, &Fields, &[Ident]) -> TokenStream;
+
+    /// Returns match arms from each unit variant to its name as a string
+    /// literal, e.g. `MyEnum::A => "a",`.
+    ///
+    /// This is for `Display`, `AsRef<str>`, or `as_str` style conversions.
+    /// Each variant's name is resolved by [`conventions::variant_names`], so
+    /// `#[namespace(rename = "name")]` overrides `case`.
+    ///
+    /// # Parameters
+    ///
+    /// * `enum_path`: Path to the enum, e.g. `Self` or `MyEnum`.
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `case`: The case to convert variant idents to, usually from
+    ///   [`conventions::rename_all`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    ///
+    /// * Any variant has fields.
+    /// * Any variant's `rename` is not a string literal.
+    /// * Two variants have the same name.
+    ///
+    /// All errors are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::{Case, DataEnumExt};
+    /// use quote::quote;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         FirstVariant,
+    ///         #[my::derive(rename = "other")]
+    ///         SecondVariant,
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let variant_to_str_arms = data_enum.variant_to_str_arms(
+    ///         &parse_quote!(Self),
+    ///         &parse_quote!(my::derive),
+    ///         Some(Case::Snake),
+    ///     )?;
+    ///     let tokens = quote! {
+    ///         match self {
+    ///             #variant_to_str_arms
+    ///         }
+    ///     };
+    ///
+    ///     let expected = quote! {
+    ///         match self {
+    ///             Self::FirstVariant => "first_variant",
+    ///             Self::SecondVariant => "other",
+    ///         }
+    ///     };
+    ///     assert_eq!(expected.to_string(), tokens.to_string());
+    /// }
+    /// # Ok::<(), syn::Error>(())
+    /// ```
+    ///
+    /// [`conventions::rename_all`]: crate::conventions::rename_all
+    /// [`conventions::variant_names`]: crate::conventions::variant_names
+    fn variant_to_str_arms(
+        &self,
+        enum_path: &Path,
+        namespace: &Path,
+        case: Option<Case>,
+    ) -> syn::Result<TokenStream>;
+
+    /// Returns match arms from each unit variant's name as a string literal
+    /// to the variant, e.g. `"a" => MyEnum::A,`.
+    ///
+    /// This is for `FromStr` or `TryFrom<&str>` style conversions. The caller
+    /// adds the arm for unknown strings. Names are resolved the same way as
+    /// [`variant_to_str_arms`](Self::variant_to_str_arms).
+    ///
+    /// # Parameters
+    ///
+    /// * `enum_path`: Path to the enum, e.g. `Self` or `MyEnum`.
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `case`: The case to convert variant idents to, usually from
+    ///   [`conventions::rename_all`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`variant_to_str_arms`](Self::variant_to_str_arms).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::{Case, DataEnumExt};
+    /// use quote::quote;
+    /// use syn::{parse_quote, Data, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum MyEnum {
+    ///         FirstVariant,
+    ///         SecondVariant,
+    ///     }
+    /// };
+    ///
+    /// if let Data::Enum(data_enum) = &ast.data {
+    ///     let str_to_variant_arms = data_enum.str_to_variant_arms(
+    ///         &parse_quote!(Self),
+    ///         &parse_quote!(my::derive),
+    ///         Some(Case::Kebab),
+    ///     )?;
+    ///     let tokens = quote! {
+    ///         match s {
+    ///             #str_to_variant_arms
+    ///             _ => return Err(ParseError),
+    ///         }
+    ///     };
+    ///
+    ///     let expected = quote! {
+    ///         match s {
+    ///             "first-variant" => Self::FirstVariant,
+    ///             "second-variant" => Self::SecondVariant,
+    ///             _ => return Err(ParseError),
+    ///         }
+    ///     };
+    ///     assert_eq!(expected.to_string(), tokens.to_string());
+    /// }
+    /// # Ok::<(), syn::Error>(())
+    /// ```
+    ///
+    /// [`conventions::rename_all`]: crate::conventions::rename_all
+    fn str_to_variant_arms(
+        &self,
+        enum_path: &Path,
+        namespace: &Path,
+        case: Option<Case>,
+    ) -> syn::Result<TokenStream>;
+}
+
+impl DataEnumExt for DataEnum {
+    fn at_most_one_with_tag(&self, namespace: &Path, tag: &Path) -> syn::Result<Option<&Variant>> {
+        let tagged_variants = self
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                util::contains_tag_spanned(&variant.attrs, namespace, tag)
+                    .map(|span| (variant, span))
+            })
+            .collect::<Vec<_>>();
+
+        match tagged_variants.as_slice() {
+            [] => Ok(None),
+            [(variant, _)] => Ok(Some(variant)),
+            [(_, span_first), (_, span_second), tagged_variants_rest @ ..] => {
+                let tag_formatted = format!(
+                    "`#[{}({})]`",
+                    util::format_path(namespace),
+                    util::format_path(tag)
+                );
+                let error_builder = ErrorBuilder::new(
+                    *span_second,
+                    format!("Expected at most one variant with {tag_formatted}."),
+                )
+                .note(*span_first, format!("{tag_formatted} is also used here"));
+                let error_builder =
+                    tagged_variants_rest
+                        .iter()
+                        .fold(error_builder, |error_builder, (_, span)| {
+                            error_builder.note(*span, format!("{tag_formatted} is also used here"))
+                        });
+
+                Err(error_builder.build())
+            }
+        }
+    }
+
+    fn variants_with_tag<'f>(
+        &'f self,
+        namespace: &'f Path,
+        tag: &'f Path,
+    ) -> impl Iterator<Item = &'f Variant> + 'f {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.contains_tag(namespace, tag))
+    }
+
+    fn variants_without_tag<'f>(
+        &'f self,
+        namespace: &'f Path,
+        tag: &'f Path,
+    ) -> impl Iterator<Item = &'f Variant> + 'f {
+        self.variants
+            .iter()
+            .filter(move |variant| !variant.contains_tag(namespace, tag))
+    }
+
+    fn is_fieldless(&self) -> bool {
+        self.variants.iter().all(VariantExt::is_unit)
+    }
+
+    fn has_data_variants(&self) -> bool {
+        !self.is_fieldless()
+    }
+
+    fn unit_variants(&self) -> impl Iterator<Item = &Variant> + '_ {
+        self.variants.iter().filter(|variant| variant.is_unit())
+    }
+
+    fn check_fieldless(&self) -> syn::Result<()> {
+        self.variants
+            .iter()
+            .filter(|variant| !variant.is_unit())
+            .map(|variant| {
+                Error::new_spanned(
+                    &variant.fields,
+                    format!("Fields are not supported on variant `{}`.", variant.ident),
+                )
+            })
+            .collect::<Diagnostics>()
+            .error_or(())
+    }
+
+    fn variant_discriminants(&self) -> Vec<(Ident, Expr)> {
+        let mut previous: Option<(&Expr, u32)> = None;
+
+        self.variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let discriminant = match (&variant.discriminant, previous) {
+                    (Some((_, expr)), _) => {
+                        previous = Some((expr, 0));
+                        expr.clone()
+                    }
+                    (None, Some((expr_previous, offset))) => {
+                        let offset = offset + 1;
+                        previous = Some((expr_previous, offset));
+                        discriminant_following(expr_previous, offset)
+                    }
+                    (None, None) => int_expr(index as i128, "", Span::call_site()),
+                };
+
+                (variant.ident.clone(), discriminant)
+            })
+            .collect()
+    }
+
+    fn discriminant_values(&self) -> syn::Result<Vec<(Ident, i128)>> {
+        let mut diagnostics = Diagnostics::new();
+        let mut value_next = 0i128;
+
+        let values = self
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let value = match &variant.discriminant {
+                    Some((_, expr)) => match int_value(expr) {
+                        Some((value, _suffix)) => value,
+                        None => {
+                            diagnostics.push(Error::new_spanned(
+                                expr,
+                                format!(
+                                    "Expected discriminant of variant `{}` to be an integer \
+                                     literal, found `{}`.",
+                                    variant.ident,
+                                    util::source_text(expr)
+                                ),
+                            ));
+                            return None;
+                        }
+                    },
+                    None => value_next,
+                };
+                value_next = value + 1;
+
+                Some((variant.ident.clone(), value))
+            })
+            .collect::<Vec<(Ident, i128)>>();
+
+        diagnostics.error_or(values)
+    }
+
+    fn assert_unique_discriminants(&self) -> syn::Result<()> {
+        let values = self.discriminant_values()?;
+        let mut first_variants = HashMap::<i128, &Ident>::new();
+
+        values
+            .iter()
+            .filter_map(|(ident, value)| match first_variants.get(value) {
+                Some(ident_first) => Some(
+                    ErrorBuilder::new(
+                        ident.span(),
+                        format!(
+                            "Discriminant `{value}` of variant `{ident}` is already used by \
+                             variant `{ident_first}`."
+                        ),
+                    )
+                    .note(ident_first.span(), "previously specified here")
+                    .build(),
+                ),
+                None => {
+                    first_variants.insert(*value, ident);
+                    None
+                }
+            })
+            .collect::<Diagnostics>()
+            .error_or(())
+    }
+
+    fn has_discriminant_gaps(&self) -> syn::Result<bool> {
+        let mut values = self
+            .discriminant_values()?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<Vec<i128>>();
+        values.sort_unstable();
+        values.dedup();
+
+        Ok(values.windows(2).any(|pair| pair[1] - pair[0] > 1))
+    }
+
+    fn match_arms<F>(&self, enum_path: &Path, mut body_fn: F) -> TokenStream
+    where
+        F: FnMut(&Variant, &Fields, &[Ident]) -> TokenStream,
+    {
+        self.variants
+            .iter()
+            .map(|variant| {
+                let fields = &variant.fields;
+                let pattern_form = variant.construction_form(enum_path);
+                let binding_idents = fields.binding_idents().collect::<Vec<Ident>>();
+                let body = body_fn(variant, fields, &binding_idents);
+
+                quote!(#pattern_form => { #body })
+            })
+            .collect()
+    }
+
+    fn variant_to_str_arms(
+        &self,
+        enum_path: &Path,
+        namespace: &Path,
+        case: Option<Case>,
+    ) -> syn::Result<TokenStream> {
+        variant_name_lits(self, namespace, case).map(|variant_name_lits| {
+            variant_name_lits
+                .iter()
+                .map(|(variant, name)| {
+                    let variant_ident = &variant.ident;
+                    quote!(#enum_path::#variant_ident => #name,)
+                })
+                .collect()
+        })
+    }
+
+    fn str_to_variant_arms(
+        &self,
+        enum_path: &Path,
+        namespace: &Path,
+        case: Option<Case>,
+    ) -> syn::Result<TokenStream> {
+        variant_name_lits(self, namespace, case).map(|variant_name_lits| {
+            variant_name_lits
+                .iter()
+                .map(|(variant, name)| {
+                    let variant_ident = &variant.ident;
+                    quote!(#name => #enum_path::#variant_ident,)
+                })
+                .collect()
+        })
+    }
+}
+
+/// Returns each unit variant with its name as a string literal.
+///
+/// Errors for variants with fields, invalid renames, and duplicate names are
+/// combined.
+fn variant_name_lits<'v>(
+    data_enum: &'v DataEnum,
+    namespace: &Path,
+    case: Option<Case>,
+) -> syn::Result<Vec<(&'v Variant, LitStr)>> {
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.check(data_enum.check_fieldless());
+    let variant_names = diagnostics
+        .check(conventions::variant_names(data_enum, namespace, case))
+        .unwrap_or_default();
+
+    let mut first_variants = HashMap::<&str, &Variant>::new();
+    variant_names
+        .iter()
+        .for_each(|(variant, name)| match first_variants.get(name.as_str()) {
+            Some(variant_first) => diagnostics.push(
+                ErrorBuilder::new(
+                    variant.ident.span(),
+                    format!(
+                        "Variant `{}` has the same name `\"{name}\"` as variant `{}`.",
+                        variant.ident, variant_first.ident
+                    ),
+                )
+                .note(variant_first.ident.span(), "previously specified here")
+                .build(),
+            ),
+            None => {
+                first_variants.insert(name, variant);
+            }
+        });
+
+    let variant_name_lits = variant_names
+        .iter()
+        .map(|(variant, name)| (*variant, LitStr::new(name, variant.ident.span())))
+        .collect::<Vec<_>>();
+
+    diagnostics.error_or(variant_name_lits)
+}
+
+/// Returns the discriminant `offset` after an explicit discriminant.
+fn discriminant_following(expr: &Expr, offset: u32) -> Expr {
+    match int_value(expr) {
+        Some((value, suffix)) => int_expr(value + i128::from(offset), &suffix, expr.span()),
+        None => {
+            let offset = LitInt::new(&offset.to_string(), expr.span());
+            parse_quote!((#expr) + #offset)
+        }
+    }
+}
+
+/// Returns the value and suffix of an integer literal expression, including
+/// negative literals.
+fn int_value(expr: &Expr) -> Option<(i128, String)> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit_int),
+            ..
+        }) => lit_int
+            .base10_parse::<i128>()
+            .ok()
+            .map(|value| (value, String::from(lit_int.suffix()))),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_value(expr).map(|(value, suffix)| (-value, suffix)),
+        _ => None,
+    }
+}
+
+/// Returns an integer literal expression, which is negated if the value is
+/// negative.
+fn int_expr(value: i128, suffix: &str, span: Span) -> Expr {
+    let lit_int = LitInt::new(&format!("{}{}", value.unsigned_abs(), suffix), span);
+    if value < 0 {
+        parse_quote!(-#lit_int)
+    } else {
+        parse_quote!(#lit_int)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, Data, DataEnum, DeriveInput};
+
+    use super::DataEnumExt;
+    use crate::Case;
+
+    fn data_enum(ast: DeriveInput) -> DataEnum {
+        match ast.data {
+            Data::Enum(data_enum) => data_enum,
+            _ => panic!("Expected an enum."),
+        }
+    }
+
+    #[test]
+    fn at_most_one_with_tag_returns_none_when_no_variant_is_tagged() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[other(default)]
+                A,
+                B,
+            }
+        });
+
+        let variant = data_enum
+            .at_most_one_with_tag(&parse_quote!(my::derive), &parse_quote!(default))
+            .expect("Expected no error when no variant is tagged.");
+
+        assert!(variant.is_none());
+    }
+
+    #[test]
+    fn at_most_one_with_tag_returns_error_when_multiple_variants_are_tagged() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[my::derive(default)]
+                A,
+                #[my::derive(default)]
+                B,
+                #[my::derive(default)]
+                C,
+            }
+        });
+
+        let error = data_enum
+            .at_most_one_with_tag(&parse_quote!(my::derive), &parse_quote!(default))
+            .expect_err("Expected an error when multiple variants are tagged.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![
+                "Expected at most one variant with `#[my::derive(default)]`.",
+                "`#[my::derive(default)]` is also used here",
+                "`#[my::derive(default)]` is also used here",
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn variants_with_tag_and_without_tag_partition_variants() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[my::derive(skip)]
+                A,
+                #[other(skip)]
+                B,
+                #[my::derive(default, skip)]
+                C,
+            }
+        });
+        let ns = parse_quote!(my::derive);
+        let tag = parse_quote!(skip);
+
+        let with_tag = data_enum
+            .variants_with_tag(&ns, &tag)
+            .map(|variant| variant.ident.to_string())
+            .collect::<Vec<String>>();
+        let without_tag = data_enum
+            .variants_without_tag(&ns, &tag)
+            .map(|variant| variant.ident.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(vec!["A", "C"], with_tag);
+        assert_eq!(vec!["B"], without_tag);
+    }
+
+    #[test]
+    fn match_arms_is_empty_for_enum_without_variants() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {}
+        });
+
+        let match_arms = data_enum.match_arms(&parse_quote!(MyEnum), |_, _, _| quote!(()));
+
+        assert!(match_arms.is_empty());
+    }
+
+    #[test]
+    fn match_arms_passes_each_variant_to_body_fn() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                A,
+                B(u32),
+                C { c: u32, d: u32 },
+            }
+        });
+
+        let match_arms = data_enum.match_arms(
+            &parse_quote!(my::MyEnum),
+            |variant, fields, binding_idents| {
+                let variant_name = variant.ident.to_string();
+                let field_count = fields.len();
+                quote!(#variant_name, #field_count, [#(#binding_idents),*])
+            },
+        );
+
+        let expected = quote! {
+            my::MyEnum::A => { "A", 0usize, [] }
+            my::MyEnum::B(_0,) => { "B", 1usize, [_0] }
+            my::MyEnum::C { c, d, } => { "C", 2usize, [c, d] }
+        };
+        assert_eq!(expected.to_string(), match_arms.to_string());
+    }
+
+    #[test]
+    fn is_fieldless_returns_true_when_all_variants_are_unit() {
+        let data_enum_unit = data_enum(parse_quote! {
+            enum MyEnum { A, B = 2 }
+        });
+        let data_enum_empty = data_enum(parse_quote! {
+            enum MyEnum {}
+        });
+
+        assert!(data_enum_unit.is_fieldless());
+        assert!(!data_enum_unit.has_data_variants());
+        assert!(data_enum_empty.is_fieldless());
+    }
+
+    #[test]
+    fn has_data_variants_returns_true_when_any_variant_has_fields() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B(), C { c: u32 } }
+        });
+
+        assert!(data_enum.has_data_variants());
+        assert!(!data_enum.is_fieldless());
+    }
+
+    #[test]
+    fn unit_variants_returns_only_unit_variants() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B(u32), C }
+        });
+
+        let variant_names = data_enum
+            .unit_variants()
+            .map(|variant| variant.ident.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(vec!["A", "C"], variant_names);
+    }
+
+    #[test]
+    fn check_fieldless_reports_each_variant_with_fields() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B(u32), C { c: u32 } }
+        });
+
+        let error = data_enum
+            .check_fieldless()
+            .expect_err("Expected an error for variants with fields.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![
+                "Fields are not supported on variant `B`.",
+                "Fields are not supported on variant `C`.",
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn variant_discriminants_keeps_literal_suffix_and_sign() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A = -2i8, B, C, D = 0x10u8, E }
+        });
+
+        let discriminants = data_enum
+            .variant_discriminants()
+            .into_iter()
+            .map(|(ident, expr)| format!("{ident}: {}", quote!(#expr)))
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            vec!["A: - 2i8", "B: - 1i8", "C: 0i8", "D: 0x10u8", "E: 17u8"],
+            discriminants
+        );
+    }
+
+    #[test]
+    fn discriminant_values_numbers_implicit_discriminants() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B = 10, C, D = -1, E }
+        });
+
+        let values = data_enum
+            .discriminant_values()
+            .expect("Expected integer discriminants to be parsed.")
+            .into_iter()
+            .map(|(ident, value)| (ident.to_string(), value))
+            .collect::<Vec<(String, i128)>>();
+
+        assert_eq!(
+            vec![
+                (String::from("A"), 0),
+                (String::from("B"), 10),
+                (String::from("C"), 11),
+                (String::from("D"), -1),
+                (String::from("E"), 0),
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn discriminant_values_returns_error_for_non_literal_discriminant() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A = OFFSET, B }
+        });
+
+        let error = data_enum
+            .discriminant_values()
+            .expect_err("Expected an error for a non-literal discriminant.");
+
+        assert_eq!(
+            "Expected discriminant of variant `A` to be an integer literal, found `OFFSET`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn assert_unique_discriminants_reports_each_duplicate() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B, C = 0, D = 1 }
+        });
+
+        let error = data_enum
+            .assert_unique_discriminants()
+            .expect_err("Expected an error for duplicate discriminants.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![
+                "Discriminant `0` of variant `C` is already used by variant `A`.",
+                "previously specified here",
+                "Discriminant `1` of variant `D` is already used by variant `B`.",
+                "previously specified here",
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn assert_unique_discriminants_returns_ok_when_unique() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A = 3, B = 1, C }
+        });
+
+        assert!(data_enum.assert_unique_discriminants().is_ok());
+    }
+
+    #[test]
+    fn has_discriminant_gaps_returns_whether_values_are_contiguous() {
+        let data_enum_contiguous = data_enum(parse_quote! {
+            enum MyEnum { A = 2, B = 0, C = 1 }
+        });
+        let data_enum_gap = data_enum(parse_quote! {
+            enum MyEnum { A, B, C = 3 }
+        });
+
+        assert!(!data_enum_contiguous.has_discriminant_gaps().unwrap());
+        assert!(data_enum_gap.has_discriminant_gaps().unwrap());
+    }
+
+    #[test]
+    fn to_str_arms_uses_variant_ident_when_no_case() -> syn::Result<()> {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum { A, B }
+        });
+
+        let variant_to_str_arms = data_enum.variant_to_str_arms(
+            &parse_quote!(MyEnum),
+            &parse_quote!(my::derive),
+            None,
+        )?;
+
+        let expected = quote! {
+            MyEnum::A => "A",
+            MyEnum::B => "B",
+        };
+        assert_eq!(expected.to_string(), variant_to_str_arms.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_arms_honors_rename() -> syn::Result<()> {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                #[my::derive(rename = "first")]
+                FirstVariant,
+                SecondVariant,
+            }
+        });
+
+        let str_to_variant_arms = data_enum.str_to_variant_arms(
+            &parse_quote!(Self),
+            &parse_quote!(my::derive),
+            Some(Case::ScreamingSnake),
+        )?;
+
+        let expected = quote! {
+            "first" => Self::FirstVariant,
+            "SECOND_VARIANT" => Self::SecondVariant,
+        };
+        assert_eq!(expected.to_string(), str_to_variant_arms.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn to_str_arms_reports_data_variants_and_duplicate_names() {
+        let data_enum = data_enum(parse_quote! {
+            enum MyEnum {
+                A(u32),
+                MyVariant,
+                #[my::derive(rename = "my_variant")]
+                Other,
+            }
+        });
+
+        let error = data_enum
+            .variant_to_str_arms(
+                &parse_quote!(Self),
+                &parse_quote!(my::derive),
+                Some(Case::Snake),
+            )
+            .expect_err("Expected errors for a data variant and a duplicate name.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![
+                "Fields are not supported on variant `A`.",
+                "Variant `Other` has the same name `\"my_variant\"` as variant `MyVariant`.",
+                "previously specified here",
+            ],
+            messages
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use syn::{
+    fold::Fold, punctuated::Punctuated, Data, DataStruct, DeriveInput, Error, Fields, FieldsNamed,
+    FieldsUnnamed, Meta, Path, Token,
+};
+
+use crate::{
+    derive_input_ext, fields_named_append::ERR_MUST_BE_UNIT_OR_NAMED,
+    fields_unnamed_append::ERR_MUST_BE_UNIT_OR_UNNAMED, util, Diagnostics, FieldsNamedAppend,
+    FieldsUnnamedAppend, StripAttrs,
+};
+
+/// Chainable editor to modify a `DeriveInput`.
+///
+/// Each operation is applied in order. Operations that fail record an error
+/// instead of panicking, and the remaining operations continue to be applied,
+/// so that all errors are reported together by [`finish`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::DeriveInputEditor;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(tag)]
+///     #[derive(Debug)]
+///     struct Struct { a: u32 }
+/// };
+///
+/// let ast = DeriveInputEditor::new(ast)
+///     .append_derives(parse_quote!(Clone, Copy))
+///     .append_named(parse_quote!({ b: i32 }))
+///     .strip_namespace(&parse_quote!(my::derive))
+///     .finish()
+///     .expect("Expected edits to succeed.");
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[derive(Debug, Clone, Copy)]
+///     struct Struct { a: u32, b: i32 }
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+///
+/// [`finish`]: Self::finish
+#[derive(Debug)]
+pub struct DeriveInputEditor {
+    /// The `DeriveInput` being edited.
+    derive_input: DeriveInput,
+    /// Errors from edits that failed.
+    diagnostics: Diagnostics,
+}
+
+impl DeriveInputEditor {
+    /// Returns a new `DeriveInputEditor` for the given `DeriveInput`.
+    pub fn new(derive_input: DeriveInput) -> Self {
+        Self {
+            derive_input,
+            diagnostics: Diagnostics::new(),
+        }
+    }
+
+    /// Appends derives to the list of derives.
+    ///
+    /// Records an error if any of the derives already exist.
+    ///
+    /// See [`DeriveInputExt::append_derives`].
+    ///
+    /// [`DeriveInputExt::append_derives`]: crate::DeriveInputExt::append_derives
+    pub fn append_derives(mut self, derives: Punctuated<Path, Token![,]>) -> Self {
+        if let Err(error) = derive_input_ext::append_derives(&mut self.derive_input, derives) {
+            self.diagnostics.push(error);
+        }
+        self
+    }
+
+    /// Appends named fields to the struct.
+    ///
+    /// Records an error if the `DeriveInput` is not a unit struct or a struct
+    /// with named fields.
+    pub fn append_named(mut self, fields_named: FieldsNamed) -> Self {
+        match &self.derive_input.data {
+            Data::Struct(DataStruct {
+                fields: Fields::Named(..) | Fields::Unit,
+                ..
+            }) => self.derive_input.append_named(fields_named),
+            _ => {
+                let error = Error::new(self.derive_input.ident.span(), ERR_MUST_BE_UNIT_OR_NAMED);
+                self.diagnostics.push(error);
+            }
+        }
+        self
+    }
+
+    /// Appends unnamed fields to the struct.
+    ///
+    /// Records an error if the `DeriveInput` is not a unit struct or a tuple
+    /// struct.
+    pub fn append_unnamed(mut self, fields_unnamed: FieldsUnnamed) -> Self {
+        match &self.derive_input.data {
+            Data::Struct(DataStruct {
+                fields: Fields::Unnamed(..) | Fields::Unit,
+                ..
+            }) => self.derive_input.append_unnamed(fields_unnamed),
+            _ => {
+                let error = Error::new(self.derive_input.ident.span(), ERR_MUST_BE_UNIT_OR_UNNAMED);
+                self.diagnostics.push(error);
+            }
+        }
+        self
+    }
+
+    /// Appends `tag` to the type's `#[namespace(..)]` attribute.
+    ///
+    /// See [`append_tag`](crate::append_tag).
+    pub fn append_tag(mut self, namespace: &Path, tag: Meta) -> Self {
+        util::append_tag(&mut self.derive_input.attrs, namespace, tag);
+        self
+    }
+
+    /// Removes `#[namespace..]` attributes from the type and its fields,
+    /// variants, and generic parameters.
+    ///
+    /// See [`StripAttrs`].
+    pub fn strip_namespace(self, namespace: &Path) -> Self {
+        self.fold(&mut StripAttrs::new(namespace.clone()))
+    }
+
+    /// Applies a [`Fold`] to the `DeriveInput`.
+    pub fn fold(mut self, fold: &mut dyn Fold) -> Self {
+        self.derive_input = fold.fold_derive_input(self.derive_input);
+        self
+    }
+
+    /// Returns the edited `DeriveInput`, or the combined errors from failed
+    /// edits.
+    pub fn finish(self) -> Result<DeriveInput, Error> {
+        self.diagnostics.error_or(self.derive_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::DeriveInputEditor;
+
+    #[test]
+    fn finish_returns_edited_derive_input() {
+        let ast: DeriveInput = parse_quote! {
+            struct Struct;
+        };
+
+        let ast = DeriveInputEditor::new(ast)
+            .append_unnamed(parse_quote!((u32,)))
+            .append_tag(&parse_quote!(my::derive), parse_quote!(tag))
+            .finish()
+            .expect("Expected edits to succeed.");
+
+        let ast_expected: DeriveInput = parse_quote! {
+            #[my::derive(tag)]
+            struct Struct(u32,);
+        };
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn finish_returns_accumulated_errors() {
+        let ast: DeriveInput = parse_quote! {
+            #[derive(Clone)]
+            struct Struct(u32);
+        };
+
+        let error = DeriveInputEditor::new(ast)
+            .append_derives(parse_quote!(Clone))
+            .append_named(parse_quote!({ a: u32 }))
+            .finish()
+            .expect_err("Expected edits to fail.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                String::from(
+                    "The following are automatically derived when this attribute is used:\n\
+                     [\"Clone\"]"
+                ),
+                String::from(
+                    "Macro must be used on either a unit struct or a struct with named fields.\n\
+                     This derive does not work on tuple structs."
+                ),
+            ],
+            messages
+        );
+    }
+}
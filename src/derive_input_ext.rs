@@ -1,6 +1,11 @@
-use syn::{parse_quote, punctuated::Punctuated, Attribute, DeriveInput, Meta, Path, Token};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Data, DataEnum, DataStruct, DeriveInput, Error,
+    Field, Fields, Ident, Meta, Path, Token, Variant,
+};
 
-use crate::util;
+use crate::{field_context, util, Diagnostics, FieldContext, InputKind};
 
 /// Functions to make it ergonomic to work with `struct` ASTs.
 pub trait DeriveInputExt {
@@ -14,7 +19,9 @@ pub trait DeriveInputExt {
     ///   that overlap with the derives to append, this macro will panic with
     ///   the overlapping derives.
     /// * If the `derive` attribute exists, and there are no overlapping
-    ///   `derive`s, then they will be combined.
+    ///   `derive`s, then they will be combined. The derives are appended to the
+    ///   existing attribute in place, so the existing derives keep their order
+    ///   and spelling, and the attribute keeps its position.
     ///
     /// # Panics
     ///
@@ -25,6 +32,54 @@ pub trait DeriveInputExt {
     /// [*derive*]: <https://doc.rust-lang.org/reference/procedural-macros.html#derive-mode-macros>
     fn append_derives(&mut self, derives: Punctuated<Path, Token![,]>);
 
+    /// Appends derives to the list of derives.
+    ///
+    /// This behaves the same as [`append_derives`], but returns an error
+    /// instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the first existing `derive` that overlaps
+    /// with the derives to append.
+    ///
+    /// [`append_derives`]: Self::append_derives
+    fn try_append_derives(&mut self, derives: Punctuated<Path, Token![,]>) -> syn::Result<()>;
+
+    /// Appends derives to the list of derives, warning about derives that
+    /// already exist.
+    ///
+    /// This behaves the same as [`append_derives`], except that existing
+    /// `derive`s that overlap with the derives to append are left as is, and
+    /// tokens that emit a warning at each of them are returned. These tokens
+    /// should be emitted alongside the item.
+    ///
+    /// See [`emit_warning`] for how the warning is emitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let mut ast: DeriveInput = parse_quote!(
+    ///     #[derive(Clone)]
+    ///     struct Struct;
+    /// );
+    ///
+    /// let warnings = ast.append_derives_or_warn(parse_quote!(Clone, Copy));
+    ///
+    /// let ast_expected: DeriveInput = parse_quote!(
+    ///     #[derive(Clone, Copy)]
+    ///     struct Struct;
+    /// );
+    /// assert_eq!(ast_expected, ast);
+    /// assert!(!warnings.is_empty());
+    /// ```
+    ///
+    /// [`append_derives`]: Self::append_derives
+    /// [`emit_warning`]: crate::emit_warning
+    fn append_derives_or_warn(&mut self, derives: Punctuated<Path, Token![,]>) -> TokenStream;
+
     /// Returns whether the type contains a given `#[namespace]` attribute.
     ///
     /// # Parameters
@@ -45,6 +100,11 @@ pub trait DeriveInputExt {
 
     /// Returns the parameters from `#[namespace(param1, param2, ..)]`.
     ///
+    /// Malformed attributes are ignored. Use [`namespace_nested_metas_checked`]
+    /// to report them instead.
+    ///
+    /// [`namespace_nested_metas_checked`]: crate::namespace_nested_metas_checked
+    ///
     /// # Parameters
     ///
     /// * `namespace`: The `path()` of the first-level attribute.
@@ -72,66 +132,194 @@ pub trait DeriveInputExt {
 
     /// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`.
     ///
+    /// Malformed attributes and `tag` parameters are ignored. Use
+    /// [`tag_nested_metas_checked`] to report them instead.
+    ///
+    /// [`tag_nested_metas_checked`]: crate::tag_nested_metas_checked
+    ///
     /// # Parameters
     ///
     /// * `namespace`: The `path()` of the first-level attribute.
     /// * `tag`: The `path()` of the second-level attribute.
     fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta>;
+
+    /// Returns the shape of the type.
+    fn input_kind(&self) -> InputKind;
+
+    /// Calls the closure with each field of the type.
+    ///
+    /// For structs and unions, each field is visited in order. For enums, the
+    /// fields of each variant are visited in order, and the owning variant is
+    /// passed in the [`FieldContext`].
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Function to call with each field.
+    fn for_each_field<'f, F>(&'f self, f: F)
+    where
+        F: FnMut(FieldContext<'f>);
+
+    /// Returns the values of the closure called with each field of the type.
+    ///
+    /// See [`for_each_field`] for the order the fields are visited.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Function to map each field.
+    ///
+    /// [`for_each_field`]: Self::for_each_field
+    fn map_fields<'f, F, T>(&'f self, f: F) -> Vec<T>
+    where
+        F: FnMut(FieldContext<'f>) -> T;
+
+    /// Returns the values of the closure called with each field of the type,
+    /// or all errors combined.
+    ///
+    /// Every field is visited even if the closure returns an error for an
+    /// earlier field, so that all errors are reported at once. See
+    /// [`for_each_field`] for the order the fields are visited.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Function to map each field.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors returned by the closure, combined into one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputExt;
+    /// use syn::{parse_quote, DeriveInput, Error, Type};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     enum Shape {
+    ///         Circle { radius: f64 },
+    ///         Square(u32),
+    ///     }
+    /// };
+    ///
+    /// let result = ast.try_map_fields(|field_context| {
+    ///     if field_context.field.ident.is_some() {
+    ///         Ok(field_context.field.ty.clone())
+    ///     } else {
+    ///         Err(Error::new_spanned(
+    ///             field_context.field,
+    ///             "Fields must be named.",
+    ///         ))
+    ///     }
+    /// });
+    ///
+    /// let error = result.unwrap_err();
+    /// assert_eq!("Fields must be named.", error.to_string());
+    /// ```
+    ///
+    /// [`for_each_field`]: Self::for_each_field
+    fn try_map_fields<'f, F, T>(&'f self, f: F) -> syn::Result<Vec<T>>
+    where
+        F: FnMut(FieldContext<'f>) -> syn::Result<T>;
+
+    /// Calls the closure with a mutable reference to each field of the type.
+    ///
+    /// The closure is passed the ident of the variant that owns the field if
+    /// the type is an enum, the index of the field within its struct, variant,
+    /// or union, and the field. The fields are visited in the same order as
+    /// [`for_each_field`].
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Function to call with each field.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let mut ast: DeriveInput = parse_quote! {
+    ///     enum Shape {
+    ///         Circle(#[my::derive(skip)] f64),
+    ///         Square(#[my::derive(skip)] u32),
+    ///     }
+    /// };
+    ///
+    /// ast.for_each_field_mut(|_variant_ident, _index, field| field.attrs.clear());
+    ///
+    /// let ast_expected: DeriveInput = parse_quote! {
+    ///     enum Shape {
+    ///         Circle(f64),
+    ///         Square(u32),
+    ///     }
+    /// };
+    /// assert_eq!(ast_expected, ast);
+    /// ```
+    ///
+    /// [`for_each_field`]: Self::for_each_field
+    fn for_each_field_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(Option<&Ident>, usize, &mut Field);
+
+    /// Returns the values of the closure called with each variant of an enum.
+    ///
+    /// # Parameters
+    ///
+    /// * `f`: Function to map each variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type's ident if the AST is not for an
+    /// enum.
+    fn map_variants<'f, F, T>(&'f self, f: F) -> syn::Result<Vec<T>>
+    where
+        F: FnMut(&'f Variant) -> T;
 }
 
 impl DeriveInputExt for DeriveInput {
     fn append_derives(&mut self, derives_to_append: Punctuated<Path, Token![,]>) {
-        let attr_derives_existing = self
-            .attrs
-            .iter_mut()
-            .filter(|attr| attr.path().is_ident("derive"))
-            .filter_map(|attr| {
-                match attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated) {
-                    Ok(derives_existing) => Some((attr, derives_existing)),
-                    _ => None, // kcov-ignore
-                }
+        if let Err(error) = append_derives(self, derives_to_append) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_append_derives(
+        &mut self,
+        derives_to_append: Punctuated<Path, Token![,]>,
+    ) -> syn::Result<()> {
+        append_derives(self, derives_to_append)
+    }
+
+    fn append_derives_or_warn(
+        &mut self,
+        derives_to_append: Punctuated<Path, Token![,]>,
+    ) -> TokenStream {
+        let derives_existing = derives_existing(self);
+        let (superfluous, derives_to_append) = derives_to_append
+            .into_iter()
+            .partition::<Vec<Path>, _>(|derive_to_append| {
+                derives_existing.iter().any(|path| path == derive_to_append)
+            });
+
+        let warnings = derives_existing
+            .iter()
+            .filter(|derive_existing| superfluous.contains(derive_existing))
+            .map(|derive_existing| {
+                util::emit_warning(
+                    derive_existing.span(),
+                    &format!(
+                        "`{}` is automatically derived when this attribute is used.",
+                        util::format_path(derive_existing)
+                    ),
+                )
             })
-            .next();
+            .collect::<TokenStream>();
 
-        if let Some((attr, mut derives_existing)) = attr_derives_existing {
-            // Emit warning if the user derives any of the existing derives, as we do that
-            // for them.
-            let superfluous = derives_to_append
-                .iter()
-                .filter(|derive_to_append| {
-                    derives_existing
-                        .iter()
-                        .any(|derive_existing| derive_existing == *derive_to_append)
-                })
-                .map(util::format_path)
-                .collect::<Vec<_>>();
-            if !superfluous.is_empty() {
-                // TODO: Emit warning, pending <https://github.com/rust-lang/rust/issues/54140>
-                // derives_existing
-                //     .span()
-                //     .warning(
-                //         "The following are automatically derived by this proc macro
-                // attribute.",     )
-                //     .emit();
-                panic!(
-                    "The following are automatically derived when this attribute is used:\n\
-                     {:?}",
-                    superfluous
-                );
-            } else {
-                derives_existing.extend(derives_to_append);
-
-                // Replace the existing `Attribute`.
-                //
-                // `attr.parse_meta()` returns a `Meta`, which is not referenced by the
-                // `DeriveInput`, so we have to replace `attr` itself.
-                *attr = parse_quote!(#[derive(#derives_existing)]);
-            }
-        } else {
-            // Add a new `#[derive(..)]` attribute with all the derives.
-            let derive_attribute: Attribute = parse_quote!(#[derive(#derives_to_append)]);
-            self.attrs.push(derive_attribute);
+        if !derives_to_append.is_empty() {
+            append_derives(self, derives_to_append.into_iter().collect())
+                .expect("Expected overlapping derives to be removed.");
         }
+
+        warnings
     }
 
     fn contains_namespace(&self, namespace: &Path) -> bool {
@@ -157,15 +345,162 @@ impl DeriveInputExt for DeriveInput {
     fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta> {
         util::tag_parameters(&self.attrs, namespace, tag)
     }
+
+    fn input_kind(&self) -> InputKind {
+        match &self.data {
+            Data::Struct(DataStruct { fields, .. }) => match fields {
+                Fields::Unit => InputKind::UnitStruct,
+                Fields::Named(..) => InputKind::NamedStruct,
+                Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
+                    InputKind::Newtype
+                }
+                Fields::Unnamed(..) => InputKind::TupleStruct,
+            },
+            Data::Enum(..) => InputKind::Enum,
+            Data::Union(..) => InputKind::Union,
+        }
+    }
+
+    fn for_each_field<'f, F>(&'f self, f: F)
+    where
+        F: FnMut(FieldContext<'f>),
+    {
+        field_context::field_contexts(&self.data).for_each(f);
+    }
+
+    fn map_fields<'f, F, T>(&'f self, f: F) -> Vec<T>
+    where
+        F: FnMut(FieldContext<'f>) -> T,
+    {
+        field_context::field_contexts(&self.data).map(f).collect()
+    }
+
+    fn try_map_fields<'f, F, T>(&'f self, mut f: F) -> syn::Result<Vec<T>>
+    where
+        F: FnMut(FieldContext<'f>) -> syn::Result<T>,
+    {
+        let mut diagnostics = Diagnostics::new();
+        let values = field_context::field_contexts(&self.data)
+            .filter_map(|field_context| diagnostics.check(f(field_context)))
+            .collect::<Vec<T>>();
+
+        diagnostics.error_or(values)
+    }
+
+    fn for_each_field_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(Option<&Ident>, usize, &mut Field),
+    {
+        field_context::for_each_field_mut(&mut self.data, f);
+    }
+
+    fn map_variants<'f, F, T>(&'f self, f: F) -> syn::Result<Vec<T>>
+    where
+        F: FnMut(&'f Variant) -> T,
+    {
+        if let Data::Enum(DataEnum { variants, .. }) = &self.data {
+            Ok(variants.iter().map(f).collect())
+        } else {
+            Err(Error::new(
+                self.ident.span(),
+                "This macro must be used on an enum.",
+            ))
+        }
+    }
+}
+
+/// Returns the derives in the first `#[derive(..)]` attribute.
+fn derives_existing(derive_input: &DeriveInput) -> Punctuated<Path, Token![,]> {
+    derive_input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .find_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends derives to the list of derives, returning an error if any of the
+/// derives already exist.
+///
+/// The error is spanned at the first existing derive that overlaps.
+pub(crate) fn append_derives(
+    derive_input: &mut DeriveInput,
+    derives_to_append: Punctuated<Path, Token![,]>,
+) -> Result<(), Error> {
+    let attr_derives_existing = derive_input
+        .attrs
+        .iter_mut()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            match attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated) {
+                Ok(derives_existing) => Some((attr, derives_existing)),
+                _ => None, // kcov-ignore
+            }
+        })
+        .next();
+
+    if let Some((attr, derives_existing)) = attr_derives_existing {
+        // Error if the user derives any of the existing derives, as we do that for
+        // them. `append_derives_or_warn` warns instead.
+        let superfluous = derives_existing
+            .iter()
+            .filter(|derive_existing| {
+                derives_to_append
+                    .iter()
+                    .any(|derive_to_append| derive_to_append == *derive_existing)
+            })
+            .collect::<Vec<_>>();
+        if let Some(superfluous_first) = superfluous.first() {
+            let superfluous = derives_to_append
+                .iter()
+                .filter(|derive_to_append| superfluous.contains(derive_to_append))
+                .map(util::format_path)
+                .collect::<Vec<_>>();
+            Err(Error::new_spanned(
+                superfluous_first,
+                format!(
+                    "The following are automatically derived when this attribute is used:\n\
+                     {:?}",
+                    superfluous
+                ),
+            ))
+        } else {
+            // Append to the existing attribute's tokens instead of regenerating them, so
+            // the user's derives keep their order, spelling, and spans.
+            if let Meta::List(meta_list) = &mut attr.meta {
+                if !derives_existing.empty_or_trailing() {
+                    meta_list.tokens.extend(quote!(,));
+                }
+                meta_list
+                    .tokens
+                    .extend(derives_to_append.into_token_stream());
+            }
+
+            Ok(())
+        }
+    } else {
+        // Add a new `#[derive(..)]` attribute with all the derives.
+        let derive_attribute = util::attribute_list(
+            Path::from(Ident::new("derive", Span::call_site())),
+            derives_to_append.into_token_stream(),
+        );
+        derive_input.attrs.push(derive_attribute);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use proc_macro2::Span;
     use quote::quote;
-    use syn::{parse_quote, DeriveInput, Error, Meta, MetaNameValue};
+    use syn::{parse_quote, DeriveInput, Error, Meta, MetaNameValue, Type};
 
     use super::DeriveInputExt;
+    use crate::InputKind;
 
     #[test]
     fn append_derives_creates_attr_when_attr_does_not_exist() {
@@ -183,6 +518,28 @@ mod tests {
         assert_eq!(ast_expected, ast);
     }
 
+    #[test]
+    fn append_derives_preserves_existing_tokens_and_attr_position() {
+        let mut ast: DeriveInput = parse_quote!(
+            #[doc = "Docs."]
+            #[derive(::core::fmt::Debug, Clone)]
+            #[repr(C)]
+            struct Struct;
+        );
+        let derives = parse_quote!(Copy);
+
+        ast.append_derives(derives);
+
+        let attr_derive = &ast.attrs[1];
+        assert!(attr_derive.path().is_ident("derive"));
+        let tokens = match &attr_derive.meta {
+            Meta::List(meta_list) => meta_list.tokens.to_string(),
+            meta => panic!("Expected `#[derive(..)]` to be a list, but was: {meta:?}"),
+        };
+        assert_eq!(quote!(::core::fmt::Debug, Clone, Copy).to_string(), tokens);
+        assert!(ast.attrs[2].path().is_ident("repr"));
+    }
+
     #[test]
     fn append_derives_appends_to_attr_when_attr_exists() {
         let mut ast: DeriveInput = parse_quote!(
@@ -215,6 +572,73 @@ mod tests {
         ast.append_derives(derives);
     }
 
+    #[test]
+    fn append_derives_or_warn_warns_for_each_existing_derive() {
+        let mut ast: DeriveInput = parse_quote!(
+            #[derive(Debug, Copy, Clone)]
+            struct Struct;
+        );
+        let derives = parse_quote!(Clone, Copy, Default);
+
+        let warnings = ast.append_derives_or_warn(derives);
+
+        let ast_expected: DeriveInput = parse_quote!(
+            #[derive(Debug, Copy, Clone, Default)]
+            struct Struct;
+        );
+        assert_eq!(ast_expected, ast);
+        let warnings_expected = quote! {
+            const _: () = {
+                #[deprecated(note = "`Copy` is automatically derived when this attribute is used.")]
+                #[allow(non_upper_case_globals)]
+                const warning: () = ();
+                let _ = warning;
+            };
+            const _: () = {
+                #[deprecated(note = "`Clone` is automatically derived when this attribute is used.")]
+                #[allow(non_upper_case_globals)]
+                const warning: () = ();
+                let _ = warning;
+            };
+        };
+        assert_eq!(warnings_expected.to_string(), warnings.to_string());
+    }
+
+    #[test]
+    fn append_derives_or_warn_returns_no_warnings_when_no_overlap() {
+        let mut ast: DeriveInput = parse_quote!(
+            struct Struct;
+        );
+
+        let warnings = ast.append_derives_or_warn(parse_quote!(Clone));
+
+        let ast_expected: DeriveInput = parse_quote!(
+            #[derive(Clone)]
+            struct Struct;
+        );
+        assert_eq!(ast_expected, ast);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn try_append_derives_returns_error_when_derives_exist() {
+        let mut ast: DeriveInput = parse_quote!(
+            #[derive(Debug, Copy, Clone)]
+            struct Struct;
+        );
+        let derives = parse_quote!(Clone, Copy, Default);
+
+        let error = ast
+            .try_append_derives(derives)
+            .expect_err("Expected an error for overlapping derives.");
+
+        assert_eq!(
+            "The following are automatically derived when this attribute is used:\n\
+             [\"Clone\", \"Copy\"]",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn contains_namespace_returns_false_when_namespace_does_not_exist() -> Result<(), Error> {
         let tokens_list = vec![
@@ -279,7 +703,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Expected exactly one parameter for `#[my::derive(..)]`.")]
+    #[should_panic(
+        expected = "Expected at most one parameter for `#[my::derive(..)]`, found `Magic :: One, Magic :: Two`."
+    )]
     fn namespace_parameter_panics_when_multiple_parameters_present() {
         let ast: DeriveInput = parse_quote!(
             #[my::derive(Magic::One, Magic::Two)]
@@ -402,7 +828,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Expected exactly one parameter for `#[my::derive(tag::name(..))]`.")]
+    #[should_panic(
+        expected = "Expected at most one parameter for `#[my::derive(tag::name(..))]`, found `Magic :: One, Magic :: Two`."
+    )]
     fn tag_parameter_panics_when_multiple_parameters_present() {
         let ast: DeriveInput = parse_quote!(
             #[my::derive(tag::name(Magic::One, Magic::Two))]
@@ -444,4 +872,239 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn input_kind_returns_shape_of_type() {
+        let ast_unit: DeriveInput = parse_quote!(
+            struct Unit;
+        );
+        let ast_named: DeriveInput = parse_quote!(
+            struct Named {
+                a: u32,
+            }
+        );
+        let ast_tuple: DeriveInput = parse_quote!(
+            struct Tuple(u32, u32);
+        );
+        let ast_newtype: DeriveInput = parse_quote!(
+            struct Newtype(u32);
+        );
+        let ast_enum: DeriveInput = parse_quote!(
+            enum Enum {}
+        );
+        let ast_union: DeriveInput = parse_quote!(
+            union Union {
+                a: u32,
+            }
+        );
+
+        assert_eq!(InputKind::UnitStruct, ast_unit.input_kind());
+        assert_eq!(InputKind::NamedStruct, ast_named.input_kind());
+        assert_eq!(InputKind::TupleStruct, ast_tuple.input_kind());
+        assert_eq!(InputKind::Newtype, ast_newtype.input_kind());
+        assert_eq!(InputKind::Enum, ast_enum.input_kind());
+        assert_eq!(InputKind::Union, ast_union.input_kind());
+    }
+
+    #[test]
+    fn for_each_field_visits_struct_fields() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct {
+                a: u32,
+                b: i32,
+            }
+        );
+
+        let mut fields_visited = Vec::new();
+        ast.for_each_field(|field_context| {
+            assert!(field_context.variant.is_none());
+            fields_visited.push((field_context.index, field_context.field.ident.clone()));
+        });
+
+        assert_eq!(
+            vec![(0, Some(parse_quote!(a))), (1, Some(parse_quote!(b)))],
+            fields_visited
+        );
+    }
+
+    #[test]
+    fn for_each_field_visits_enum_variant_fields() {
+        let ast: DeriveInput = parse_quote!(
+            enum Enum {
+                Unit,
+                Tuple(u32, i32),
+                Named { c: u64 },
+            }
+        );
+
+        let mut fields_visited = Vec::new();
+        ast.for_each_field(|field_context| {
+            let variant_ident = field_context
+                .variant
+                .map(|variant| variant.ident.to_string());
+            fields_visited.push((variant_ident, field_context.index));
+        });
+
+        assert_eq!(
+            vec![
+                (Some(String::from("Tuple")), 0),
+                (Some(String::from("Tuple")), 1),
+                (Some(String::from("Named")), 0),
+            ],
+            fields_visited
+        );
+    }
+
+    #[test]
+    fn map_fields_maps_union_fields() {
+        let ast: DeriveInput = parse_quote!(
+            union Union {
+                a: u32,
+                b: f32,
+            }
+        );
+
+        let field_types = ast.map_fields(|field_context| field_context.field.ty.clone());
+
+        let types_expected: Vec<Type> = vec![parse_quote!(u32), parse_quote!(f32)];
+        assert_eq!(types_expected, field_types);
+    }
+
+    #[test]
+    fn try_map_fields_returns_values_when_all_ok() -> Result<(), Error> {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct {
+                a: u32,
+                b: i32,
+            }
+        );
+
+        let field_indices = ast.try_map_fields(|field_context| Ok(field_context.index))?;
+
+        assert_eq!(vec![0, 1], field_indices);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_map_fields_combines_errors_from_all_fields() {
+        let ast: DeriveInput = parse_quote!(
+            enum Enum {
+                A(u32),
+                B { b: i32 },
+                C(u64),
+            }
+        );
+
+        let error = ast
+            .try_map_fields(|field_context| {
+                if field_context.field.ident.is_none() {
+                    Err(Error::new_spanned(field_context.field, "unnamed"))
+                } else {
+                    Ok(())
+                }
+            })
+            .expect_err("Expected `try_map_fields` to fail for unnamed fields.");
+
+        assert_eq!(2, error.into_iter().count());
+    }
+
+    #[test]
+    fn for_each_field_mut_visits_enum_variant_fields() {
+        let mut ast: DeriveInput = parse_quote!(
+            enum Enum {
+                Unit,
+                Tuple(u32, i32),
+                Named { c: u64 },
+            }
+        );
+
+        let mut fields_visited = Vec::new();
+        ast.for_each_field_mut(|variant_ident, index, field| {
+            fields_visited.push((variant_ident.map(ToString::to_string), index));
+            field.ty = parse_quote!(bool);
+        });
+
+        let ast_expected: DeriveInput = parse_quote!(
+            enum Enum {
+                Unit,
+                Tuple(bool, bool),
+                Named { c: bool },
+            }
+        );
+        assert_eq!(ast_expected, ast);
+        assert_eq!(
+            vec![
+                (Some(String::from("Tuple")), 0),
+                (Some(String::from("Tuple")), 1),
+                (Some(String::from("Named")), 0),
+            ],
+            fields_visited
+        );
+    }
+
+    #[test]
+    fn for_each_field_mut_visits_struct_and_union_fields() {
+        let mut ast_struct: DeriveInput = parse_quote!(
+            struct Struct(u32, i32);
+        );
+        let mut ast_union: DeriveInput = parse_quote!(
+            union Union {
+                a: u32,
+            }
+        );
+
+        let mut indices = Vec::new();
+        ast_struct.for_each_field_mut(|variant_ident, index, _field| {
+            assert!(variant_ident.is_none());
+            indices.push(index);
+        });
+        ast_union.for_each_field_mut(|variant_ident, index, _field| {
+            assert!(variant_ident.is_none());
+            indices.push(index);
+        });
+
+        assert_eq!(vec![0, 1, 0], indices);
+    }
+
+    #[test]
+    fn map_variants_maps_enum_variants() -> Result<(), Error> {
+        let ast: DeriveInput = parse_quote!(
+            enum Enum {
+                Unit = 1,
+                Tuple(u32),
+            }
+        );
+
+        let variants = ast.map_variants(|variant| {
+            (
+                variant.ident.to_string(),
+                variant.discriminant.is_some(),
+                variant.fields.len(),
+            )
+        })?;
+
+        assert_eq!(
+            vec![
+                (String::from("Unit"), true, 0),
+                (String::from("Tuple"), false, 1)
+            ],
+            variants
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_variants_returns_error_when_ast_is_not_enum() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct;
+        );
+
+        let error = ast
+            .map_variants(|variant| variant.ident.clone())
+            .expect_err("Expected `map_variants` to fail for struct.");
+
+        assert_eq!("This macro must be used on an enum.", error.to_string());
+    }
 }
@@ -1,4 +1,4 @@
-use syn::{Data, DataStruct, DeriveInput, Field, Fields};
+use syn::{Data, DataStruct, DeriveInput, Error, Field, Fields};
 
 const NEWTYPE_MUST_HAVE_ONLY_ONE_FIELD: &str = "Newtype struct must only have one field.\n\
      See https://doc.rust-lang.org/book/ch19-04-advanced-types.html#advanced-types \
@@ -23,6 +23,37 @@ pub trait DeriveInputNewtypeExt {
     /// Panics if the AST is not for a newtype struct.
     fn inner_type_mut(&mut self) -> &mut Field;
 
+    /// Returns the `Field` of the first unnamed field of this struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the AST is not for a newtype struct. The error is
+    /// spanned at the fields if there is more than one, otherwise at the type
+    /// name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputNewtypeExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote!(
+    ///     struct Unit;
+    /// );
+    ///
+    /// assert!(ast.try_inner_type().is_err());
+    /// ```
+    fn try_inner_type(&self) -> syn::Result<&Field>;
+
+    /// Returns the `Field` of the first unnamed field of this struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the AST is not for a newtype struct. The error is
+    /// spanned at the fields if there is more than one, otherwise at the type
+    /// name.
+    fn try_inner_type_mut(&mut self) -> syn::Result<&mut Field>;
+
     /// Returns true if the AST is for a struct with **exactly one** unnamed
     /// field.
     fn is_newtype(&self) -> bool;
@@ -30,43 +61,63 @@ pub trait DeriveInputNewtypeExt {
 
 impl DeriveInputNewtypeExt for DeriveInput {
     fn inner_type(&self) -> &Field {
+        self.try_inner_type()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn inner_type_mut(&mut self) -> &mut Field {
+        self.try_inner_type_mut()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_inner_type(&self) -> syn::Result<&Field> {
         if let Data::Struct(DataStruct {
             fields: Fields::Unnamed(fields_unnamed),
             ..
         }) = &self.data
         {
             if fields_unnamed.unnamed.len() == 1 {
-                fields_unnamed
+                Ok(fields_unnamed
                     .unnamed
                     .first()
-                    .expect("Expected field to exist.")
+                    .expect("Expected field to exist."))
             } else {
-                panic!("{}", NEWTYPE_MUST_HAVE_ONLY_ONE_FIELD)
+                Err(Error::new_spanned(
+                    fields_unnamed,
+                    NEWTYPE_MUST_HAVE_ONLY_ONE_FIELD,
+                ))
             }
         } else {
-            panic!("{}", MACRO_MUST_BE_USED_ON_NEWTYPE_STRUCT)
+            Err(Error::new(
+                self.ident.span(),
+                MACRO_MUST_BE_USED_ON_NEWTYPE_STRUCT,
+            ))
         }
     }
 
-    fn inner_type_mut(&mut self) -> &mut Field {
+    fn try_inner_type_mut(&mut self) -> syn::Result<&mut Field> {
         if let Data::Struct(DataStruct {
             fields: Fields::Unnamed(fields_unnamed),
             ..
         }) = &mut self.data
         {
             if fields_unnamed.unnamed.len() == 1 {
-                fields_unnamed
+                Ok(fields_unnamed
                     .unnamed
                     .iter_mut()
                     .next()
-                    .expect("Expected field to exist.")
-            // kcov-ignore-start
+                    .expect("Expected field to exist."))
             } else {
-                // kcov-ignore-end
-                panic!("{}", NEWTYPE_MUST_HAVE_ONLY_ONE_FIELD)
+                Err(Error::new_spanned(
+                    &*fields_unnamed,
+                    NEWTYPE_MUST_HAVE_ONLY_ONE_FIELD,
+                ))
             }
         } else {
-            panic!("{}", MACRO_MUST_BE_USED_ON_NEWTYPE_STRUCT)
+            Err(Error::new(
+                self.ident.span(),
+                MACRO_MUST_BE_USED_ON_NEWTYPE_STRUCT,
+            ))
         }
     }
 
@@ -82,7 +133,6 @@ impl DeriveInputNewtypeExt for DeriveInput {
         }
     }
 }
-
 #[cfg(test)]
 mod tests {
     use syn::{parse_quote, DeriveInput, Type};
@@ -161,6 +211,54 @@ mod tests {
         ast.inner_type_mut();
     } // kcov-ignore
 
+    #[test]
+    fn try_inner_type_returns_field() {
+        let ast: DeriveInput = parse_quote! {
+            struct Newtype(u32);
+        };
+
+        let inner_field = ast.try_inner_type().expect("Expected inner field.");
+
+        let expected_type: Type = Type::Path(parse_quote!(u32));
+        assert_eq!(expected_type, inner_field.ty);
+    }
+
+    #[test]
+    fn try_inner_type_returns_error_when_struct_has_multiple_fields() {
+        let ast: DeriveInput = parse_quote! {
+            struct Newtype(u32, u32);
+        };
+
+        let error = ast
+            .try_inner_type()
+            .expect_err("Expected an error for multiple fields.");
+
+        assert_eq!(
+            "Newtype struct must only have one field.\n\
+            See https://doc.rust-lang.org/book/ch19-04-advanced-types.html#advanced-types \
+            for more information.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn try_inner_type_mut_returns_error_when_struct_fields_not_unnamed() {
+        let mut ast: DeriveInput = parse_quote! {
+            struct Unit;
+        };
+
+        let error = ast
+            .try_inner_type_mut()
+            .expect_err("Expected an error for a unit struct.");
+
+        assert_eq!(
+            "This macro must be used on a newtype struct.\n\
+            See https://doc.rust-lang.org/book/ch19-04-advanced-types.html#advanced-types \
+            for more information.",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn is_newtype_returns_true_when_fields_unnamed_and_exactly_one() {
         let ast: DeriveInput = parse_quote! {
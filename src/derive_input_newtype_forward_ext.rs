@@ -0,0 +1,340 @@
+use quote::quote;
+use syn::{
+    parse_quote, DeriveInput, ImplItem, ImplItemFn, ItemImpl, ItemTrait, Path, Signature,
+    TraitItem, TraitItemConst, TraitItemType, Type, Visibility,
+};
+
+use crate::{
+    util::{self, trait_impl_generics},
+    DeriveInputNewtypeExt,
+};
+
+/// Functions to generate impls for newtype `struct`s that forward to the inner
+/// field.
+///
+/// This requires the `items` feature.
+pub trait DeriveInputNewtypeForwardExt {
+    /// Returns an inherent impl block with methods that forward to the inner
+    /// field.
+    ///
+    /// Each method calls the method of the same name on `self.0`:
+    ///
+    /// * `&self` and `&mut self` methods call `self.0.method(..)`.
+    /// * `self` methods call `self.0.method(..)`, moving the inner value.
+    /// * Associated functions call `<Inner>::method(..)`.
+    /// * `async` methods `.await` the inner method.
+    /// * If the return type is `Self`, the return value is wrapped in
+    ///   `Self(..)`.
+    ///
+    /// The forwarded methods have the same visibility as the newtype.
+    ///
+    /// # Parameters
+    ///
+    /// * `signatures`: Signatures of the methods to forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a newtype struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputNewtypeForwardExt;
+    /// use syn::{parse_quote, DeriveInput, ItemImpl, Signature};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     pub struct Wrapper<T>(Vec<T>);
+    /// };
+    /// let signatures: [Signature; 2] = [
+    ///     parse_quote!(fn len(&self) -> usize),
+    ///     parse_quote!(fn push(&mut self, value: T)),
+    /// ];
+    ///
+    /// let item_impl_expected: ItemImpl = parse_quote! {
+    ///     impl<T> Wrapper<T> {
+    ///         pub fn len(&self) -> usize {
+    ///             self.0.len()
+    ///         }
+    ///         pub fn push(&mut self, value: T) {
+    ///             self.0.push(value)
+    ///         }
+    ///     }
+    /// };
+    /// assert_eq!(item_impl_expected, ast.forward_methods(&signatures));
+    /// ```
+    fn forward_methods(&self, signatures: &[Signature]) -> ItemImpl;
+
+    /// Returns an impl of the trait for the newtype that forwards to the inner
+    /// field.
+    ///
+    /// * Methods call `<Inner as Trait>::method(..)` with `&self.0`, `&mut
+    ///   self.0`, or `self.0` depending on the receiver.
+    /// * Associated types are `<Inner as Trait>::Type`.
+    /// * Associated consts are `<Inner as Trait>::CONST`.
+    ///
+    /// The impl has a `where Inner: Trait` bound. Methods taking other
+    /// parameters of type `Self` are not converted, and supertraits are not
+    /// implemented.
+    ///
+    /// Trait generic parameters with the same name as the newtype's, such as
+    /// `T` in `struct Wrapper<T>` and `trait Convert<T>`, are renamed in the
+    /// impl by appending a number, e.g. `T1`.
+    ///
+    /// # Parameters
+    ///
+    /// * `item_trait`: The trait to forward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a newtype struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputNewtypeForwardExt;
+    /// use syn::{parse_quote, DeriveInput, ItemImpl, ItemTrait};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     struct Wrapper(Inner);
+    /// };
+    /// let item_trait: ItemTrait = parse_quote! {
+    ///     trait Named {
+    ///         type Name;
+    ///         fn name(&self) -> Self::Name;
+    ///     }
+    /// };
+    ///
+    /// let item_impl_expected: ItemImpl = parse_quote! {
+    ///     impl Named for Wrapper where Inner: Named {
+    ///         type Name = <Inner as Named>::Name;
+    ///         fn name(&self) -> Self::Name {
+    ///             <Inner as Named>::name(&self.0)
+    ///         }
+    ///     }
+    /// };
+    /// assert_eq!(item_impl_expected, ast.forward_trait(&item_trait));
+    /// ```
+    fn forward_trait(&self, item_trait: &ItemTrait) -> ItemImpl;
+}
+
+impl DeriveInputNewtypeForwardExt for DeriveInput {
+    fn forward_methods(&self, signatures: &[Signature]) -> ItemImpl {
+        let inner_type = &self.inner_type().ty;
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let impl_item_fns = signatures.iter().map(|sig| {
+            let mut impl_item_fn = forwarding_fn(sig, inner_type, None);
+            impl_item_fn.vis = self.vis.clone();
+            impl_item_fn
+        });
+
+        parse_quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#impl_item_fns)*
+            }
+        }
+    }
+
+    fn forward_trait(&self, item_trait: &ItemTrait) -> ItemImpl {
+        let inner_type = &self.inner_type().ty;
+        let ident = &self.ident;
+        let (item_trait, mut generics) = trait_impl_generics(item_trait, &self.generics);
+        let trait_ident = &item_trait.ident;
+        let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+        let trait_path: Path = parse_quote!(#trait_ident #trait_ty_generics);
+
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#inner_type: #trait_path));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+
+        let impl_items = item_trait
+            .items
+            .iter()
+            .filter_map(|trait_item| match trait_item {
+                TraitItem::Const(TraitItemConst {
+                    ident: const_ident,
+                    ty,
+                    ..
+                }) => Some(parse_quote! {
+                    const #const_ident: #ty = <#inner_type as #trait_path>::#const_ident;
+                }),
+                TraitItem::Type(TraitItemType {
+                    ident: type_ident,
+                    generics: type_generics,
+                    ..
+                }) => {
+                    let (_, type_ty_generics, type_where_clause) = type_generics.split_for_impl();
+                    Some(parse_quote! {
+                        type #type_ident #type_generics =
+                            <#inner_type as #trait_path>::#type_ident #type_ty_generics
+                            #type_where_clause;
+                    })
+                }
+                TraitItem::Fn(trait_item_fn) => Some(ImplItem::Fn(forwarding_fn(
+                    &trait_item_fn.sig,
+                    inner_type,
+                    Some(&trait_path),
+                ))),
+                _ => None,
+            });
+
+        parse_quote! {
+            impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+                #(#impl_items)*
+            }
+        }
+    }
+}
+
+/// Returns a function that forwards to the function of the same name on the
+/// inner type.
+///
+/// If `trait_path` is `Some`, the function is called using fully qualified
+/// syntax.
+fn forwarding_fn(sig: &Signature, inner_type: &Type, trait_path: Option<&Path>) -> ImplItemFn {
+    let mut sig = sig.clone();
+    let args = util::forwarding_args(&mut sig);
+    let fn_ident = &sig.ident;
+
+    let receiver_expr =
+        sig.receiver().map(
+            |receiver| match (&receiver.reference, &receiver.mutability) {
+                (Some(_), Some(_)) => quote!(&mut self.0),
+                (Some(_), None) => quote!(&self.0),
+                (None, _) => quote!(self.0),
+            },
+        );
+
+    let call = match (trait_path, receiver_expr) {
+        (Some(trait_path), receiver_expr) => {
+            let args = receiver_expr
+                .into_iter()
+                .chain(args.iter().map(|arg| quote!(#arg)));
+            quote!(<#inner_type as #trait_path>::#fn_ident(#(#args),*))
+        }
+        (None, Some(_)) => quote!(self.0.#fn_ident(#(#args),*)),
+        (None, None) => quote!(<#inner_type>::#fn_ident(#(#args),*)),
+    };
+    let call = if sig.asyncness.is_some() {
+        quote!(#call.await)
+    } else {
+        call
+    };
+    let body = if util::returns_self(&sig) {
+        quote!(Self(#call))
+    } else {
+        call
+    };
+
+    ImplItemFn {
+        attrs: Vec::new(),
+        vis: Visibility::Inherited,
+        defaultness: None,
+        sig,
+        block: parse_quote!({ #body }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput, ItemImpl, ItemTrait, Signature};
+
+    use super::DeriveInputNewtypeForwardExt;
+
+    #[test]
+    fn forward_methods_handles_receivers_and_patterns() {
+        let ast: DeriveInput = parse_quote! {
+            struct Newtype(Inner);
+        };
+        let signatures: [Signature; 4] = [
+            parse_quote!(fn into_parts(self) -> (u32, u32)),
+            parse_quote!(fn set(&mut self, mut value: u32, (a, b): (u32, u32))),
+            parse_quote!(fn new() -> Self),
+            parse_quote!(async fn load(&self) -> u32),
+        ];
+
+        let item_impl = ast.forward_methods(&signatures);
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl Newtype {
+                fn into_parts(self) -> (u32, u32) {
+                    self.0.into_parts()
+                }
+                fn set(&mut self, value: u32, arg_2: (u32, u32)) {
+                    self.0.set(value, arg_2)
+                }
+                fn new() -> Self {
+                    Self(<Inner>::new())
+                }
+                async fn load(&self) -> u32 {
+                    self.0.load().await
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn forward_trait_merges_generics_and_forwards_items() {
+        let ast: DeriveInput = parse_quote! {
+            struct Newtype<'a, T>(&'a mut Vec<T>) where T: Clone;
+        };
+        let item_trait: ItemTrait = parse_quote! {
+            trait Store<K = u32> {
+                const CAPACITY: usize;
+                fn insert(&mut self, key: K);
+                fn create() -> Self;
+            }
+        };
+
+        let item_impl = ast.forward_trait(&item_trait);
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl<'a, T, K> Store<K> for Newtype<'a, T>
+            where
+                T: Clone,
+                &'a mut Vec<T>: Store<K>
+            {
+                const CAPACITY: usize = <&'a mut Vec<T> as Store<K> >::CAPACITY;
+                fn insert(&mut self, key: K) {
+                    <&'a mut Vec<T> as Store<K> >::insert(&mut self.0, key)
+                }
+                fn create() -> Self {
+                    Self(<&'a mut Vec<T> as Store<K> >::create())
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn forward_trait_renames_trait_generics_that_conflict() {
+        let ast: DeriveInput = parse_quote! {
+            struct Wrapper<'a, T>(&'a T);
+        };
+        let item_trait: ItemTrait = parse_quote! {
+            trait Convert<'a, T> where T: 'a {
+                fn convert(&self, value: &'a T) -> T;
+            }
+        };
+
+        let item_impl = ast.forward_trait(&item_trait);
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl<'a, 'a1, T, T1> Convert<'a1, T1> for Wrapper<'a, T>
+            where
+                T1: 'a1,
+                &'a T: Convert<'a1, T1>
+            {
+                fn convert(&self, value: &'a1 T1) -> T1 {
+                    <&'a T as Convert<'a1, T1> >::convert(&self.0, value)
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+}
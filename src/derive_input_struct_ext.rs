@@ -1,4 +1,13 @@
-use syn::{Data, DataStruct, DeriveInput, Fields, FieldsNamed};
+use proc_macro2::Ident;
+use syn::{Data, DataStruct, DeriveInput, Error, Fields, FieldsNamed, Variant, Visibility};
+
+const ERR_MUST_BE_STRUCT: &str = "This macro must be used on a struct.";
+const ERR_MUST_BE_UNIT: &str = "This macro must be used on a unit struct.";
+const ERR_MUST_BE_NAMED: &str = "This macro must be used on a struct with named fields.";
+const ERR_MUST_BE_UNNAMED: &str = "This macro must be used on a struct with unnamed fields.";
+const SHAPE_UNIT: &str = "a unit struct";
+const SHAPE_NAMED: &str = "a struct with named fields";
+const SHAPE_UNNAMED: &str = "a struct with unnamed fields";
 
 /// Functions to make it ergonomic to work with `struct` ASTs.
 pub trait DeriveInputStructExt {
@@ -44,6 +53,68 @@ pub trait DeriveInputStructExt {
     /// Panics if the AST is not for a struct with named fields.
     fn fields_named_mut(&mut self) -> &mut FieldsNamed;
 
+    /// Returns a reference to the data_struct of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct.
+    fn try_data_struct(&self) -> syn::Result<&DataStruct>;
+
+    /// Returns a mutable reference to the data_struct of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct.
+    fn try_data_struct_mut(&mut self) -> syn::Result<&mut DataStruct>;
+
+    /// Returns a reference to the fields of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputStructExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote!(
+    ///     enum NotStruct {}
+    /// );
+    ///
+    /// let error = ast.try_fields().unwrap_err();
+    /// assert_eq!("This macro must be used on a struct.", error.to_string());
+    /// ```
+    fn try_fields(&self) -> syn::Result<&Fields>;
+
+    /// Returns a mutable reference to the fields of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct.
+    fn try_fields_mut(&mut self) -> syn::Result<&mut Fields>;
+
+    /// Returns a reference to the named fields of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct with named fields.
+    fn try_fields_named(&self) -> syn::Result<&FieldsNamed>;
+
+    /// Returns a mutable reference to the named fields of a struct's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct with named fields.
+    fn try_fields_named_mut(&mut self) -> syn::Result<&mut FieldsNamed>;
+
     /// Returns true if the AST is for a unit struct.
     fn is_unit(&self) -> bool;
 
@@ -61,62 +132,255 @@ pub trait DeriveInputStructExt {
 
     /// Panics if the AST is not for a struct with unnamed fields.
     fn assert_fields_unnamed(&self);
+
+    /// Panics if the AST is not for a unit struct, naming the macro in the
+    /// message.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    fn assert_fields_unit_for(&self, macro_name: &str);
+
+    /// Panics if the AST is not for a struct with named fields, naming the
+    /// macro in the message.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    fn assert_fields_named_for(&self, macro_name: &str);
+
+    /// Panics if the AST is not for a struct with unnamed fields, naming the
+    /// macro in the message.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    fn assert_fields_unnamed_for(&self, macro_name: &str);
+
+    /// Returns an error if the AST is not for a unit struct.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    fn check_fields_unit(&self) -> syn::Result<()>;
+
+    /// Returns an error if the AST is not for a struct with named fields.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputStructExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote!(
+    ///     struct Tuple(u32);
+    /// );
+    ///
+    /// let error = ast.check_fields_named().unwrap_err();
+    /// assert_eq!(
+    ///     "This macro must be used on a struct with named fields.",
+    ///     error.to_string()
+    /// );
+    /// ```
+    fn check_fields_named(&self) -> syn::Result<()>;
+
+    /// Returns an error if the AST is not for a struct with unnamed fields.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    fn check_fields_unnamed(&self) -> syn::Result<()>;
+
+    /// Returns an error naming the macro if the AST is not for a unit struct.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    fn check_fields_unit_for(&self, macro_name: &str) -> syn::Result<()>;
+
+    /// Returns an error naming the macro if the AST is not for a struct with
+    /// named fields.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputStructExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote!(
+    ///     struct Tuple(u32);
+    /// );
+    ///
+    /// let error = ast.check_fields_named_for("MyDerive").unwrap_err();
+    /// assert_eq!(
+    ///     "`MyDerive` must be used on a struct with named fields.",
+    ///     error.to_string()
+    /// );
+    /// ```
+    fn check_fields_named_for(&self, macro_name: &str) -> syn::Result<()>;
+
+    /// Returns an error naming the macro if the AST is not for a struct with
+    /// unnamed fields.
+    ///
+    /// The error is spanned at the fields if the AST is for a struct, otherwise
+    /// at the type name.
+    ///
+    /// # Parameters
+    ///
+    /// * `macro_name`: Display name of the macro, e.g. `MyDerive`.
+    fn check_fields_unnamed_for(&self, macro_name: &str) -> syn::Result<()>;
+
+    /// Returns an enum variant with the same fields as this struct.
+    ///
+    /// * Field attributes are kept, so field-level helper attributes still
+    ///   apply to the variant.
+    /// * Field visibility is removed, as variant fields cannot declare their
+    ///   own.
+    /// * The struct's attributes are not kept, as they usually do not apply to
+    ///   a variant, e.g. `#[derive(..)]` or `#[repr(..)]`.
+    ///
+    /// Variants do not have their own generics, so the enum must declare the
+    /// generic parameters that the fields reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `ident`: Name of the variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a struct.
+    fn to_variant(&self, ident: Ident) -> Variant;
+
+    /// Returns an enum variant with the same fields as this struct.
+    ///
+    /// See [`to_variant`] for how the fields are converted.
+    ///
+    /// # Parameters
+    ///
+    /// * `ident`: Name of the variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputStructExt;
+    /// use syn::{parse_quote, DeriveInput, Variant};
+    ///
+    /// let ast: DeriveInput = parse_quote! {
+    ///     #[derive(Debug)]
+    ///     pub struct Circle<T> {
+    ///         #[my::derive(skip)]
+    ///         pub radius: T,
+    ///     }
+    /// };
+    ///
+    /// let variant_expected: Variant = parse_quote! {
+    ///     Round {
+    ///         #[my::derive(skip)]
+    ///         radius: T,
+    ///     }
+    /// };
+    /// assert_eq!(variant_expected, ast.try_to_variant(parse_quote!(Round))?);
+    /// # Ok::<(), syn::Error>(())
+    /// ```
+    ///
+    /// [`to_variant`]: Self::to_variant
+    fn try_to_variant(&self, ident: Ident) -> syn::Result<Variant>;
 }
 
 impl DeriveInputStructExt for DeriveInput {
     fn data_struct(&self) -> &DataStruct {
-        if let Data::Struct(data_struct) = &self.data {
-            data_struct
-        } else {
-            panic!("This macro must be used on a struct.");
-        }
+        self.try_data_struct()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     fn data_struct_mut(&mut self) -> &mut DataStruct {
-        if let Data::Struct(data_struct) = &mut self.data {
-            data_struct
-        } else {
-            panic!("This macro must be used on a struct.");
-        }
+        self.try_data_struct_mut()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     fn fields(&self) -> &Fields {
-        if let Data::Struct(DataStruct { fields, .. }) = &self.data {
-            fields
+        self.try_fields()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn fields_mut(&mut self) -> &mut Fields {
+        self.try_fields_mut()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn fields_named(&self) -> &FieldsNamed {
+        self.try_fields_named()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn fields_named_mut(&mut self) -> &mut FieldsNamed {
+        self.try_fields_named_mut()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_data_struct(&self) -> syn::Result<&DataStruct> {
+        if let Data::Struct(data_struct) = &self.data {
+            Ok(data_struct)
         } else {
-            panic!("This macro must be used on a struct.");
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_STRUCT))
         }
     }
 
-    fn fields_mut(&mut self) -> &mut Fields {
-        if let Data::Struct(DataStruct { fields, .. }) = &mut self.data {
-            fields
+    fn try_data_struct_mut(&mut self) -> syn::Result<&mut DataStruct> {
+        if let Data::Struct(data_struct) = &mut self.data {
+            Ok(data_struct)
         } else {
-            panic!("This macro must be used on a struct.");
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_STRUCT))
         }
     }
 
-    fn fields_named(&self) -> &FieldsNamed {
+    fn try_fields(&self) -> syn::Result<&Fields> {
+        self.try_data_struct()
+            .map(|data_struct| &data_struct.fields)
+    }
+
+    fn try_fields_mut(&mut self) -> syn::Result<&mut Fields> {
+        self.try_data_struct_mut()
+            .map(|data_struct| &mut data_struct.fields)
+    }
+
+    fn try_fields_named(&self) -> syn::Result<&FieldsNamed> {
         if let Data::Struct(DataStruct {
             fields: Fields::Named(fields_named),
             ..
         }) = &self.data
         {
-            fields_named
+            Ok(fields_named)
         } else {
-            panic!("This macro must be used on a struct with named fields.");
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_NAMED))
         }
     }
 
-    fn fields_named_mut(&mut self) -> &mut FieldsNamed {
+    fn try_fields_named_mut(&mut self) -> syn::Result<&mut FieldsNamed> {
         if let Data::Struct(DataStruct {
             fields: Fields::Named(fields_named),
             ..
         }) = &mut self.data
         {
-            fields_named
+            Ok(fields_named)
         } else {
-            panic!("This macro must be used on a struct with named fields.");
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_NAMED))
         }
     }
 
@@ -151,27 +415,132 @@ impl DeriveInputStructExt for DeriveInput {
     }
 
     fn assert_fields_unit(&self) {
-        if !self.is_unit() {
-            panic!("This macro must be used on a unit struct.");
+        if let Err(error) = self.check_fields_unit() {
+            panic!("{}", error);
         }
     }
 
     fn assert_fields_named(&self) {
-        if !self.is_named() {
-            panic!("This macro must be used on a struct with named fields.");
+        if let Err(error) = self.check_fields_named() {
+            panic!("{}", error);
         }
     }
 
     fn assert_fields_unnamed(&self) {
-        if !self.is_tuple() {
-            panic!("This macro must be used on a struct with unnamed fields.");
+        if let Err(error) = self.check_fields_unnamed() {
+            panic!("{}", error);
+        }
+    }
+
+    fn check_fields_unit(&self) -> syn::Result<()> {
+        if self.is_unit() {
+            Ok(())
+        } else {
+            Err(shape_error(self, ERR_MUST_BE_UNIT))
+        }
+    }
+
+    fn check_fields_named(&self) -> syn::Result<()> {
+        if self.is_named() {
+            Ok(())
+        } else {
+            Err(shape_error(self, ERR_MUST_BE_NAMED))
+        }
+    }
+
+    fn check_fields_unnamed(&self) -> syn::Result<()> {
+        if self.is_tuple() {
+            Ok(())
+        } else {
+            Err(shape_error(self, ERR_MUST_BE_UNNAMED))
+        }
+    }
+
+    fn assert_fields_unit_for(&self, macro_name: &str) {
+        if let Err(error) = self.check_fields_unit_for(macro_name) {
+            panic!("{}", error);
+        }
+    }
+
+    fn assert_fields_named_for(&self, macro_name: &str) {
+        if let Err(error) = self.check_fields_named_for(macro_name) {
+            panic!("{}", error);
+        }
+    }
+
+    fn assert_fields_unnamed_for(&self, macro_name: &str) {
+        if let Err(error) = self.check_fields_unnamed_for(macro_name) {
+            panic!("{}", error);
+        }
+    }
+
+    fn check_fields_unit_for(&self, macro_name: &str) -> syn::Result<()> {
+        if self.is_unit() {
+            Ok(())
+        } else {
+            Err(shape_error(self, &must_be_used_on(macro_name, SHAPE_UNIT)))
+        }
+    }
+
+    fn check_fields_named_for(&self, macro_name: &str) -> syn::Result<()> {
+        if self.is_named() {
+            Ok(())
+        } else {
+            Err(shape_error(self, &must_be_used_on(macro_name, SHAPE_NAMED)))
+        }
+    }
+
+    fn check_fields_unnamed_for(&self, macro_name: &str) -> syn::Result<()> {
+        if self.is_tuple() {
+            Ok(())
+        } else {
+            Err(shape_error(
+                self,
+                &must_be_used_on(macro_name, SHAPE_UNNAMED),
+            ))
         }
     }
+
+    fn to_variant(&self, ident: Ident) -> Variant {
+        self.try_to_variant(ident)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_to_variant(&self, ident: Ident) -> syn::Result<Variant> {
+        let mut fields = self.try_fields()?.clone();
+        fields
+            .iter_mut()
+            .for_each(|field| field.vis = Visibility::Inherited);
+
+        Ok(Variant {
+            attrs: Vec::new(),
+            ident,
+            fields,
+            discriminant: None,
+        })
+    }
+}
+
+/// Returns the message for a macro used on an item of the wrong shape.
+fn must_be_used_on(macro_name: &str, shape: &str) -> String {
+    format!("`{macro_name}` must be used on {shape}.")
+}
+
+/// Returns an error spanned at the struct's fields, or at the type name if the
+/// AST is not for a struct or the struct is a unit struct.
+fn shape_error(derive_input: &DeriveInput, message: &str) -> Error {
+    match &derive_input.data {
+        Data::Struct(DataStruct {
+            fields: fields @ (Fields::Named(..) | Fields::Unnamed(..)),
+            ..
+        }) => Error::new_spanned(fields, message),
+        _ => Error::new(derive_input.ident.span(), message),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use syn::{parse_quote, DeriveInput, Fields, FieldsNamed};
+    use syn::{parse_quote, DeriveInput, Fields, FieldsNamed, Variant};
 
     use super::DeriveInputStructExt;
 
@@ -194,6 +563,118 @@ mod tests {
         ast.data_struct();
     } // kcov-ignore
 
+    #[test]
+    fn try_data_struct_returns_error_when_ast_is_not_struct() {
+        let ast: DeriveInput = parse_quote! {
+            enum NotStruct {}
+        };
+
+        let error = ast
+            .try_data_struct()
+            .expect_err("Expected an error for an enum.");
+
+        assert_eq!("This macro must be used on a struct.", error.to_string());
+    }
+
+    #[test]
+    fn try_fields_named_returns_error_when_fields_not_named() {
+        let mut ast: DeriveInput = parse_quote! {
+            struct Unnamed(u32);
+        };
+
+        assert!(ast.try_fields().is_ok());
+        let error = ast
+            .try_fields_named_mut()
+            .expect_err("Expected an error for a tuple struct.");
+
+        assert_eq!(
+            "This macro must be used on a struct with named fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn check_fields_unit_returns_error_when_fields_not_unit() {
+        let ast: DeriveInput = parse_quote! {
+            struct Named { a: u32 }
+        };
+
+        let error = ast
+            .check_fields_unit()
+            .expect_err("Expected an error for a struct with named fields.");
+
+        assert_eq!(
+            "This macro must be used on a unit struct.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn check_fields_unnamed_returns_error_when_not_struct() {
+        let ast: DeriveInput = parse_quote! {
+            enum NotStruct {}
+        };
+
+        let error = ast
+            .check_fields_unnamed()
+            .expect_err("Expected an error for an enum.");
+
+        assert_eq!(
+            "This macro must be used on a struct with unnamed fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn check_fields_named_returns_ok_when_fields_named() {
+        let ast: DeriveInput = parse_quote! {
+            struct Named { a: u32 }
+        };
+
+        assert!(ast.check_fields_named().is_ok());
+    }
+
+    #[test]
+    fn check_fields_unit_for_names_macro_in_error() {
+        let ast: DeriveInput = parse_quote! {
+            struct Named { a: u32 }
+        };
+
+        let error = ast
+            .check_fields_unit_for("MyDerive")
+            .expect_err("Expected an error for a struct with named fields.");
+
+        assert_eq!(
+            "`MyDerive` must be used on a unit struct.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn check_fields_unnamed_for_names_macro_in_error() {
+        let ast: DeriveInput = parse_quote! {
+            enum NotStruct {}
+        };
+
+        let error = ast
+            .check_fields_unnamed_for("#[my_attribute]")
+            .expect_err("Expected an error for an enum.");
+
+        assert_eq!(
+            "`#[my_attribute]` must be used on a struct with unnamed fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn check_fields_named_for_returns_ok_when_fields_named() {
+        let ast: DeriveInput = parse_quote! {
+            struct Named { a: u32 }
+        };
+
+        assert!(ast.check_fields_named_for("MyDerive").is_ok());
+    }
+
     #[test]
     fn data_struct_mut_returns_data_struct_mut() {
         let mut ast: DeriveInput = parse_quote! {
@@ -473,4 +954,72 @@ mod tests {
 
         ast.assert_fields_unnamed();
     } // kcov-ignore
+
+    #[test]
+    #[should_panic(expected = "`MyDerive` must be used on a unit struct.")]
+    fn assert_fields_unit_for_panics_when_fields_not_unit() {
+        let ast: DeriveInput = parse_quote! {
+            struct Named {}
+        };
+
+        ast.assert_fields_unit_for("MyDerive");
+    } // kcov-ignore
+
+    #[test]
+    #[should_panic(expected = "`MyDerive` must be used on a struct with named fields.")]
+    fn assert_fields_named_for_panics_when_fields_not_named() {
+        let ast: DeriveInput = parse_quote! {
+            struct Unit;
+        };
+
+        ast.assert_fields_named_for("MyDerive");
+    } // kcov-ignore
+
+    #[test]
+    fn assert_fields_unnamed_for_does_not_panic_when_fields_unnamed() {
+        let ast: DeriveInput = parse_quote! {
+            struct Unnamed(u32);
+        };
+
+        ast.assert_fields_unnamed_for("MyDerive");
+    }
+
+    #[test]
+    fn to_variant_keeps_field_attrs_and_removes_visibility() {
+        let ast: DeriveInput = parse_quote! {
+            #[repr(C)]
+            pub struct Pair(#[my::derive(skip)] pub u32, pub(crate) u64);
+        };
+
+        let variant = ast.to_variant(parse_quote!(Pair));
+
+        let variant_expected: Variant = parse_quote!(Pair(
+            #[my::derive(skip)]
+            u32,
+            u64
+        ));
+        assert_eq!(variant_expected, variant);
+    }
+
+    #[test]
+    fn to_variant_converts_unit_struct() {
+        let ast: DeriveInput = parse_quote! {
+            struct Unit;
+        };
+
+        let variant = ast.to_variant(parse_quote!(Empty));
+
+        let variant_expected: Variant = parse_quote!(Empty);
+        assert_eq!(variant_expected, variant);
+    }
+
+    #[test]
+    #[should_panic(expected = "This macro must be used on a struct.")]
+    fn to_variant_panics_when_ast_is_not_struct() {
+        let ast: DeriveInput = parse_quote! {
+            enum NotStruct {}
+        };
+
+        ast.to_variant(parse_quote!(Variant));
+    } // kcov-ignore
 }
@@ -0,0 +1,217 @@
+use syn::{Data, DataUnion, DeriveInput, Error, FieldsNamed};
+
+const ERR_MUST_BE_UNION: &str = "This macro must be used on a union.";
+
+/// Functions to make it ergonomic to work with `union` ASTs.
+///
+/// The field accessors are named `union_fields*` rather than `fields_named*`,
+/// so that they do not conflict with [`DeriveInputStructExt`] when both traits
+/// are in scope.
+///
+/// [`DeriveInputStructExt`]: crate::DeriveInputStructExt
+pub trait DeriveInputUnionExt {
+    /// Returns a reference to the data_union of a union's AST.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a union.
+    fn data_union(&self) -> &DataUnion;
+
+    /// Returns a mutable reference to the data_union of a union's AST.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a union.
+    fn data_union_mut(&mut self) -> &mut DataUnion;
+
+    /// Returns a reference to the fields of a union's AST.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a union.
+    fn union_fields(&self) -> &FieldsNamed;
+
+    /// Returns a mutable reference to the fields of a union's AST.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the AST is not for a union.
+    fn union_fields_mut(&mut self) -> &mut FieldsNamed;
+
+    /// Returns a reference to the data_union of a union's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// union.
+    fn try_data_union(&self) -> syn::Result<&DataUnion>;
+
+    /// Returns a mutable reference to the data_union of a union's AST.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the type name if the AST is not for a
+    /// union.
+    fn try_data_union_mut(&mut self) -> syn::Result<&mut DataUnion>;
+
+    /// Returns true if the AST is for a union.
+    fn is_union(&self) -> bool;
+
+    /// Panics if the AST is not for a union.
+    fn assert_union(&self);
+
+    /// Returns an error if the AST is not for a union.
+    ///
+    /// The error is spanned at the type name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::DeriveInputUnionExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let ast: DeriveInput = parse_quote!(
+    ///     struct Unit;
+    /// );
+    ///
+    /// let error = ast.check_union().unwrap_err();
+    /// assert_eq!("This macro must be used on a union.", error.to_string());
+    /// ```
+    fn check_union(&self) -> syn::Result<()>;
+}
+
+impl DeriveInputUnionExt for DeriveInput {
+    fn data_union(&self) -> &DataUnion {
+        self.try_data_union()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn data_union_mut(&mut self) -> &mut DataUnion {
+        self.try_data_union_mut()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn union_fields(&self) -> &FieldsNamed {
+        &self.data_union().fields
+    }
+
+    fn union_fields_mut(&mut self) -> &mut FieldsNamed {
+        &mut self.data_union_mut().fields
+    }
+
+    fn try_data_union(&self) -> syn::Result<&DataUnion> {
+        if let Data::Union(data_union) = &self.data {
+            Ok(data_union)
+        } else {
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_UNION))
+        }
+    }
+
+    fn try_data_union_mut(&mut self) -> syn::Result<&mut DataUnion> {
+        if let Data::Union(data_union) = &mut self.data {
+            Ok(data_union)
+        } else {
+            Err(Error::new(self.ident.span(), ERR_MUST_BE_UNION))
+        }
+    }
+
+    fn is_union(&self) -> bool {
+        matches!(self.data, Data::Union(..))
+    }
+
+    fn assert_union(&self) {
+        if let Err(error) = self.check_union() {
+            panic!("{}", error);
+        }
+    }
+
+    fn check_union(&self) -> syn::Result<()> {
+        self.try_data_union().map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::DeriveInputUnionExt;
+
+    #[test]
+    fn data_union_returns_data_union() {
+        let ast: DeriveInput = parse_quote! {
+            union Union { a: u32, b: f32 }
+        };
+
+        assert_eq!(2, ast.data_union().fields.named.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "This macro must be used on a union.")]
+    fn data_union_panics_when_ast_is_not_union() {
+        let ast: DeriveInput = parse_quote! {
+            struct NotUnion;
+        };
+
+        ast.data_union();
+    } // kcov-ignore
+
+    #[test]
+    fn union_fields_mut_returns_mutable_fields() {
+        let mut ast: DeriveInput = parse_quote! {
+            union Union { a: u32, b: f32 }
+        };
+
+        ast.union_fields_mut().named.pop();
+
+        assert_eq!(1, ast.union_fields().named.len());
+    }
+
+    #[test]
+    fn try_data_union_returns_error_when_ast_is_not_union() {
+        let mut ast: DeriveInput = parse_quote! {
+            enum NotUnion {}
+        };
+
+        let error = ast
+            .try_data_union_mut()
+            .expect_err("Expected an error for an enum.");
+
+        assert_eq!("This macro must be used on a union.", error.to_string());
+    }
+
+    #[test]
+    fn is_union_returns_true_only_for_union() {
+        let ast_union: DeriveInput = parse_quote! {
+            union Union { a: u32 }
+        };
+        let ast_struct: DeriveInput = parse_quote! {
+            struct Struct { a: u32 }
+        };
+        let ast_enum: DeriveInput = parse_quote! {
+            enum Enum {}
+        };
+
+        assert!(ast_union.is_union());
+        assert!(!ast_struct.is_union());
+        assert!(!ast_enum.is_union());
+    }
+
+    #[test]
+    fn assert_union_does_not_panic_when_union() {
+        let ast: DeriveInput = parse_quote! {
+            union Union { a: u32 }
+        };
+
+        ast.assert_union();
+    }
+
+    #[test]
+    #[should_panic(expected = "This macro must be used on a union.")]
+    fn assert_union_panics_when_not_union() {
+        let ast: DeriveInput = parse_quote! {
+            struct NotUnion { a: u32 }
+        };
+
+        ast.assert_union();
+    } // kcov-ignore
+}
@@ -0,0 +1,248 @@
+use std::fmt::Display;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::Error;
+
+/// Accumulates errors so that they can be reported together.
+///
+/// This allows a macro to report every problem in one compilation pass,
+/// instead of stopping at the first error.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::Diagnostics;
+/// use syn::{parse_quote, DeriveInput, Error};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct(u32, u64);
+/// };
+///
+/// let mut diagnostics = Diagnostics::new();
+/// if let syn::Data::Struct(data_struct) = &ast.data {
+///     data_struct
+///         .fields
+///         .iter()
+///         .filter(|field| field.ident.is_none())
+///         .for_each(|field| diagnostics.push(Error::new_spanned(field, "Fields must be named.")));
+/// }
+///
+/// let error = diagnostics.error_or(()).unwrap_err();
+/// assert_eq!(2, error.into_iter().count());
+/// ```
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    /// Accumulated errors.
+    errors: Vec<Error>,
+}
+
+impl Diagnostics {
+    /// Returns a new, empty `Diagnostics`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error.
+    pub fn push(&mut self, error: Error) {
+        self.errors.push(error);
+    }
+
+    /// Adds an error spanned at the given tokens.
+    ///
+    /// # Parameters
+    ///
+    /// * `tokens`: The tokens to span the error at.
+    /// * `message`: The error message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::{Diagnostics, FieldExt};
+    /// use syn::{parse_quote, FieldsNamed};
+    ///
+    /// let fields_named: FieldsNamed = parse_quote!({
+    ///     #[my::derive(skip)]
+    ///     a: u32,
+    ///     #[my::derive(skip)]
+    ///     b: u32,
+    /// });
+    ///
+    /// let mut diagnostics = Diagnostics::new();
+    /// fields_named
+    ///     .named
+    ///     .iter()
+    ///     .filter(|field| field.contains_tag(&parse_quote!(my::derive), &parse_quote!(skip)))
+    ///     .for_each(|field| diagnostics.push_spanned(field, "`skip` is not supported."));
+    ///
+    /// assert_eq!(2, diagnostics.len());
+    /// ```
+    pub fn push_spanned<T, M>(&mut self, tokens: T, message: M)
+    where
+        T: ToTokens,
+        M: Display,
+    {
+        self.push(Error::new_spanned(tokens, message));
+    }
+
+    /// Returns the value of `result`, or adds its error and returns `None`.
+    ///
+    /// # Parameters
+    ///
+    /// * `result`: The result to check.
+    pub fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.push(error);
+                None
+            }
+        }
+    }
+
+    /// Returns whether no errors have been added.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the number of errors that have been added.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `Ok(ok_value)` if no errors have been added, otherwise all
+    /// errors combined into one.
+    ///
+    /// # Parameters
+    ///
+    /// * `ok_value`: The value to return if there are no errors.
+    pub fn error_or<T>(self, ok_value: T) -> syn::Result<T> {
+        let mut errors = self.errors.into_iter();
+        match errors.next() {
+            Some(mut error) => {
+                errors.for_each(|error_next| error.combine(error_next));
+                Err(error)
+            }
+            None => Ok(ok_value),
+        }
+    }
+
+    /// Returns a `compile_error!` invocation for each error.
+    ///
+    /// This is empty if no errors have been added.
+    pub fn into_compile_errors(self) -> TokenStream {
+        self.errors.iter().map(Error::to_compile_error).collect()
+    }
+}
+
+impl Extend<Error> for Diagnostics {
+    fn extend<T>(&mut self, errors: T)
+    where
+        T: IntoIterator<Item = Error>,
+    {
+        self.errors.extend(errors);
+    }
+}
+
+impl FromIterator<Error> for Diagnostics {
+    fn from_iter<T>(errors: T) -> Self
+    where
+        T: IntoIterator<Item = Error>,
+    {
+        Self {
+            errors: errors.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type IntoIter = std::vec::IntoIter<Error>;
+    type Item = Error;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::Error;
+
+    use super::Diagnostics;
+
+    #[test]
+    fn error_or_returns_ok_when_empty() {
+        let diagnostics = Diagnostics::new();
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(1, diagnostics.error_or(1).unwrap());
+    }
+
+    #[test]
+    fn error_or_combines_errors_in_order() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Error::new(Span::call_site(), "first"));
+        diagnostics.extend(vec![
+            Error::new(Span::call_site(), "second"),
+            Error::new(Span::call_site(), "third"),
+        ]);
+
+        let error = diagnostics.error_or(()).unwrap_err();
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(vec!["first", "second", "third"], messages);
+    }
+
+    #[test]
+    fn check_adds_error_and_returns_none() {
+        let mut diagnostics = Diagnostics::new();
+
+        assert_eq!(Some(1), diagnostics.check(Ok(1)));
+        assert_eq!(
+            None,
+            diagnostics.check::<u32>(Err(Error::new(Span::call_site(), "failed")))
+        );
+        assert!(!diagnostics.is_empty());
+    }
+
+    #[test]
+    fn from_iter_collects_errors() {
+        let diagnostics = vec![
+            Error::new(Span::call_site(), "first"),
+            Error::new(Span::call_site(), "second"),
+        ]
+        .into_iter()
+        .collect::<Diagnostics>();
+
+        assert_eq!(2, diagnostics.len());
+    }
+
+    #[test]
+    fn push_spanned_adds_error_with_message() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_spanned(quote!(a: u32), "invalid field");
+
+        let error = diagnostics.error_or(()).unwrap_err();
+        assert_eq!("invalid field", error.to_string());
+    }
+
+    #[test]
+    fn into_compile_errors_emits_each_error() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Error::new(Span::call_site(), "first"));
+        diagnostics.push(Error::new(Span::call_site(), "second"));
+
+        let expected = quote! {
+            ::core::compile_error! { "first" }
+            ::core::compile_error! { "second" }
+        };
+        assert_eq!(
+            expected.to_string(),
+            diagnostics.into_compile_errors().to_string()
+        );
+    }
+}
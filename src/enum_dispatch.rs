@@ -0,0 +1,518 @@
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, ImplItemFn, ItemImpl, ItemTrait, Path, Signature,
+    TraitItem, TraitItemConst, TraitItemType, Type, Variant, Visibility,
+};
+
+use crate::{util, Diagnostics, VariantExt};
+
+const ERR_MUST_BE_ENUM: &str = "This macro must be used on an enum.";
+
+/// Returns an inherent impl block with methods that dispatch to the value
+/// wrapped by each variant.
+///
+/// Each method matches on `self`, and calls the method of the same name on
+/// the variant's inner value:
+///
+/// * `&self`, `&mut self`, and `self` methods call `inner.method(..)`.
+/// * `async` methods `.await` the inner method.
+/// * If the return type is `Self`, the return value is wrapped in the same
+///   variant.
+///
+/// The dispatched methods have the same visibility as the enum. This is the
+/// enum counterpart of [`DeriveInputNewtypeForwardExt::forward_methods`].
+///
+/// This requires the `items` feature.
+///
+/// # Parameters
+///
+/// * `ast`: The enum, whose variants each wrap a single value.
+/// * `signatures`: Signatures of the methods to dispatch.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The AST is not for an enum.
+/// * A variant does not wrap exactly one value, e.g. `Variant(Inner)`.
+/// * A signature does not take `self`.
+///
+/// All errors are combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::dispatch_methods;
+/// use syn::{parse_quote, DeriveInput, ItemImpl, Signature};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     pub enum Shape {
+///         Circle(Circle),
+///         Square(Square),
+///     }
+/// };
+/// let signatures: [Signature; 1] = [parse_quote!(fn area(&self, scale: f64) -> f64)];
+///
+/// let item_impl_expected: ItemImpl = parse_quote! {
+///     impl Shape {
+///         pub fn area(&self, scale: f64) -> f64 {
+///             match self {
+///                 Self::Circle(inner) => inner.area(scale),
+///                 Self::Square(inner) => inner.area(scale),
+///             }
+///         }
+///     }
+/// };
+/// assert_eq!(item_impl_expected, dispatch_methods(&ast, &signatures)?);
+/// # Ok::<(), syn::Error>(())
+/// ```
+///
+/// [`DeriveInputNewtypeForwardExt::forward_methods`]: crate::DeriveInputNewtypeForwardExt::forward_methods
+pub fn dispatch_methods(ast: &DeriveInput, signatures: &[Signature]) -> syn::Result<ItemImpl> {
+    let mut diagnostics = Diagnostics::new();
+    let variants = diagnostics.check(dispatch_variants(ast));
+    signatures.iter().for_each(|sig| {
+        diagnostics.check(check_receiver(sig));
+    });
+    let variants = diagnostics.error_or(variants)?.unwrap_or_default();
+
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let impl_item_fns = signatures.iter().map(|sig| {
+        let mut impl_item_fn = dispatching_fn(sig, &variants, None);
+        impl_item_fn.vis = ast.vis.clone();
+        impl_item_fn
+    });
+
+    Ok(parse_quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#impl_item_fns)*
+        }
+    })
+}
+
+/// Returns an impl of the trait for the enum that dispatches to the value
+/// wrapped by each variant.
+///
+/// Each method matches on `self`, and calls `<Inner as Trait>::method(..)`
+/// with the variant's inner value. The impl has an `Inner: Trait` bound for
+/// each variant's inner type. This is the enum counterpart of
+/// [`DeriveInputNewtypeForwardExt::forward_trait`], and renames conflicting
+/// trait generic parameters in the same way.
+///
+/// This requires the `items` feature.
+///
+/// # Parameters
+///
+/// * `ast`: The enum, whose variants each wrap a single value.
+/// * `item_trait`: The trait to dispatch.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The AST is not for an enum.
+/// * A variant does not wrap exactly one value, e.g. `Variant(Inner)`.
+/// * A trait method does not take `self`.
+/// * The trait has associated types or consts, as each variant's inner type may
+///   have a different value.
+///
+/// All errors are combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::dispatch_trait;
+/// use syn::{parse_quote, DeriveInput, ItemImpl, ItemTrait};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     enum Shape {
+///         Circle(Circle),
+///         Square(Square),
+///     }
+/// };
+/// let item_trait: ItemTrait = parse_quote! {
+///     trait Area {
+///         fn area(&self) -> f64;
+///     }
+/// };
+///
+/// let item_impl_expected: ItemImpl = parse_quote! {
+///     impl Area for Shape
+///     where
+///         Circle: Area,
+///         Square: Area
+///     {
+///         fn area(&self) -> f64 {
+///             match self {
+///                 Self::Circle(inner) => <Circle as Area>::area(inner),
+///                 Self::Square(inner) => <Square as Area>::area(inner),
+///             }
+///         }
+///     }
+/// };
+/// assert_eq!(item_impl_expected, dispatch_trait(&ast, &item_trait)?);
+/// # Ok::<(), syn::Error>(())
+/// ```
+///
+/// [`DeriveInputNewtypeForwardExt::forward_trait`]: crate::DeriveInputNewtypeForwardExt::forward_trait
+pub fn dispatch_trait(ast: &DeriveInput, item_trait: &ItemTrait) -> syn::Result<ItemImpl> {
+    let mut diagnostics = Diagnostics::new();
+    let variants = diagnostics.check(dispatch_variants(ast));
+    item_trait.items.iter().for_each(|trait_item| {
+        let error = match trait_item {
+            TraitItem::Fn(trait_item_fn) => check_receiver(&trait_item_fn.sig).err(),
+            TraitItem::Type(TraitItemType { ident, .. }) => Some(Error::new(
+                ident.span(),
+                format!(
+                    "Cannot dispatch associated type `{ident}`, as each variant may have a \
+                     different type."
+                ),
+            )),
+            TraitItem::Const(TraitItemConst { ident, .. }) => Some(Error::new(
+                ident.span(),
+                format!(
+                    "Cannot dispatch associated const `{ident}`, as each variant may have a \
+                     different value."
+                ),
+            )),
+            _ => None,
+        };
+        if let Some(error) = error {
+            diagnostics.push(error);
+        }
+    });
+    let variants = diagnostics.error_or(variants)?.unwrap_or_default();
+
+    let ident = &ast.ident;
+    let (item_trait, mut generics) = util::trait_impl_generics(item_trait, &ast.generics);
+    let trait_ident = &item_trait.ident;
+    let (_, trait_ty_generics, _) = item_trait.generics.split_for_impl();
+    let trait_path: Path = parse_quote!(#trait_ident #trait_ty_generics);
+
+    let where_clause = generics.make_where_clause();
+    variants.iter().for_each(|(_, inner_type)| {
+        where_clause
+            .predicates
+            .push(parse_quote!(#inner_type: #trait_path))
+    });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+
+    let impl_item_fns = item_trait
+        .items
+        .iter()
+        .filter_map(|trait_item| match trait_item {
+            TraitItem::Fn(trait_item_fn) => Some(dispatching_fn(
+                &trait_item_fn.sig,
+                &variants,
+                Some(&trait_path),
+            )),
+            _ => None,
+        });
+
+    Ok(parse_quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            #(#impl_item_fns)*
+        }
+    })
+}
+
+/// Returns each variant with the type of the value it wraps.
+fn dispatch_variants(ast: &DeriveInput) -> syn::Result<Vec<(&Variant, &Type)>> {
+    let data_enum = match &ast.data {
+        Data::Enum(data_enum) => data_enum,
+        Data::Struct(..) | Data::Union(..) => {
+            return Err(Error::new(ast.ident.span(), ERR_MUST_BE_ENUM));
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+    let variants = data_enum
+        .variants
+        .iter()
+        .filter_map(|variant| match variant.fields.iter().next() {
+            Some(inner_field) if variant.is_newtype() => Some((variant, &inner_field.ty)),
+            _ => {
+                diagnostics.push(Error::new(
+                    variant.ident.span(),
+                    format!(
+                        "Expected variant `{ident}` to wrap exactly one value, e.g. \
+                         `{ident}(Inner)`.",
+                        ident = variant.ident
+                    ),
+                ));
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    diagnostics.error_or(variants)
+}
+
+/// Returns an error if the signature does not take `self`.
+fn check_receiver(sig: &Signature) -> syn::Result<()> {
+    if sig.receiver().is_some() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            sig.ident.span(),
+            format!(
+                "Cannot dispatch `{}`, as it does not take `self`.",
+                sig.ident
+            ),
+        ))
+    }
+}
+
+/// Returns a function that matches on `self`, and calls the function of the
+/// same name on each variant's inner value.
+///
+/// If `trait_path` is `Some`, the function is called using fully qualified
+/// syntax.
+fn dispatching_fn(
+    sig: &Signature,
+    variants: &[(&Variant, &Type)],
+    trait_path: Option<&Path>,
+) -> ImplItemFn {
+    let mut sig = sig.clone();
+    let args = util::forwarding_args(&mut sig);
+    let fn_ident = &sig.ident;
+    let returns_self = util::returns_self(&sig);
+    let inner = Ident::new("inner", Span::mixed_site());
+
+    let match_arms = variants.iter().map(|(variant, inner_type)| {
+        let variant_ident = &variant.ident;
+        let call = match trait_path {
+            Some(trait_path) => {
+                quote!(<#inner_type as #trait_path>::#fn_ident(#inner #(, #args)*))
+            }
+            None => quote!(#inner.#fn_ident(#(#args),*)),
+        };
+        let call = if sig.asyncness.is_some() {
+            quote!(#call.await)
+        } else {
+            call
+        };
+        let body = if returns_self {
+            quote!(Self::#variant_ident(#call))
+        } else {
+            call
+        };
+
+        quote!(Self::#variant_ident(#inner) => #body,)
+    });
+    let scrutinee = match sig.receiver() {
+        Some(receiver) if variants.is_empty() && receiver.reference.is_some() => quote!(*self),
+        _ => quote!(self),
+    };
+    let block = parse_quote!({
+        match #scrutinee {
+            #(#match_arms)*
+        }
+    });
+
+    ImplItemFn {
+        attrs: Vec::new(),
+        vis: Visibility::Inherited,
+        defaultness: None,
+        sig,
+        block,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput, ItemImpl, ItemTrait, Signature};
+
+    use super::{dispatch_methods, dispatch_trait};
+
+    #[test]
+    fn dispatch_methods_wraps_self_return_and_awaits_async() {
+        let ast: DeriveInput = parse_quote! {
+            enum Shape<T> {
+                Circle(Circle<T>),
+                Square(Square),
+            }
+        };
+        let signatures: [Signature; 2] = [
+            parse_quote!(fn scaled(self, (x, y): (f64, f64)) -> Self),
+            parse_quote!(async fn load(&mut self, path: &str)),
+        ];
+
+        let item_impl = dispatch_methods(&ast, &signatures).unwrap();
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl<T> Shape<T> {
+                fn scaled(self, arg_1: (f64, f64)) -> Self {
+                    match self {
+                        Self::Circle(inner) => Self::Circle(inner.scaled(arg_1)),
+                        Self::Square(inner) => Self::Square(inner.scaled(arg_1)),
+                    }
+                }
+                async fn load(&mut self, path: &str) {
+                    match self {
+                        Self::Circle(inner) => inner.load(path).await,
+                        Self::Square(inner) => inner.load(path).await,
+                    }
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn dispatch_methods_dereferences_self_for_empty_enum() {
+        let ast: DeriveInput = parse_quote! {
+            enum Never {}
+        };
+        let signatures: [Signature; 1] = [parse_quote!(fn name(&self) -> &str)];
+
+        let item_impl = dispatch_methods(&ast, &signatures).unwrap();
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl Never {
+                fn name(&self) -> &str {
+                    match *self {}
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn dispatch_methods_combines_errors() {
+        let ast: DeriveInput = parse_quote! {
+            enum Shape {
+                Circle(Circle),
+                Empty,
+                Pair(u32, u32),
+            }
+        };
+        let signatures: [Signature; 1] = [parse_quote!(fn new() -> Self)];
+
+        let error = dispatch_methods(&ast, &signatures).unwrap_err();
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "Expected variant `Empty` to wrap exactly one value, e.g. `Empty(Inner)`.",
+                "Expected variant `Pair` to wrap exactly one value, e.g. `Pair(Inner)`.",
+                "Cannot dispatch `new`, as it does not take `self`.",
+            ],
+            messages
+        );
+    }
+
+    #[test]
+    fn dispatch_methods_returns_error_when_not_enum() {
+        let ast: DeriveInput = parse_quote! {
+            struct Shape(Circle);
+        };
+
+        let error = dispatch_methods(&ast, &[]).unwrap_err();
+
+        assert_eq!("This macro must be used on an enum.", error.to_string());
+    }
+
+    #[test]
+    fn dispatch_trait_adds_trait_generics_and_bounds() {
+        let ast: DeriveInput = parse_quote! {
+            enum Shape<T> where T: Copy {
+                Circle(Circle<T>),
+                Square(Square),
+            }
+        };
+        let item_trait: ItemTrait = parse_quote! {
+            trait Scale<U = f64> {
+                fn scale(&mut self, factor: U);
+            }
+        };
+
+        let item_impl = dispatch_trait(&ast, &item_trait).unwrap();
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl<T, U> Scale<U> for Shape<T>
+            where
+                T: Copy,
+                Circle<T>: Scale<U>,
+                Square: Scale<U>
+            {
+                fn scale(&mut self, factor: U) {
+                    match self {
+                        Self::Circle(inner) => <Circle<T> as Scale<U> >::scale(inner, factor),
+                        Self::Square(inner) => <Square as Scale<U> >::scale(inner, factor),
+                    }
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn dispatch_trait_renames_trait_generics_that_conflict() {
+        let ast: DeriveInput = parse_quote! {
+            enum Shape<T> {
+                Circle(Circle<T>),
+            }
+        };
+        let item_trait: ItemTrait = parse_quote! {
+            trait Scale<T> {
+                fn scale(&mut self, factor: T);
+            }
+        };
+
+        let item_impl =
+            dispatch_trait(&ast, &item_trait).expect("Expected trait to be dispatched.");
+
+        let item_impl_expected: ItemImpl = parse_quote! {
+            impl<T, T1> Scale<T1> for Shape<T>
+            where
+                Circle<T>: Scale<T1>
+            {
+                fn scale(&mut self, factor: T1) {
+                    match self {
+                        Self::Circle(inner) => <Circle<T> as Scale<T1> >::scale(inner, factor),
+                    }
+                }
+            }
+        };
+        assert_eq!(item_impl_expected, item_impl);
+    }
+
+    #[test]
+    fn dispatch_trait_returns_error_for_associated_items() {
+        let ast: DeriveInput = parse_quote! {
+            enum Shape {
+                Circle(Circle),
+            }
+        };
+        let item_trait: ItemTrait = parse_quote! {
+            trait Area {
+                type Output;
+                const SIDES: u32;
+                fn area(&self) -> Self::Output;
+            }
+        };
+
+        let error = dispatch_trait(&ast, &item_trait).unwrap_err();
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "Cannot dispatch associated type `Output`, as each variant may have a \
+                 different type.",
+                "Cannot dispatch associated const `SIDES`, as each variant may have a \
+                 different value.",
+            ],
+            messages
+        );
+    }
+}
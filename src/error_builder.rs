@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::Error;
+
+/// Builds an error with secondary notes at other spans.
+///
+/// Each note is a separate error combined into the primary error, so the
+/// compiler reports the primary message and then each note at its own span.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::ErrorBuilder;
+/// use syn::{parse_quote, Meta};
+///
+/// let first: Meta = parse_quote!(rename = "a");
+/// let duplicate: Meta = parse_quote!(rename = "b");
+///
+/// let error = ErrorBuilder::new_spanned(&duplicate, "duplicate `rename` parameter")
+///     .note_spanned(&first, "first defined here")
+///     .build();
+///
+/// let messages = error
+///     .into_iter()
+///     .map(|error| error.to_string())
+///     .collect::<Vec<String>>();
+/// assert_eq!(
+///     vec!["duplicate `rename` parameter", "first defined here"],
+///     messages
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ErrorBuilder {
+    /// The primary error, combined with any notes.
+    error: Error,
+}
+
+impl ErrorBuilder {
+    /// Returns a new `ErrorBuilder` with the primary message at the given
+    /// span.
+    ///
+    /// # Parameters
+    ///
+    /// * `span`: Span of the primary error.
+    /// * `message`: The primary error message.
+    pub fn new<M>(span: Span, message: M) -> Self
+    where
+        M: Display,
+    {
+        Self {
+            error: Error::new(span, message),
+        }
+    }
+
+    /// Returns a new `ErrorBuilder` with the primary message spanning the
+    /// given tokens.
+    ///
+    /// # Parameters
+    ///
+    /// * `tokens`: Tokens that the primary error spans.
+    /// * `message`: The primary error message.
+    pub fn new_spanned<T, M>(tokens: T, message: M) -> Self
+    where
+        T: ToTokens,
+        M: Display,
+    {
+        Self {
+            error: Error::new_spanned(tokens, message),
+        }
+    }
+
+    /// Adds a note at the given span.
+    ///
+    /// # Parameters
+    ///
+    /// * `span`: Span of the note.
+    /// * `message`: The note message, e.g. "first defined here".
+    pub fn note<M>(mut self, span: Span, message: M) -> Self
+    where
+        M: Display,
+    {
+        self.error.combine(Error::new(span, message));
+        self
+    }
+
+    /// Adds a note spanning the given tokens.
+    ///
+    /// # Parameters
+    ///
+    /// * `tokens`: Tokens that the note spans.
+    /// * `message`: The note message, e.g. "first defined here".
+    pub fn note_spanned<T, M>(mut self, tokens: T, message: M) -> Self
+    where
+        T: ToTokens,
+        M: Display,
+    {
+        self.error.combine(Error::new_spanned(tokens, message));
+        self
+    }
+
+    /// Returns the primary error combined with its notes.
+    pub fn build(self) -> Error {
+        self.error
+    }
+}
+
+impl From<ErrorBuilder> for Error {
+    fn from(error_builder: ErrorBuilder) -> Self {
+        error_builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::Error;
+
+    use super::ErrorBuilder;
+
+    #[test]
+    fn build_returns_primary_error_without_notes() {
+        let error = ErrorBuilder::new(Span::call_site(), "primary").build();
+
+        assert_eq!(1, error.into_iter().count());
+    }
+
+    #[test]
+    fn build_combines_notes_in_order() {
+        let error = Error::from(
+            ErrorBuilder::new(Span::call_site(), "primary")
+                .note(Span::call_site(), "note 1")
+                .note_spanned(quote!(a), "note 2"),
+        );
+
+        let expected = quote! {
+            ::core::compile_error! { "primary" }
+            ::core::compile_error! { "note 1" }
+            ::core::compile_error! { "note 2" }
+        };
+        assert_eq!(expected.to_string(), error.to_compile_error().to_string());
+    }
+}
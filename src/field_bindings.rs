@@ -0,0 +1,448 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Expr, Fields, Ident, Index, Member};
+
+/// Generates construction, pattern, and destructuring forms of fields from a
+/// single configuration.
+///
+/// Each field is bound to a variable, named:
+///
+/// * Named fields: `{prefix}{field_name}`, by default `field_name`.
+/// * Tuple fields: `{prefix}{index}`, by default `_0`, `_1`, etc.
+///
+/// Options may be set per field by index:
+///
+/// * [`skip`](Self::skip): The field is not bound. It is constructed using
+///   `Default::default()`.
+/// * [`rename`](Self::rename): The field is bound to the given variable name.
+/// * [`cloned`](Self::cloned): The field's variable is cloned when
+///   constructing.
+/// * [`by_ref`](Self::by_ref): The field is bound by reference when matching or
+///   destructuring.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::FieldBindings;
+/// use quote::quote;
+/// use syn::{parse_quote, Fields, FieldsNamed};
+///
+/// let fields_named: FieldsNamed = parse_quote!({ a: u32, b: String, c: u8 });
+/// let fields = Fields::from(fields_named);
+///
+/// let field_bindings = FieldBindings::new(&fields)
+///     .prefix("self_")
+///     .by_ref(1)
+///     .cloned(1)
+///     .skip(2);
+///
+/// let pattern_form = field_bindings.pattern_form();
+/// let construction_form = field_bindings.construction_form();
+/// let tokens = quote! {
+///     let Struct #pattern_form = self;
+///     Struct #construction_form
+/// };
+///
+/// let expected = quote! {
+///     let Struct { a: self_a, b: ref self_b, .. } = self;
+///     Struct {
+///         a: self_a,
+///         b: self_b.clone(),
+///         c: ::core::default::Default::default(),
+///     }
+/// };
+/// assert_eq!(expected.to_string(), tokens.to_string());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FieldBindings<'f> {
+    /// The fields to generate forms for.
+    fields: &'f Fields,
+    /// Prefix for variable names.
+    prefix: Option<String>,
+    /// Options for each field, by index.
+    field_options: Vec<FieldOptions>,
+}
+
+/// Options for binding a single field.
+#[derive(Clone, Debug, Default)]
+struct FieldOptions {
+    /// Whether the field is not bound.
+    skip: bool,
+    /// Variable name to bind the field to.
+    rename: Option<Ident>,
+    /// Whether the field's value is cloned.
+    cloned: bool,
+    /// Whether the field is bound by reference.
+    by_ref: bool,
+}
+
+/// A field with its member, variable name, and options.
+struct FieldBinding<'b> {
+    /// Field name or index.
+    member: Member,
+    /// Variable that the field is bound to.
+    binding: Ident,
+    /// Options for the field.
+    options: &'b FieldOptions,
+}
+
+impl<'f> FieldBindings<'f> {
+    /// Returns a new `FieldBindings` for the given fields.
+    pub fn new(fields: &'f Fields) -> Self {
+        Self {
+            fields,
+            prefix: None,
+            field_options: vec![FieldOptions::default(); fields.len()],
+        }
+    }
+
+    /// Sets the prefix for variable names.
+    ///
+    /// Renamed fields are not prefixed. Tuple fields use `_` in place of an
+    /// empty prefix, as `0` is not a valid variable name.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Skips binding the field at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn skip(mut self, index: usize) -> Self {
+        self.field_options[index].skip = true;
+        self
+    }
+
+    /// Binds the field at `index` to the given variable name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn rename(mut self, index: usize, binding: Ident) -> Self {
+        self.field_options[index].rename = Some(binding);
+        self
+    }
+
+    /// Clones the variable of the field at `index` when constructing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn cloned(mut self, index: usize) -> Self {
+        self.field_options[index].cloned = true;
+        self
+    }
+
+    /// Binds the field at `index` by reference when matching or
+    /// destructuring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn by_ref(mut self, index: usize) -> Self {
+        self.field_options[index].by_ref = true;
+        self
+    }
+
+    /// Returns a token stream of the construction form of the fields.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(_0, _1.clone(),)`
+    /// * Named fields: `{ field_0: field_0, field_1: field_1.clone(), }`
+    ///
+    /// Skipped fields are constructed using `Default::default()`.
+    pub fn construction_form(&self) -> TokenStream {
+        let values = self.field_bindings().map(|field_binding| {
+            let FieldBinding {
+                member,
+                binding,
+                options,
+            } = field_binding;
+            let value = if options.skip {
+                quote!(::core::default::Default::default())
+            } else if options.cloned {
+                quote!(#binding.clone())
+            } else {
+                quote!(#binding)
+            };
+
+            match member {
+                Member::Named(_) => quote!(#member: #value, ),
+                Member::Unnamed(_) => quote!(#value, ),
+            }
+        });
+
+        self.surround(quote!(#(#values)*))
+    }
+
+    /// Returns a token stream of the pattern form of the fields.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(_0, ref _1, _,)`
+    /// * Named fields: `{ field_0: field_0, field_1: ref field_1, .. }`
+    ///
+    /// Skipped tuple fields are matched with `_`, and skipped named fields are
+    /// matched with `..`.
+    pub fn pattern_form(&self) -> TokenStream {
+        let mut has_skipped = false;
+        let patterns = self
+            .field_bindings()
+            .filter_map(|field_binding| {
+                let FieldBinding {
+                    member,
+                    binding,
+                    options,
+                } = field_binding;
+                let by_ref = if options.by_ref {
+                    Some(quote!(ref))
+                } else {
+                    None
+                };
+
+                match (&member, options.skip) {
+                    (Member::Named(_), true) => {
+                        has_skipped = true;
+                        None
+                    }
+                    (Member::Named(_), false) => Some(quote!(#member: #by_ref #binding, )),
+                    (Member::Unnamed(_), true) => Some(quote!(_,)),
+                    (Member::Unnamed(_), false) => Some(quote!(#by_ref #binding, )),
+                }
+            })
+            .collect::<Vec<TokenStream>>();
+
+        let rest = if has_skipped { Some(quote!(..)) } else { None };
+        self.surround(quote!(#(#patterns)* #rest))
+    }
+
+    /// Returns `let` statements that bind each field of `source` to its
+    /// variable.
+    ///
+    /// * `let field_0 = source.field_0;`
+    /// * `let field_1 = &source.field_1;` for fields bound by reference.
+    ///
+    /// This binds the same variables as [`pattern_form`](Self::pattern_form),
+    /// using field access instead of a pattern. Skipped fields are not bound.
+    ///
+    /// # Parameters
+    ///
+    /// * `source`: Expression of the value to destructure, e.g. `self`.
+    pub fn destructuring_form(&self, source: &Expr) -> TokenStream {
+        self.field_bindings()
+            .filter(|field_binding| !field_binding.options.skip)
+            .map(|field_binding| {
+                let FieldBinding {
+                    member,
+                    binding,
+                    options,
+                } = field_binding;
+                let by_ref = if options.by_ref {
+                    Some(quote!(&))
+                } else {
+                    None
+                };
+
+                quote!(let #binding = #by_ref #source.#member;)
+            })
+            .collect()
+    }
+
+    /// Returns each field's member, variable name, and options.
+    fn field_bindings(&self) -> impl Iterator<Item = FieldBinding<'_>> {
+        self.fields
+            .iter()
+            .zip(self.field_options.iter())
+            .enumerate()
+            .map(move |(index, (field, options))| {
+                let (member, binding) = match &field.ident {
+                    Some(ident) => {
+                        // Raw identifiers keep their `r#` when used as is, but `format_ident!`
+                        // drops it when prefixed, as `p_r#type` is not a valid identifier.
+                        let binding = match self.prefix.as_deref() {
+                            Some(prefix) => format_ident!("{}{}", prefix, ident),
+                            None => ident.clone(),
+                        };
+                        (Member::Named(ident.clone()), binding)
+                    }
+                    None => {
+                        let prefix = self
+                            .prefix
+                            .as_deref()
+                            .filter(|prefix| !prefix.is_empty())
+                            .unwrap_or("_");
+                        let binding =
+                            Ident::new(&format!("{}{}", prefix, index), Span::call_site());
+                        (Member::Unnamed(Index::from(index)), binding)
+                    }
+                };
+                let binding = options.rename.clone().unwrap_or(binding);
+
+                FieldBinding {
+                    member,
+                    binding,
+                    options,
+                }
+            })
+    }
+
+    /// Surrounds the tokens with braces or parentheses depending on the kind
+    /// of fields.
+    fn surround(&self, tokens: TokenStream) -> TokenStream {
+        match self.fields {
+            Fields::Unit => TokenStream::new(),
+            Fields::Unnamed(_) => quote!((#tokens)),
+            Fields::Named(_) => quote!({ #tokens }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, Fields, FieldsNamed, FieldsUnnamed};
+
+    use super::FieldBindings;
+
+    #[test]
+    fn forms_are_empty_for_unit_fields() {
+        let field_bindings = FieldBindings::new(&Fields::Unit);
+
+        assert!(field_bindings.construction_form().is_empty());
+        assert!(field_bindings.pattern_form().is_empty());
+        assert!(field_bindings
+            .destructuring_form(&parse_quote!(self))
+            .is_empty());
+    }
+
+    #[test]
+    fn default_forms_match_construction_form() {
+        let fields_unnamed: FieldsUnnamed = parse_quote!((u32, u32));
+        let fields = Fields::from(fields_unnamed);
+        let field_bindings = FieldBindings::new(&fields);
+
+        assert_eq!(
+            quote!((_0, _1,)).to_string(),
+            field_bindings.construction_form().to_string()
+        );
+        assert_eq!(
+            quote!((_0, _1,)).to_string(),
+            field_bindings.pattern_form().to_string()
+        );
+    }
+
+    #[test]
+    fn forms_support_raw_identifier_fields() {
+        let fields_named: FieldsNamed = parse_quote!({ r#type: u32 });
+        let fields = Fields::from(fields_named);
+        let field_bindings = FieldBindings::new(&fields);
+
+        assert_eq!(
+            quote! {
+                let r#type = self.r#type;
+            }
+            .to_string(),
+            field_bindings
+                .destructuring_form(&parse_quote!(self))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn forms_strip_raw_prefix_when_prefixed() {
+        let fields_named: FieldsNamed = parse_quote!({ r#type: u32 });
+        let fields = Fields::from(fields_named);
+        let field_bindings = FieldBindings::new(&fields).prefix("p_");
+
+        assert_eq!(
+            quote! {
+                let p_type = self.r#type;
+            }
+            .to_string(),
+            field_bindings
+                .destructuring_form(&parse_quote!(self))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn forms_use_underscore_for_empty_tuple_field_prefix() {
+        let fields_unnamed: FieldsUnnamed = parse_quote!((u32, u32));
+        let fields = Fields::from(fields_unnamed);
+        let field_bindings = FieldBindings::new(&fields).prefix("");
+
+        assert_eq!(
+            quote!((_0, _1,)).to_string(),
+            field_bindings.pattern_form().to_string()
+        );
+    }
+
+    #[test]
+    fn forms_apply_tuple_field_options() {
+        let fields_unnamed: FieldsUnnamed = parse_quote!((u32, String, u8, u16));
+        let fields = Fields::from(fields_unnamed);
+        let field_bindings = FieldBindings::new(&fields)
+            .prefix("a")
+            .by_ref(1)
+            .cloned(1)
+            .skip(2)
+            .rename(3, parse_quote!(last));
+
+        assert_eq!(
+            quote!((a0, a1.clone(), ::core::default::Default::default(), last,)).to_string(),
+            field_bindings.construction_form().to_string()
+        );
+        assert_eq!(
+            quote! { (a0, ref a1, _, last,) }.to_string(),
+            field_bindings.pattern_form().to_string()
+        );
+        assert_eq!(
+            quote! {
+                let a0 = self.0;
+                let a1 = &self.1;
+                let last = self.3;
+            }
+            .to_string(),
+            field_bindings
+                .destructuring_form(&parse_quote!(self))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn forms_apply_named_field_options() {
+        let fields_named: FieldsNamed = parse_quote!({ a: u32, b: String, c: u8 });
+        let fields = Fields::from(fields_named);
+        let field_bindings = FieldBindings::new(&fields)
+            .skip(0)
+            .rename(1, parse_quote!(name))
+            .cloned(2);
+
+        assert_eq!(
+            quote!({
+                a: ::core::default::Default::default(),
+                b: name,
+                c: c.clone(),
+            })
+            .to_string(),
+            field_bindings.construction_form().to_string()
+        );
+        assert_eq!(
+            quote!({ b: name, c: c, .. }).to_string(),
+            field_bindings.pattern_form().to_string()
+        );
+        assert_eq!(
+            quote! {
+                let name = other.b;
+                let c = other.c;
+            }
+            .to_string(),
+            field_bindings
+                .destructuring_form(&parse_quote!(other))
+                .to_string()
+        );
+    }
+}
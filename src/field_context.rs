@@ -0,0 +1,78 @@
+use proc_macro2::Ident;
+use syn::{Data, DataEnum, DataStruct, DataUnion, Field, Variant};
+
+/// A field along with where it is located in its type.
+///
+/// This is passed to [`DeriveInputExt::for_each_field`] and
+/// [`DeriveInputExt::map_fields`], so that field level code generation works
+/// the same way for structs, enums, and unions.
+///
+/// [`DeriveInputExt::for_each_field`]: crate::DeriveInputExt::for_each_field
+/// [`DeriveInputExt::map_fields`]: crate::DeriveInputExt::map_fields
+#[derive(Clone, Copy, Debug)]
+pub struct FieldContext<'f> {
+    /// The field.
+    pub field: &'f Field,
+    /// Index of the field within its struct, variant, or union.
+    pub index: usize,
+    /// The enum variant that owns the field, if the type is an enum.
+    pub variant: Option<&'f Variant>,
+}
+
+/// Returns an iterator over each field in the data, along with its context.
+pub(crate) fn field_contexts(data: &Data) -> Box<dyn Iterator<Item = FieldContext<'_>> + '_> {
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => Box::new(fields.iter().enumerate().map(
+            |(index, field)| FieldContext {
+                field,
+                index,
+                variant: None,
+            },
+        )),
+        Data::Enum(DataEnum { variants, .. }) => Box::new(variants.iter().flat_map(|variant| {
+            variant
+                .fields
+                .iter()
+                .enumerate()
+                .map(move |(index, field)| FieldContext {
+                    field,
+                    index,
+                    variant: Some(variant),
+                })
+        })),
+        Data::Union(DataUnion { fields, .. }) => Box::new(fields.named.iter().enumerate().map(
+            |(index, field)| FieldContext {
+                field,
+                index,
+                variant: None,
+            },
+        )),
+    }
+}
+
+/// Calls the closure with each field in the data, along with the ident of the
+/// variant that owns it and its index.
+pub(crate) fn for_each_field_mut<F>(data: &mut Data, mut f: F)
+where
+    F: FnMut(Option<&Ident>, usize, &mut Field),
+{
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => fields
+            .iter_mut()
+            .enumerate()
+            .for_each(|(index, field)| f(None, index, field)),
+        Data::Enum(DataEnum { variants, .. }) => variants.iter_mut().for_each(|variant| {
+            let variant_ident = &variant.ident;
+            variant
+                .fields
+                .iter_mut()
+                .enumerate()
+                .for_each(|(index, field)| f(Some(variant_ident), index, field))
+        }),
+        Data::Union(DataUnion { fields, .. }) => fields
+            .named
+            .iter_mut()
+            .enumerate()
+            .for_each(|(index, field)| f(None, index, field)),
+    }
+}
@@ -1,4 +1,8 @@
-use syn::{Field, Ident, Meta, Path, PathSegment, Type, TypePath};
+use syn::{
+    parse_quote, punctuated::Punctuated, AngleBracketedGenericArguments, Error, Expr, ExprLit,
+    ExprPath, Field, GenericArgument, Ident, Lifetime, Lit, Meta, MetaNameValue, Path,
+    PathArguments, PathSegment, Token, Type, TypePath, TypeReference,
+};
 
 use crate::util;
 
@@ -9,6 +13,21 @@ pub trait FieldExt {
     /// For example, the `PhantomData` in `std::marker::PhantomData<T>`.
     fn type_name(&self) -> &Ident;
 
+    /// Returns the simple type name of a field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the field type if it is not a path.
+    fn try_type_name(&self) -> syn::Result<&Ident>;
+
+    /// Returns the generic arguments of the field type's last path segment.
+    ///
+    /// For example, the `K, V` in `std::collections::HashMap<K, V>`.
+    ///
+    /// Returns `None` if the field type is not a path, or the last segment
+    /// does not have angle bracketed arguments.
+    fn generic_args(&self) -> Option<&Punctuated<GenericArgument, Token![,]>>;
+
     /// Returns whether the field is `PhantomData`.
     ///
     /// Note that the detection is a string comparison instead of a type ID
@@ -18,6 +37,17 @@ pub trait FieldExt {
     /// * `use other_crate::OtherType as PhantomData;`
     fn is_phantom_data(&self) -> bool;
 
+    /// Returns whether the field type is a reference, e.g. `&'a T`.
+    fn is_reference(&self) -> bool;
+
+    /// Returns whether the field type is a mutable reference, e.g.
+    /// `&'a mut T`.
+    fn is_mut_reference(&self) -> bool;
+
+    /// Returns the lifetime of the field type if it is a reference with a
+    /// lifetime, e.g. `'a` in `&'a T`.
+    fn reference_lifetime(&self) -> Option<&Lifetime>;
+
     /// Returns whether a field contains a given `#[namespace(tag)]` attribute.
     ///
     /// # Parameters
@@ -39,6 +69,11 @@ pub trait FieldExt {
 
     /// Returns the parameters from `#[namespace(param1, param2, ..)]`.
     ///
+    /// Malformed attributes are ignored. Use [`namespace_nested_metas_checked`]
+    /// to report them instead.
+    ///
+    /// [`namespace_nested_metas_checked`]: crate::namespace_nested_metas_checked
+    ///
     /// # Parameters
     ///
     /// * `namespace`: The `path()` of the first-level attribute.
@@ -58,35 +93,128 @@ pub trait FieldExt {
 
     /// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`.
     ///
+    /// Malformed attributes and `tag` parameters are ignored. Use
+    /// [`tag_nested_metas_checked`] to report them instead.
+    ///
+    /// [`tag_nested_metas_checked`]: crate::tag_nested_metas_checked
+    ///
     /// # Parameters
     ///
     /// * `namespace`: The `path()` of the first-level attribute.
     /// * `tag`: The `path()` of the second-level attribute.
     fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta>;
+
+    /// Returns the default value expression from `#[namespace(default..)]`.
+    ///
+    /// The following forms are supported:
+    ///
+    /// * `#[namespace(default)]`: `::core::default::Default::default()`.
+    /// * `#[namespace(default = 42)]`: The expression, `42`.
+    /// * `#[namespace(default = "Vec::new()")]`: The string contents parsed as
+    ///   an expression, `Vec::new()`.
+    ///
+    /// Returns `Ok(None)` if there is no `default` parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    ///
+    /// * `default` is in list form, e.g. `#[namespace(default(..))]`.
+    /// * The string form does not parse as an expression.
+    /// * `default` is specified more than once.
+    fn default_value(&self, namespace: &Path) -> syn::Result<Option<Expr>>;
+
+    /// Returns the function path from `#[namespace(tag = "path::to::fn")]` or
+    /// `#[namespace(tag(path::to::fn))]`.
+    ///
+    /// The unquoted form `#[namespace(tag = path::to::fn)]` is also supported.
+    ///
+    /// Returns `Ok(None)` if there is no `tag` parameter.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanned at the value if:
+    ///
+    /// * The value does not parse as a path.
+    /// * `tag` has no value, e.g. `#[namespace(tag)]`.
+    /// * `tag` is specified more than once.
+    fn tag_fn_path(&self, namespace: &Path, tag: &Path) -> syn::Result<Option<Path>>;
 }
 
 impl FieldExt for Field {
     fn type_name(&self) -> &Ident {
+        self.try_type_name()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_type_name(&self) -> syn::Result<&Ident> {
         if let Type::Path(TypePath { path, .. }) = &self.ty {
             if let Some(PathSegment { ident, .. }) = path.segments.last() {
-                return ident;
+                return Ok(ident);
             }
         }
-        // kcov-ignore-start
-        panic!(
-            "Expected {}field type to be a `Path` with a segment.",
-            self.ident
-                .as_ref()
-                .map(|ident| format!("`{:?}` ", ident))
-                .unwrap_or_else(|| String::from(""))
-        );
-        // kcov-ignore-end
+        Err(Error::new_spanned(
+            &self.ty,
+            format!(
+                "Expected {}field type to be a `Path` with a segment.",
+                self.ident
+                    .as_ref()
+                    .map(|ident| format!("`{}` ", ident))
+                    .unwrap_or_default()
+            ),
+        ))
+    }
+
+    fn generic_args(&self) -> Option<&Punctuated<GenericArgument, Token![,]>> {
+        if let Type::Path(TypePath { path, .. }) = &self.ty {
+            if let Some(PathSegment {
+                arguments:
+                    PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+                ..
+            }) = path.segments.last()
+            {
+                return Some(args);
+            }
+        }
+
+        None
     }
 
     fn is_phantom_data(&self) -> bool {
         self.type_name() == "PhantomData"
     }
 
+    fn is_reference(&self) -> bool {
+        matches!(&self.ty, Type::Reference(..))
+    }
+
+    fn is_mut_reference(&self) -> bool {
+        matches!(
+            &self.ty,
+            Type::Reference(TypeReference {
+                mutability: Some(..),
+                ..
+            })
+        )
+    }
+
+    fn reference_lifetime(&self) -> Option<&Lifetime> {
+        if let Type::Reference(TypeReference { lifetime, .. }) = &self.ty {
+            lifetime.as_ref()
+        } else {
+            None
+        }
+    }
+
     fn contains_tag(&self, namespace: &Path, tag: &Path) -> bool {
         util::contains_tag(&self.attrs, namespace, tag)
     }
@@ -106,11 +234,98 @@ impl FieldExt for Field {
     fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta> {
         util::tag_parameters(&self.attrs, namespace, tag)
     }
+
+    fn default_value(&self, namespace: &Path) -> syn::Result<Option<Expr>> {
+        let mut default_metas = util::namespace_nested_metas_iter(&self.attrs, namespace)
+            .filter(|meta| meta.path().is_ident("default"));
+        let default_meta = default_metas.next();
+
+        if let Some(default_meta_second) = default_metas.next() {
+            return Err(Error::new_spanned(
+                default_meta_second,
+                format!(
+                    "Expected at most one `default` parameter for `#[{}(..)]`.",
+                    util::format_path(namespace)
+                ),
+            ));
+        }
+
+        default_meta
+            .map(|default_meta| match default_meta {
+                Meta::Path(_) => Ok(parse_quote!(::core::default::Default::default())),
+                Meta::NameValue(MetaNameValue {
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit_str),
+                            ..
+                        }),
+                    ..
+                }) => lit_str.parse::<Expr>(),
+                Meta::NameValue(MetaNameValue { value, .. }) => Ok(value),
+                Meta::List(meta_list) => Err(Error::new_spanned(
+                    meta_list,
+                    "Expected `default` or `default = ..`.",
+                )),
+            })
+            .transpose()
+    }
+
+    fn tag_fn_path(&self, namespace: &Path, tag: &Path) -> syn::Result<Option<Path>> {
+        let mut tag_metas = util::namespace_nested_metas_iter(&self.attrs, namespace)
+            .filter(|meta| meta.path() == tag);
+        let tag_meta = tag_metas.next();
+
+        if let Some(tag_meta_second) = tag_metas.next() {
+            return Err(Error::new_spanned(
+                tag_meta_second,
+                format!(
+                    "Expected at most one `{}` parameter for `#[{}(..)]`.",
+                    util::format_path(tag),
+                    util::format_path(namespace)
+                ),
+            ));
+        }
+
+        tag_meta
+            .map(|tag_meta| match tag_meta {
+                Meta::NameValue(MetaNameValue {
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit_str),
+                            ..
+                        }),
+                    ..
+                }) => lit_str.parse::<Path>(),
+                Meta::NameValue(MetaNameValue {
+                    value:
+                        Expr::Path(ExprPath {
+                            qself: None, path, ..
+                        }),
+                    ..
+                }) => Ok(path),
+                Meta::NameValue(MetaNameValue { value, .. }) => Err(Error::new_spanned(
+                    value,
+                    "Expected a path to a function, e.g. `\"path::to::function\"`.",
+                )),
+                Meta::List(meta_list) => meta_list.parse_args::<Path>(),
+                Meta::Path(path) => Err(Error::new_spanned(
+                    path,
+                    format!(
+                        "Expected `{tag} = \"path::to::function\"` or `{tag}(path::to::function)`.",
+                        tag = util::format_path(tag)
+                    ),
+                )),
+            })
+            .transpose()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use syn::{parse_quote, Fields, FieldsNamed, Meta, MetaNameValue};
+    use syn::{
+        parse_quote, punctuated::Punctuated, Expr, Fields, FieldsNamed, GenericArgument, Lifetime,
+        Meta, MetaNameValue, Path, Token,
+    };
 
     use super::FieldExt;
 
@@ -125,6 +340,49 @@ mod tests {
         assert_eq!(field.type_name(), "PhantomData");
     }
 
+    #[test]
+    fn try_type_name_returns_error_when_type_not_path() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub name: &'a str,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let error = field
+            .try_type_name()
+            .expect_err("Expected an error for a reference type.");
+
+        assert_eq!(
+            "Expected `name` field type to be a `Path` with a segment.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn generic_args_returns_last_segment_generic_args() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub name: std::collections::HashMap<'a, K, V>,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let generic_args_expected: Punctuated<GenericArgument, Token![,]> = parse_quote!('a, K, V);
+        assert_eq!(Some(&generic_args_expected), field.generic_args());
+    }
+
+    #[test]
+    fn generic_args_returns_none_when_no_generic_args() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub a: u32,
+            pub b: &'a Vec<u32>,
+        }};
+        let fields = Fields::from(fields_named);
+
+        fields
+            .iter()
+            .for_each(|field| assert_eq!(None, field.generic_args()));
+    }
+
     #[test]
     fn is_phantom_data_returns_true_for_phantom_data() {
         let fields_named: FieldsNamed = parse_quote! {{
@@ -147,6 +405,42 @@ mod tests {
         assert!(!field.is_phantom_data());
     }
 
+    #[test]
+    fn is_reference_returns_whether_type_is_reference() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub a: &'a u32,
+            pub b: &mut u32,
+            pub c: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let is_reference = fields
+            .iter()
+            .map(|field| (field.is_reference(), field.is_mut_reference()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(true, false), (true, true), (false, false)],
+            is_reference
+        );
+    }
+
+    #[test]
+    fn reference_lifetime_returns_lifetime_when_present() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub a: &'a u32,
+            pub b: &mut u32,
+            pub c: Cow<'a, str>,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let lifetime_expected: Lifetime = parse_quote!('a);
+        let lifetimes = fields
+            .iter()
+            .map(FieldExt::reference_lifetime)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Some(&lifetime_expected), None, None], lifetimes);
+    }
+
     #[test]
     fn namespace_parameter_returns_none_when_not_present() {
         let fields_named: FieldsNamed = parse_quote! {{
@@ -176,7 +470,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Expected exactly one parameter for `#[my::derive(..)]`.")]
+    #[should_panic(
+        expected = "Expected at most one parameter for `#[my::derive(..)]`, found `Magic :: One, Magic :: Two`."
+    )]
     fn namespace_parameter_panics_when_multiple_parameters_present() {
         let fields_named: FieldsNamed = parse_quote! {{
             #[my::derive(Magic::One, Magic::Two)]
@@ -254,7 +550,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Expected exactly one parameter for `#[my::derive(tag::name(..))]`.")]
+    #[should_panic(
+        expected = "Expected at most one parameter for `#[my::derive(tag::name(..))]`, found `Magic :: One, Magic :: Two`."
+    )]
     fn tag_parameter_panics_when_multiple_parameters_present() {
         let fields_named: FieldsNamed = parse_quote! {{
             #[my::derive(tag::name(Magic::One, Magic::Two))]
@@ -303,6 +601,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_value_returns_none_when_not_present() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(skip)]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        assert_eq!(None, field.default_value(&parse_quote!(my::derive))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_value_returns_default_call_when_path() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(default)]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let expr_expected: Expr = parse_quote!(::core::default::Default::default());
+        assert_eq!(
+            Some(expr_expected),
+            field.default_value(&parse_quote!(my::derive))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_value_returns_expr_when_name_value_literal() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(default = 42)]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let expr_expected: Expr = parse_quote!(42);
+        assert_eq!(
+            Some(expr_expected),
+            field.default_value(&parse_quote!(my::derive))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_value_returns_parsed_expr_when_name_value_string() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(default = "Vec::new()")]
+            pub name: Vec<u32>,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let expr_expected: Expr = parse_quote!(Vec::new());
+        assert_eq!(
+            Some(expr_expected),
+            field.default_value(&parse_quote!(my::derive))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_value_returns_error_when_list() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(default(1))]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let error = field
+            .default_value(&parse_quote!(my::derive))
+            .expect_err("Expected `default_value` to fail.");
+        assert_eq!("Expected `default` or `default = ..`.", error.to_string());
+    }
+
+    #[test]
+    fn default_value_returns_error_when_specified_multiple_times() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(default = 1)]
+            #[my::derive(default = 2)]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        let error = field
+            .default_value(&parse_quote!(my::derive))
+            .expect_err("Expected `default_value` to fail.");
+        assert_eq!(
+            "Expected at most one `default` parameter for `#[my::derive(..)]`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn tag_fn_path_returns_none_when_not_present() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(skip)]
+            pub name: u32,
+        }};
+        let fields = Fields::from(fields_named);
+        let field = fields.iter().next().expect("Expected field to exist.");
+
+        assert_eq!(
+            None,
+            field.tag_fn_path(&parse_quote!(my::derive), &parse_quote!(validate))?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tag_fn_path_returns_path_for_supported_forms() -> syn::Result<()> {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(validate = "path::to::function")]
+            pub a: u32,
+            #[my::derive(validate = path::to::function)]
+            pub b: u32,
+            #[my::derive(validate(path::to::function))]
+            pub c: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let path_expected: Path = parse_quote!(path::to::function);
+        fields.iter().try_for_each(|field| {
+            assert_eq!(
+                Some(&path_expected),
+                field
+                    .tag_fn_path(&parse_quote!(my::derive), &parse_quote!(validate))?
+                    .as_ref()
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn tag_fn_path_returns_error_when_value_is_not_path() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            #[my::derive(validate = "not a path")]
+            pub a: u32,
+            #[my::derive(validate = 1)]
+            pub b: u32,
+            #[my::derive(validate)]
+            pub c: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        fields.iter().for_each(|field| {
+            let result = field.tag_fn_path(&parse_quote!(my::derive), &parse_quote!(validate));
+            assert!(
+                result.is_err(),
+                "Expected error for field: {:?}",
+                field.ident
+            );
+        });
+    }
+
     mod fields_named {
         use proc_macro2::Span;
         use quote::quote;
@@ -0,0 +1,60 @@
+use syn::{Field, Index, Member};
+
+/// A field along with its index in its `Fields`.
+///
+/// This is returned by [`zip_fields`](crate::zip_fields).
+#[derive(Clone, Copy, Debug)]
+pub struct FieldRef<'f> {
+    /// The field.
+    pub field: &'f Field,
+    /// Index of the field within its `Fields`.
+    pub index: usize,
+}
+
+impl<'f> FieldRef<'f> {
+    /// Returns how the field is accessed, i.e. its name or index.
+    ///
+    /// This can be interpolated as `value.#member`.
+    pub fn member(&self) -> Member {
+        match &self.field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(self.index)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse::Parser, parse_quote, Field, Member};
+
+    use super::FieldRef;
+
+    #[test]
+    fn member_is_named_for_named_field() {
+        let field: Field = Field::parse_named
+            .parse2(quote!(pub a: u32))
+            .expect("Expected field to be parsed.");
+        let field_ref = FieldRef {
+            field: &field,
+            index: 3,
+        };
+
+        let member_expected: Member = parse_quote!(a);
+        assert_eq!(member_expected, field_ref.member());
+    }
+
+    #[test]
+    fn member_is_index_for_unnamed_field() {
+        let field: Field = Field::parse_unnamed
+            .parse2(quote!(u32))
+            .expect("Expected field to be parsed.");
+        let field_ref = FieldRef {
+            field: &field,
+            index: 3,
+        };
+
+        let member_expected: Member = parse_quote!(3);
+        assert_eq!(member_expected, field_ref.member());
+    }
+}
@@ -0,0 +1,220 @@
+use quote::ToTokens;
+use syn::{Error, Fields};
+
+use crate::{ErrorBuilder, FieldRef};
+
+/// How strictly two sets of fields must match to be compatible.
+///
+/// Used by [`fields_compatible`](crate::fields_compatible).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Corresponding fields must have the same type.
+    ExactTypes,
+    /// Corresponding fields may have different types.
+    NamesOnly,
+}
+
+/// A difference between two sets of fields.
+///
+/// Returned by [`fields_compatible`](crate::fields_compatible). `a` refers to
+/// the source fields, and `b` to the target fields.
+#[derive(Clone, Copy, Debug)]
+pub enum Mismatch<'f> {
+    /// A field in `b` has no counterpart in `a`.
+    Missing(FieldRef<'f>),
+    /// A field in `a` has no counterpart in `b`.
+    Extra(FieldRef<'f>),
+    /// Corresponding fields have different types.
+    Type {
+        /// The field in `a`.
+        a: FieldRef<'f>,
+        /// The field in `b`.
+        b: FieldRef<'f>,
+    },
+    /// The fields are of different kinds, e.g. named and tuple fields.
+    Kind {
+        /// The source fields.
+        a: &'f Fields,
+        /// The target fields.
+        b: &'f Fields,
+    },
+}
+
+impl Mismatch<'_> {
+    /// Returns an error describing this mismatch.
+    ///
+    /// The error is spanned at the field in `b` where there is one, otherwise
+    /// at the field in `a`.
+    pub fn to_error(&self) -> Error {
+        match self {
+            Mismatch::Missing(field_ref) => Error::new_spanned(
+                field_ref.field,
+                format!(
+                    "Field `{}` does not exist in the source fields.",
+                    field_ref.member().into_token_stream()
+                ),
+            ),
+            Mismatch::Extra(field_ref) => Error::new_spanned(
+                field_ref.field,
+                format!(
+                    "Field `{}` does not exist in the target fields.",
+                    field_ref.member().into_token_stream()
+                ),
+            ),
+            Mismatch::Type { a, b } => ErrorBuilder::new_spanned(
+                &b.field.ty,
+                format!(
+                    "Field `{}` has type `{}`, but the source field has type `{}`.",
+                    b.member().into_token_stream(),
+                    b.field.ty.to_token_stream(),
+                    a.field.ty.to_token_stream(),
+                ),
+            )
+            .note_spanned(&a.field.ty, "source field type is here")
+            .build(),
+            Mismatch::Kind { a, b } => Error::new_spanned(
+                b,
+                format!(
+                    "Expected {} fields, found {} fields.",
+                    fields_kind(a),
+                    fields_kind(b)
+                ),
+            ),
+        }
+    }
+}
+
+/// Returns the corresponding fields of `a` and `b`, and fields that have no
+/// counterpart.
+///
+/// Named fields are matched by name, in the order of `a`. Tuple fields are
+/// matched by position.
+pub(crate) fn match_fields<'f>(
+    a: &'f Fields,
+    b: &'f Fields,
+) -> (Vec<(FieldRef<'f>, FieldRef<'f>)>, Vec<Mismatch<'f>>) {
+    let field_refs = |fields: &'f Fields| {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| FieldRef { field, index })
+            .collect::<Vec<FieldRef<'f>>>()
+    };
+    let field_refs_a = field_refs(a);
+    let field_refs_b = field_refs(b);
+
+    let mut mismatches = Vec::new();
+    let pairs = match (a, b) {
+        (Fields::Unit, Fields::Unit) => Vec::new(),
+        (Fields::Named(_), Fields::Named(_)) => {
+            let find = |field_refs: &[FieldRef<'f>], field_ref: &FieldRef<'f>| {
+                field_refs
+                    .iter()
+                    .find(|field_ref_other| field_ref_other.field.ident == field_ref.field.ident)
+                    .copied()
+            };
+
+            let pairs = field_refs_a
+                .iter()
+                .filter_map(|field_ref_a| match find(&field_refs_b, field_ref_a) {
+                    Some(field_ref_b) => Some((*field_ref_a, field_ref_b)),
+                    None => {
+                        mismatches.push(Mismatch::Extra(*field_ref_a));
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            field_refs_b
+                .iter()
+                .filter(|field_ref_b| find(&field_refs_a, field_ref_b).is_none())
+                .for_each(|field_ref_b| mismatches.push(Mismatch::Missing(*field_ref_b)));
+
+            pairs
+        }
+        (Fields::Unnamed(_), Fields::Unnamed(_)) => {
+            field_refs_a
+                .iter()
+                .skip(field_refs_b.len())
+                .for_each(|field_ref_a| mismatches.push(Mismatch::Extra(*field_ref_a)));
+            field_refs_b
+                .iter()
+                .skip(field_refs_a.len())
+                .for_each(|field_ref_b| mismatches.push(Mismatch::Missing(*field_ref_b)));
+
+            field_refs_a
+                .into_iter()
+                .zip(field_refs_b)
+                .collect::<Vec<_>>()
+        }
+        _ => {
+            mismatches.push(Mismatch::Kind { a, b });
+            Vec::new()
+        }
+    };
+
+    (pairs, mismatches)
+}
+
+/// Returns the kind of fields for use in error messages.
+fn fields_kind(fields: &Fields) -> &'static str {
+    match fields {
+        Fields::Unit => "unit",
+        Fields::Named(_) => "named",
+        Fields::Unnamed(_) => "tuple",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Fields, FieldsNamed, FieldsUnnamed};
+
+    use super::{match_fields, Mismatch};
+
+    #[test]
+    fn match_fields_reports_extra_and_missing_named_fields() {
+        let fields_a: FieldsNamed = parse_quote!({ a: u32, b: u32 });
+        let fields_b: FieldsNamed = parse_quote!({ c: u32, a: u32 });
+        let fields_a = Fields::from(fields_a);
+        let fields_b = Fields::from(fields_b);
+
+        let (pairs, mismatches) = match_fields(&fields_a, &fields_b);
+
+        assert_eq!(1, pairs.len());
+        assert_eq!((0, 1), (pairs[0].0.index, pairs[0].1.index));
+        assert!(matches!(
+            mismatches.as_slice(),
+            [Mismatch::Extra(extra), Mismatch::Missing(missing)]
+                if extra.index == 1 && missing.index == 0
+        ));
+    }
+
+    #[test]
+    fn match_fields_reports_missing_tuple_fields() {
+        let fields_a: FieldsUnnamed = parse_quote!((u32,));
+        let fields_b: FieldsUnnamed = parse_quote!((u32, u64));
+        let fields_a = Fields::from(fields_a);
+        let fields_b = Fields::from(fields_b);
+
+        let (pairs, mismatches) = match_fields(&fields_a, &fields_b);
+
+        assert_eq!(1, pairs.len());
+        assert!(matches!(
+            mismatches.as_slice(),
+            [Mismatch::Missing(missing)] if missing.index == 1
+        ));
+    }
+
+    #[test]
+    fn match_fields_reports_kind_mismatch() {
+        let fields_a: FieldsUnnamed = parse_quote!((u32,));
+        let fields_a = Fields::from(fields_a);
+
+        let (pairs, mismatches) = match_fields(&fields_a, &Fields::Unit);
+
+        assert!(pairs.is_empty());
+        assert_eq!(
+            "Expected tuple fields, found unit fields.",
+            mismatches[0].to_error().to_string()
+        );
+    }
+}
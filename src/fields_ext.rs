@@ -1,6 +1,10 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Fields, FieldsNamed, FieldsUnnamed, Ident};
+use syn::{
+    Error, Expr, Field, Fields, FieldsNamed, FieldsUnnamed, Ident, Index, Member, Path, Token, Type,
+};
+
+use crate::{util, CloneStrategy, Diagnostics, FieldRef};
 
 /// Functions to make it ergonomic to work with `Fields`.
 pub trait FieldsExt {
@@ -22,6 +26,257 @@ pub trait FieldsExt {
     ///
     /// # Examples
     fn construction_form(&self) -> TokenStream;
+
+    /// Returns a token stream of the construction form of the fields,
+    /// borrowing each field from `self`.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(&self.0, &self.1,)`
+    /// * Named fields: `{ field_0: &self.field_0, field_1: &self.field_1, }`
+    ///
+    /// If `mutability` is `Some`, the fields are borrowed mutably, e.g.
+    /// `&mut self.0`.
+    ///
+    /// # Parameters
+    ///
+    /// * `mutability`: Whether to borrow the fields mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsNamed};
+    ///
+    /// let fields_named: FieldsNamed = parse_quote!({ a: u32, b: String });
+    /// let fields = Fields::from(fields_named);
+    ///
+    /// let construction_form = fields.construction_form_refs(None);
+    /// let tokens = quote!(StructRef #construction_form);
+    ///
+    /// let expected = quote!(StructRef { a: &self.a, b: &self.b, });
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn construction_form_refs(&self, mutability: Option<Token![mut]>) -> TokenStream;
+
+    /// Returns a token stream of the pattern form of the fields, binding each
+    /// field by reference.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(ref _0, ref _1,)`
+    /// * Named fields: `{ ref field_0, ref field_1, }`
+    ///
+    /// This is useful when matching on `&self` without moving fields.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsUnnamed};
+    ///
+    /// let fields_unnamed: FieldsUnnamed = parse_quote!((u32, String));
+    /// let fields = Fields::from(fields_unnamed);
+    ///
+    /// let pattern_form = fields.pattern_form_ref();
+    /// let tokens = quote!(match self { MyEnum::Variant #pattern_form => {} });
+    ///
+    /// let expected = quote!(match self { MyEnum::Variant (ref _0, ref _1,) => {} });
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn pattern_form_ref(&self) -> TokenStream;
+
+    /// Returns a token stream of the pattern form of the fields, binding each
+    /// field by mutable reference.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(ref mut _0, ref mut _1,)`
+    /// * Named fields: `{ ref mut field_0, ref mut field_1, }`
+    ///
+    /// This is useful when matching on `&mut self` without moving fields.
+    fn pattern_form_ref_mut(&self) -> TokenStream;
+
+    /// Returns a token stream of the pattern form of the fields, ignoring
+    /// fields tagged with `#[namespace(tag)]`.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(_0, _, _2,)`
+    /// * Named fields: `{ field_0, field_2, .. }`
+    ///
+    /// Ignored tuple fields are matched with `_`, and ignored named fields are
+    /// matched with `..`. The bindings match
+    /// [`construction_form`](Self::construction_form).
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute, e.g. `skip`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsUnnamed};
+    ///
+    /// let fields_unnamed: FieldsUnnamed = parse_quote!((u32, #[my::derive(skip)] String, u8));
+    /// let fields = Fields::from(fields_unnamed);
+    ///
+    /// let pattern_form =
+    ///     fields.pattern_form_ignoring(&parse_quote!(my::derive), &parse_quote!(skip));
+    /// let tokens = quote!(match self { MyEnum::Variant #pattern_form => {} });
+    ///
+    /// let expected = quote!(match self { MyEnum::Variant (_0, _, _2,) => {} });
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn pattern_form_ignoring(&self, namespace: &Path, tag: &Path) -> TokenStream;
+
+    /// Returns a token stream of the construction form of the fields, taking
+    /// each field's value from `source`.
+    ///
+    /// For unit fields, this returns an empty token stream.
+    ///
+    /// * Tuple fields: `(source.0.clone(), source.1.clone(),)`
+    /// * Named fields: `{ field_0: source.field_0.clone(), .. }`
+    ///
+    /// The `.clone()` is only present for [`CloneStrategy::Clone`].
+    ///
+    /// # Parameters
+    ///
+    /// * `source`: Expression of the instance to take values from.
+    /// * `clone_strategy`: How to obtain each field's value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::{CloneStrategy, FieldsExt};
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsNamed};
+    ///
+    /// let fields_named: FieldsNamed = parse_quote!({ a: u32, b: String });
+    /// let fields = Fields::from(fields_named);
+    ///
+    /// let construction_form =
+    ///     fields.construction_form_from(&parse_quote!(self), CloneStrategy::Clone);
+    /// let tokens = quote!(Struct #construction_form);
+    ///
+    /// let expected = quote!(Struct { a: self.a.clone(), b: self.b.clone(), });
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn construction_form_from(&self, source: &Expr, clone_strategy: CloneStrategy) -> TokenStream;
+
+    /// Returns an iterator over the expressions accessing each field on
+    /// `self`, in order.
+    ///
+    /// * Tuple fields: `self.0`, `self.1`
+    /// * Named fields: `self.field_0`, `self.field_1`
+    ///
+    /// This pairs with [`types`](Self::types) and
+    /// [`binding_idents`](Self::binding_idents) for zipping into generated
+    /// method bodies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsNamed};
+    ///
+    /// let fields_named: FieldsNamed = parse_quote!({ a: u32, b: String });
+    /// let fields = Fields::from(fields_named);
+    ///
+    /// let statements = fields
+    ///     .binding_idents()
+    ///     .zip(fields.types())
+    ///     .zip(fields.self_accessors())
+    ///     .map(|((binding, ty), accessor)| quote!(let #binding: &#ty = &#accessor;));
+    /// let tokens = quote!(#(#statements)*);
+    ///
+    /// let expected = quote! {
+    ///     let a: &u32 = &self.a;
+    ///     let b: &String = &self.b;
+    /// };
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn self_accessors(&self) -> impl Iterator<Item = TokenStream> + '_;
+
+    /// Returns an iterator over the types of each field, in order.
+    fn types(&self) -> impl Iterator<Item = &Type> + '_;
+
+    /// Returns an iterator over the member of each field, in order.
+    ///
+    /// * Tuple fields: `Member::Unnamed(0)`, `Member::Unnamed(1)`
+    /// * Named fields: `Member::Named(field_0)`, `Member::Named(field_1)`
+    ///
+    /// A member refers to a field regardless of the kind of fields, in field
+    /// access expressions, struct expressions, and patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use quote::quote;
+    /// use syn::{parse_quote, Fields, FieldsUnnamed};
+    ///
+    /// let fields_unnamed: FieldsUnnamed = parse_quote!((u32, String));
+    /// let fields = Fields::from(fields_unnamed);
+    ///
+    /// let members = fields.members();
+    /// let tokens = quote!(Struct { #(#members: Default::default()),* });
+    ///
+    /// let expected = quote!(Struct { 0: Default::default(), 1: Default::default() });
+    /// assert_eq!(expected.to_string(), tokens.to_string());
+    /// ```
+    fn members(&self) -> impl Iterator<Item = Member> + '_;
+
+    /// Returns an iterator over the variable names that each field is bound to
+    /// in [`construction_form`](Self::construction_form), in order.
+    ///
+    /// * Tuple fields: `_0`, `_1`
+    /// * Named fields: `field_0`, `field_1`
+    fn binding_idents(&self) -> impl Iterator<Item = Ident> + '_;
+
+    /// Returns an error if any field is tagged with `#[namespace(tag)]`.
+    ///
+    /// This is for derives where a tag is only valid on the container, and
+    /// not on fields.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for each tagged field, spanned at the tag. All errors
+    /// are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::FieldsExt;
+    /// use syn::{parse_quote, Fields, FieldsNamed};
+    ///
+    /// let fields_named: FieldsNamed = parse_quote!({
+    ///     #[my::derive(transparent)]
+    ///     a: u32,
+    ///     b: u32,
+    /// });
+    /// let fields = Fields::from(fields_named);
+    ///
+    /// let error = fields
+    ///     .assert_none_with_tag(&parse_quote!(my::derive), &parse_quote!(transparent))
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     "`#[my::derive(transparent)]` is not supported on field `a`.",
+    ///     error.to_string()
+    /// );
+    /// ```
+    fn assert_none_with_tag(&self, namespace: &Path, tag: &Path) -> syn::Result<()>;
 }
 
 impl FieldsExt for Fields {
@@ -63,14 +318,278 @@ impl FieldsExt for Fields {
             }
         }
     }
+
+    fn construction_form_refs(&self, mutability: Option<Token![mut]>) -> TokenStream {
+        match self {
+            Fields::Unit => TokenStream::new(),
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+                let token_stream =
+                    (0..unnamed.len()).fold(TokenStream::new(), |mut token_stream, n| {
+                        let index = Index::from(n);
+                        token_stream.extend(quote!(&#mutability self.#index, ));
+                        token_stream
+                    });
+
+                quote! { (#token_stream) }
+            }
+            Fields::Named(FieldsNamed { named, .. }) => {
+                let token_stream = named.iter().filter_map(|field| field.ident.as_ref()).fold(
+                    TokenStream::new(),
+                    |mut token_stream, field_name| {
+                        token_stream.extend(quote!(#field_name: &#mutability self.#field_name, ));
+                        token_stream
+                    },
+                );
+
+                quote!({ #token_stream })
+            }
+        }
+    }
+
+    fn pattern_form_ref(&self) -> TokenStream {
+        pattern_form_with_binding_mode(self, quote!(ref))
+    }
+
+    fn pattern_form_ref_mut(&self) -> TokenStream {
+        pattern_form_with_binding_mode(self, quote!(ref mut))
+    }
+
+    fn pattern_form_ignoring(&self, namespace: &Path, tag: &Path) -> TokenStream {
+        let is_ignored = |field: &Field| util::contains_tag(&field.attrs, namespace, tag);
+
+        match self {
+            Fields::Unit => TokenStream::new(),
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+                let patterns = unnamed.iter().enumerate().map(|(n, field)| {
+                    if is_ignored(field) {
+                        quote!(_)
+                    } else {
+                        let tuple_field = Ident::new(format!("_{}", n).as_str(), Span::call_site());
+                        quote!(#tuple_field)
+                    }
+                });
+
+                quote! { (#(#patterns, )*) }
+            }
+            Fields::Named(FieldsNamed { named, .. }) => {
+                let field_names = named
+                    .iter()
+                    .filter(|field| !is_ignored(field))
+                    .filter_map(|field| field.ident.as_ref());
+                let rest = if named.iter().any(is_ignored) {
+                    Some(quote!(..))
+                } else {
+                    None
+                };
+
+                quote!({ #(#field_names, )* #rest })
+            }
+        }
+    }
+
+    fn construction_form_from(&self, source: &Expr, clone_strategy: CloneStrategy) -> TokenStream {
+        let clone = match clone_strategy {
+            CloneStrategy::Clone => Some(quote!(.clone())),
+            CloneStrategy::Copy | CloneStrategy::Move => None,
+        };
+        let values = self.members().map(|member| match &member {
+            Member::Named(_) => quote!(#member: #source.#member #clone, ),
+            Member::Unnamed(_) => quote!(#source.#member #clone, ),
+        });
+
+        match self {
+            Fields::Unit => TokenStream::new(),
+            Fields::Unnamed(_) => quote!((#(#values)*)),
+            Fields::Named(_) => quote!({ #(#values)* }),
+        }
+    }
+
+    fn self_accessors(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.iter()
+            .enumerate()
+            .map(|(index, field)| match &field.ident {
+                Some(ident) => quote!(self.#ident),
+                None => {
+                    let index = Index::from(index);
+                    quote!(self.#index)
+                }
+            })
+    }
+
+    fn types(&self) -> impl Iterator<Item = &Type> + '_ {
+        self.iter().map(|field| &field.ty)
+    }
+
+    fn members(&self) -> impl Iterator<Item = Member> + '_ {
+        self.iter()
+            .enumerate()
+            .map(|(index, field)| FieldRef { field, index }.member())
+    }
+
+    fn binding_idents(&self) -> impl Iterator<Item = Ident> + '_ {
+        self.iter()
+            .enumerate()
+            .map(|(index, field)| match &field.ident {
+                Some(ident) => ident.clone(),
+                None => Ident::new(format!("_{}", index).as_str(), Span::call_site()),
+            })
+    }
+
+    fn assert_none_with_tag(&self, namespace: &Path, tag: &Path) -> syn::Result<()> {
+        let mut diagnostics = Diagnostics::new();
+
+        self.iter()
+            .enumerate()
+            .filter_map(|(index, field)| {
+                util::contains_tag_spanned(&field.attrs, namespace, tag)
+                    .map(|span| (FieldRef { field, index }, span))
+            })
+            .for_each(|(field_ref, span)| {
+                let member = match field_ref.member() {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(index) => index.index.to_string(),
+                };
+                diagnostics.push(Error::new(
+                    span,
+                    format!(
+                        "`#[{}({})]` is not supported on field `{}`.",
+                        util::format_path(namespace),
+                        util::format_path(tag),
+                        member
+                    ),
+                ));
+            });
+
+        diagnostics.error_or(())
+    }
+}
+
+/// Returns the pattern form of the fields, with each binding preceded by the
+/// given binding mode.
+fn pattern_form_with_binding_mode(fields: &Fields, binding_mode: TokenStream) -> TokenStream {
+    match fields {
+        Fields::Unit => TokenStream::new(),
+        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+            let token_stream =
+                (0..unnamed.len()).fold(TokenStream::new(), |mut token_stream, n| {
+                    let tuple_field = Ident::new(format!("_{}", n).as_str(), Span::call_site());
+                    token_stream.extend(quote!(#binding_mode #tuple_field, ));
+                    token_stream
+                });
+
+            quote! { (#token_stream) }
+        }
+        Fields::Named(FieldsNamed { named, .. }) => {
+            let token_stream = named.iter().filter_map(|field| field.ident.as_ref()).fold(
+                TokenStream::new(),
+                |mut token_stream, field_name| {
+                    token_stream.extend(quote!(#binding_mode #field_name, ));
+                    token_stream
+                },
+            );
+
+            quote!({ #token_stream })
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use quote::quote;
-    use syn::{parse_quote, Fields, FieldsNamed, FieldsUnnamed};
+    use syn::{parse_quote, Fields, FieldsNamed, FieldsUnnamed, Ident, Member, Type};
 
     use super::FieldsExt;
+    use crate::CloneStrategy;
+
+    #[test]
+    fn members_returns_named_members_for_named_fields() {
+        let fields_named: FieldsNamed = parse_quote!({ a: u32, b: u32 });
+        let fields = Fields::from(fields_named);
+
+        let members = fields.members().collect::<Vec<Member>>();
+
+        let members_expected: Vec<Member> = vec![parse_quote!(a), parse_quote!(b)];
+        assert_eq!(members_expected, members);
+    }
+
+    #[test]
+    fn members_returns_empty_for_unit_fields() {
+        assert_eq!(0, Fields::Unit.members().count());
+    }
+
+    #[test]
+    fn pattern_form_ignoring_uses_rest_pattern_for_named_fields() {
+        let fields_named: FieldsNamed = parse_quote!({
+            a: u32,
+            #[my::derive(skip)]
+            b: u32,
+            c: u32,
+        });
+        let fields = Fields::from(fields_named);
+
+        let pattern_form =
+            fields.pattern_form_ignoring(&parse_quote!(my::derive), &parse_quote!(skip));
+
+        assert_eq!(quote!({ a, c, .. }).to_string(), pattern_form.to_string());
+    }
+
+    #[test]
+    fn pattern_form_ignoring_matches_pattern_form_when_no_field_is_tagged() {
+        let fields_named: FieldsNamed = parse_quote!({ a: u32, b: u32 });
+        let fields = Fields::from(fields_named);
+
+        let pattern_form =
+            fields.pattern_form_ignoring(&parse_quote!(my::derive), &parse_quote!(skip));
+
+        assert_eq!(
+            fields.construction_form().to_string(),
+            pattern_form.to_string()
+        );
+    }
+
+    #[test]
+    fn assert_none_with_tag_returns_ok_when_no_field_is_tagged() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(
+            #[my::derive(skip)]
+            u32,
+            #[other(transparent)]
+            u32,
+        )};
+        let fields = Fields::from(fields_unnamed);
+
+        let result =
+            fields.assert_none_with_tag(&parse_quote!(my::derive), &parse_quote!(transparent));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn assert_none_with_tag_returns_error_for_each_tagged_field() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(
+            #[my::derive(transparent)]
+            u32,
+            u32,
+            #[my::derive(skip, transparent)]
+            u32,
+        )};
+        let fields = Fields::from(fields_unnamed);
+
+        let error = fields
+            .assert_none_with_tag(&parse_quote!(my::derive), &parse_quote!(transparent))
+            .expect_err("Expected tagged fields to produce an error.");
+
+        let messages = error
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![
+                "`#[my::derive(transparent)]` is not supported on field `0`.",
+                "`#[my::derive(transparent)]` is not supported on field `2`.",
+            ],
+            messages
+        );
+    }
 
     #[test]
     fn is_unit_returns_true_when_fields_unit() {
@@ -148,4 +667,154 @@ mod tests {
         let expected_tokens = quote!((_0,));
         assert_eq!(expected_tokens.to_string(), construction_tokens.to_string());
     }
+
+    #[test]
+    fn construction_form_refs_fields_unit_is_empty_token_stream() {
+        assert!(Fields::Unit.construction_form_refs(None).is_empty());
+    }
+
+    #[test]
+    fn construction_form_refs_fields_named_borrows_each_field() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub field_0: u32,
+            pub field_1: SomeType,
+        }};
+        let fields = Fields::from(fields_named);
+        let construction_tokens = fields.construction_form_refs(None);
+
+        let expected_tokens = quote!({ field_0: &self.field_0, field_1: &self.field_1, });
+        assert_eq!(expected_tokens.to_string(), construction_tokens.to_string());
+    }
+
+    #[test]
+    fn construction_form_refs_fields_unnamed_borrows_each_field_mutably() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(u32, u32)};
+        let fields = Fields::from(fields_unnamed);
+        let construction_tokens = fields.construction_form_refs(Some(Default::default()));
+
+        let expected_tokens = quote!((&mut self.0, &mut self.1,));
+        assert_eq!(expected_tokens.to_string(), construction_tokens.to_string());
+    }
+
+    #[test]
+    fn pattern_form_ref_fields_unit_is_empty_token_stream() {
+        assert!(Fields::Unit.pattern_form_ref().is_empty());
+        assert!(Fields::Unit.pattern_form_ref_mut().is_empty());
+    }
+
+    #[test]
+    fn pattern_form_ref_fields_named_binds_each_field_by_reference() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub field_0: u32,
+            pub field_1: SomeType,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let expected_tokens = quote!({ ref field_0, ref field_1, });
+        assert_eq!(
+            expected_tokens.to_string(),
+            fields.pattern_form_ref().to_string()
+        );
+    }
+
+    #[test]
+    fn pattern_form_ref_mut_fields_unnamed_binds_each_field_by_mutable_reference() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(u32, u32)};
+        let fields = Fields::from(fields_unnamed);
+
+        let expected_tokens = quote! { (ref mut _0, ref mut _1,) };
+        assert_eq!(
+            expected_tokens.to_string(),
+            fields.pattern_form_ref_mut().to_string()
+        );
+    }
+
+    #[test]
+    fn construction_form_from_fields_unit_is_empty_token_stream() {
+        assert!(Fields::Unit
+            .construction_form_from(&parse_quote!(self), CloneStrategy::Clone)
+            .is_empty());
+    }
+
+    #[test]
+    fn construction_form_from_clone_clones_each_field() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(u32, String)};
+        let fields = Fields::from(fields_unnamed);
+        let construction_tokens =
+            fields.construction_form_from(&parse_quote!(source), CloneStrategy::Clone);
+
+        let expected_tokens = quote! { (source.0.clone(), source.1.clone(),) };
+        assert_eq!(expected_tokens.to_string(), construction_tokens.to_string());
+    }
+
+    #[test]
+    fn construction_form_from_copy_and_move_access_each_field() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub field_0: u32,
+            pub field_1: u32,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let expected_tokens = quote!({ field_0: other.field_0, field_1: other.field_1, });
+        assert_eq!(
+            expected_tokens.to_string(),
+            fields
+                .construction_form_from(&parse_quote!(other), CloneStrategy::Copy)
+                .to_string()
+        );
+        assert_eq!(
+            expected_tokens.to_string(),
+            fields
+                .construction_form_from(&parse_quote!(other), CloneStrategy::Move)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn self_accessors_fields_unnamed_are_indices_on_self() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(u32, u32)};
+        let fields = Fields::from(fields_unnamed);
+
+        let accessors = fields
+            .self_accessors()
+            .map(|accessor| accessor.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec![quote!(self.0).to_string(), quote!(self.1).to_string()],
+            accessors
+        );
+    }
+
+    #[test]
+    fn types_and_binding_idents_are_in_field_order() {
+        let fields_named: FieldsNamed = parse_quote! {{
+            pub field_0: u32,
+            pub field_1: SomeType,
+        }};
+        let fields = Fields::from(fields_named);
+
+        let types_expected: Vec<Type> = vec![parse_quote!(u32), parse_quote!(SomeType)];
+        assert_eq!(
+            types_expected.iter().collect::<Vec<&Type>>(),
+            fields.types().collect::<Vec<&Type>>()
+        );
+        let binding_idents_expected: Vec<Ident> =
+            vec![parse_quote!(field_0), parse_quote!(field_1)];
+        assert_eq!(
+            binding_idents_expected,
+            fields.binding_idents().collect::<Vec<Ident>>()
+        );
+    }
+
+    #[test]
+    fn binding_idents_fields_unnamed_are_underscore_indices() {
+        let fields_unnamed: FieldsUnnamed = parse_quote! {(u32, u32)};
+        let fields = Fields::from(fields_unnamed);
+
+        let binding_idents_expected: Vec<Ident> = vec![parse_quote!(_0), parse_quote!(_1)];
+        assert_eq!(
+            binding_idents_expected,
+            fields.binding_idents().collect::<Vec<Ident>>()
+        );
+    }
 }
@@ -0,0 +1,288 @@
+use proc_macro2::Span;
+use syn::{punctuated::Punctuated, DeriveInput, Meta, Path, Token};
+
+use crate::{util, Diagnostics};
+
+/// State shared by the functions that generate a macro's output.
+///
+/// This bundles the values that are otherwise passed through every
+/// generation function:
+///
+/// * The path to the runtime crate, which users may override with a `crate`
+///   parameter.
+/// * The span to use for generated tokens.
+/// * The macro's name, for error messages.
+/// * A [`Diagnostics`] to accumulate errors.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::GenContext;
+/// use quote::quote;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my_derive(crate = ::renamed)]
+///     struct Struct;
+/// };
+///
+/// let gen_context = GenContext::derive(
+///     &ast,
+///     &parse_quote!(my_derive),
+///     parse_quote!(::my_crate),
+///     "MyDerive",
+/// );
+///
+/// let trait_path = gen_context.crate_item(&parse_quote!(MyTrait));
+/// let ident = &ast.ident;
+/// let tokens = quote!(impl #trait_path for #ident {});
+///
+/// assert_eq!(
+///     quote!(impl ::renamed::MyTrait for Struct {}).to_string(),
+///     tokens.to_string()
+/// );
+/// assert!(gen_context.error_or(()).is_ok());
+/// ```
+#[derive(Debug)]
+pub struct GenContext {
+    /// Path to the runtime crate.
+    crate_path: Path,
+    /// Span to use for generated tokens.
+    span: Span,
+    /// Name of the macro, for error messages.
+    macro_name: String,
+    /// Accumulated errors.
+    diagnostics: Diagnostics,
+}
+
+impl GenContext {
+    /// Returns a new `GenContext` at the call site.
+    ///
+    /// # Parameters
+    ///
+    /// * `crate_path`: Path to the runtime crate, e.g. `::my_crate`.
+    /// * `macro_name`: Name of the macro, e.g. `MyDerive`.
+    pub fn new(crate_path: Path, macro_name: &str) -> Self {
+        Self {
+            crate_path,
+            span: Span::call_site(),
+            macro_name: String::from(macro_name),
+            diagnostics: Diagnostics::new(),
+        }
+    }
+
+    /// Returns a new `GenContext` for a derive macro.
+    ///
+    /// The crate path is read from `#[namespace(crate = path)]` on the item,
+    /// falling back to `default_crate_path`. If a `#[namespace]` attribute
+    /// cannot be parsed, or the `crate` parameter is not a path, the error is
+    /// added to the diagnostics.
+    ///
+    /// # Parameters
+    ///
+    /// * `ast`: The item the derive is applied to.
+    /// * `namespace`: The `path()` of the derive's helper attribute.
+    /// * `default_crate_path`: Path to the runtime crate, e.g. `::my_crate`.
+    /// * `macro_name`: Name of the macro, e.g. `MyDerive`.
+    pub fn derive(
+        ast: &DeriveInput,
+        namespace: &Path,
+        default_crate_path: Path,
+        macro_name: &str,
+    ) -> Self {
+        let mut gen_context = Self::new(default_crate_path, macro_name);
+        let crate_meta = gen_context
+            .diagnostics
+            .check(util::namespace_nested_metas_checked(&ast.attrs, namespace))
+            .and_then(|metas| metas.into_iter().find(|meta| meta.path().is_ident("crate")));
+        gen_context.resolve_crate_path(crate_meta.as_ref());
+
+        gen_context
+    }
+
+    /// Returns a new `GenContext` for an attribute macro.
+    ///
+    /// The crate path is read from the `crate = path` argument, falling back
+    /// to `default_crate_path`. If the `crate` argument is not a path, the
+    /// error is added to the diagnostics.
+    ///
+    /// # Parameters
+    ///
+    /// * `args`: The attribute arguments, e.g. from [`attribute_macro`].
+    /// * `default_crate_path`: Path to the runtime crate, e.g. `::my_crate`.
+    /// * `macro_name`: Name of the macro, e.g. `my_attribute`.
+    ///
+    /// [`attribute_macro`]: crate::attribute_macro
+    pub fn attribute(
+        args: &Punctuated<Meta, Token![,]>,
+        default_crate_path: Path,
+        macro_name: &str,
+    ) -> Self {
+        let mut gen_context = Self::new(default_crate_path, macro_name);
+        let crate_meta = args.iter().find(|meta| meta.path().is_ident("crate"));
+        gen_context.resolve_crate_path(crate_meta);
+
+        gen_context
+    }
+
+    /// Sets the span to use for generated tokens.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Returns the path to the runtime crate.
+    pub fn crate_path(&self) -> &Path {
+        &self.crate_path
+    }
+
+    /// Returns the path to an item in the runtime crate.
+    ///
+    /// The identifiers of `path` are spanned at [`span`](Self::span).
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path of the item relative to the crate root, e.g. `MyTrait`.
+    pub fn crate_item(&self, path: &Path) -> Path {
+        let mut crate_item = self.crate_path.clone();
+        crate_item
+            .segments
+            .extend(path.segments.iter().cloned().map(|mut segment| {
+                segment.ident.set_span(self.span);
+                segment
+            }));
+        crate_item
+    }
+
+    /// Returns the span to use for generated tokens.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Returns the name of the macro.
+    pub fn macro_name(&self) -> &str {
+        &self.macro_name
+    }
+
+    /// Returns the accumulated errors.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Returns the accumulated errors, to add further errors.
+    pub fn diagnostics_mut(&mut self) -> &mut Diagnostics {
+        &mut self.diagnostics
+    }
+
+    /// Returns `Ok(ok_value)` if no errors have been added, otherwise all
+    /// errors combined into one.
+    ///
+    /// # Parameters
+    ///
+    /// * `ok_value`: The value to return if there are no errors.
+    pub fn error_or<T>(self, ok_value: T) -> syn::Result<T> {
+        self.diagnostics.error_or(ok_value)
+    }
+
+    /// Sets the crate path from a `crate = path` parameter, if present.
+    fn resolve_crate_path(&mut self, crate_meta: Option<&Meta>) {
+        if let Some(crate_meta) = crate_meta {
+            if let Some(crate_path) = self.diagnostics.check(util::expect_path(crate_meta)) {
+                self.crate_path = crate_path;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::{parse_quote, punctuated::Punctuated, DeriveInput, Meta, Path, Token};
+
+    use super::GenContext;
+
+    #[test]
+    fn derive_uses_default_crate_path_when_not_overridden() {
+        let ast: DeriveInput = parse_quote! {
+            #[my_derive(skip)]
+            struct Struct;
+        };
+
+        let gen_context = GenContext::derive(
+            &ast,
+            &parse_quote!(my_derive),
+            parse_quote!(::my_crate),
+            "MyDerive",
+        );
+
+        let crate_path_expected: Path = parse_quote!(::my_crate);
+        assert_eq!(&crate_path_expected, gen_context.crate_path());
+        assert_eq!("MyDerive", gen_context.macro_name());
+        assert!(gen_context.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn derive_reports_error_when_crate_is_not_path() {
+        let ast: DeriveInput = parse_quote! {
+            #[my_derive(crate = 1)]
+            struct Struct;
+        };
+
+        let gen_context = GenContext::derive(
+            &ast,
+            &parse_quote!(my_derive),
+            parse_quote!(::my_crate),
+            "MyDerive",
+        );
+
+        let crate_path_expected: Path = parse_quote!(::my_crate);
+        assert_eq!(&crate_path_expected, gen_context.crate_path());
+        let error = gen_context.error_or(()).unwrap_err();
+        assert_eq!("Expected a path for `crate`, found `1`.", error.to_string());
+    }
+
+    #[test]
+    fn derive_reports_error_when_attribute_is_malformed() {
+        let ast: DeriveInput = parse_quote! {
+            #[my_derive(crate = ::renamed, skip = )]
+            struct Struct;
+        };
+
+        let gen_context = GenContext::derive(
+            &ast,
+            &parse_quote!(my_derive),
+            parse_quote!(::my_crate),
+            "MyDerive",
+        );
+
+        let crate_path_expected: Path = parse_quote!(::my_crate);
+        assert_eq!(&crate_path_expected, gen_context.crate_path());
+        assert!(gen_context.error_or(()).is_err());
+    }
+
+    #[test]
+    fn attribute_reads_crate_path_from_args() {
+        let args: Punctuated<Meta, Token![,]> = parse_quote!(skip, crate = "renamed");
+
+        let gen_context = GenContext::attribute(&args, parse_quote!(::my_crate), "my_attribute")
+            .with_span(Span::mixed_site());
+
+        let item_expected: Path = parse_quote!(renamed::Item);
+        assert_eq!(item_expected, gen_context.crate_item(&parse_quote!(Item)));
+    }
+
+    #[test]
+    fn diagnostics_mut_accumulates_errors() {
+        let mut gen_context = GenContext::new(parse_quote!(::my_crate), "MyDerive");
+
+        gen_context
+            .diagnostics_mut()
+            .push_spanned(quote::quote!(a), "first");
+        gen_context
+            .diagnostics_mut()
+            .push_spanned(quote::quote!(b), "second");
+
+        let error = gen_context.error_or(()).unwrap_err();
+        assert_eq!(2, error.into_iter().count());
+    }
+}
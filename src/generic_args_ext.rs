@@ -0,0 +1,104 @@
+use syn::{AngleBracketedGenericArguments, GenericArgument, Path, PathArguments, Type, TypePath};
+
+/// Functions to make it ergonomic to access generic type arguments.
+///
+/// For paths and types, the arguments of the last path segment are used, e.g.
+/// `u32` and `String` in `std::result::Result<u32, String>`.
+pub trait GenericArgsExt {
+    /// Returns the type arguments, skipping lifetime and const arguments.
+    fn type_args(&self) -> Vec<&Type>;
+
+    /// Returns the `n`th type argument, skipping lifetime and const arguments.
+    ///
+    /// For example, `type_arg(1)` on `Result<T, E>` returns `E`, and
+    /// `type_arg(0)` on `Cow<'a, str>` returns `str`.
+    ///
+    /// # Parameters
+    ///
+    /// * `n`: Index of the type argument, starting from `0`.
+    fn type_arg(&self, n: usize) -> Option<&Type>;
+}
+
+impl GenericArgsExt for PathArguments {
+    fn type_args(&self) -> Vec<&Type> {
+        if let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) = self {
+            args.iter()
+                .filter_map(|arg| {
+                    if let GenericArgument::Type(ty) = arg {
+                        Some(ty)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn type_arg(&self, n: usize) -> Option<&Type> {
+        self.type_args().into_iter().nth(n)
+    }
+}
+
+impl GenericArgsExt for Path {
+    fn type_args(&self) -> Vec<&Type> {
+        self.segments
+            .last()
+            .map(|segment| segment.arguments.type_args())
+            .unwrap_or_default()
+    }
+
+    fn type_arg(&self, n: usize) -> Option<&Type> {
+        self.type_args().into_iter().nth(n)
+    }
+}
+
+impl GenericArgsExt for Type {
+    fn type_args(&self) -> Vec<&Type> {
+        if let Type::Path(TypePath { path, .. }) = self {
+            path.type_args()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn type_arg(&self, n: usize) -> Option<&Type> {
+        self.type_args().into_iter().nth(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Path, Type};
+
+    use super::GenericArgsExt;
+
+    #[test]
+    fn type_arg_returns_nth_type_argument() {
+        let ty: Type = parse_quote!(std::result::Result<u32, String>);
+
+        let type_expected_0: Type = parse_quote!(u32);
+        let type_expected_1: Type = parse_quote!(String);
+        assert_eq!(Some(&type_expected_0), ty.type_arg(0));
+        assert_eq!(Some(&type_expected_1), ty.type_arg(1));
+        assert_eq!(None, ty.type_arg(2));
+    }
+
+    #[test]
+    fn type_arg_skips_lifetimes_and_const_args() {
+        let path: Path = parse_quote!(Container<'a, T, 3, U>);
+
+        let type_expected_0: Type = parse_quote!(T);
+        let type_expected_1: Type = parse_quote!(U);
+        assert_eq!(vec![&type_expected_0, &type_expected_1], path.type_args());
+    }
+
+    #[test]
+    fn type_args_is_empty_for_non_path_types() {
+        let ty: Type = parse_quote!(&'a Vec<u32>);
+
+        assert!(ty.type_args().is_empty());
+        assert_eq!(None, ty.type_arg(0));
+    }
+}
@@ -0,0 +1,98 @@
+use syn::{GenericParam, Generics};
+
+/// Predicates on `Generics`, to decide which impl generation path to take.
+pub trait GenericsExt {
+    /// Returns whether there are any type parameters.
+    fn has_type_params(&self) -> bool;
+
+    /// Returns whether there are any lifetime parameters.
+    fn has_lifetimes(&self) -> bool;
+
+    /// Returns whether there are any const parameters.
+    fn has_const_params(&self) -> bool;
+
+    /// Returns whether there are no generic parameters and no where clause
+    /// predicates.
+    fn is_empty(&self) -> bool;
+}
+
+impl GenericsExt for Generics {
+    fn has_type_params(&self) -> bool {
+        self.params
+            .iter()
+            .any(|param| matches!(param, GenericParam::Type(_)))
+    }
+
+    fn has_lifetimes(&self) -> bool {
+        self.params
+            .iter()
+            .any(|param| matches!(param, GenericParam::Lifetime(_)))
+    }
+
+    fn has_const_params(&self) -> bool {
+        self.params
+            .iter()
+            .any(|param| matches!(param, GenericParam::Const(_)))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.params.is_empty()
+            && self
+                .where_clause
+                .as_ref()
+                .map(|where_clause| where_clause.predicates.is_empty())
+                .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::GenericsExt;
+
+    #[test]
+    fn predicates_return_false_for_no_generics() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct;
+        );
+
+        assert!(!ast.generics.has_type_params());
+        assert!(!ast.generics.has_lifetimes());
+        assert!(!ast.generics.has_const_params());
+        assert!(ast.generics.is_empty());
+    }
+
+    #[test]
+    fn predicates_return_true_for_each_kind_of_param() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct<'a, T, const N: usize>(&'a [T; N]);
+        );
+
+        assert!(ast.generics.has_type_params());
+        assert!(ast.generics.has_lifetimes());
+        assert!(ast.generics.has_const_params());
+        assert!(!ast.generics.is_empty());
+    }
+
+    #[test]
+    fn has_type_params_returns_false_for_lifetime_only() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct<'a>(&'a str);
+        );
+
+        assert!(!ast.generics.has_type_params());
+        assert!(ast.generics.has_lifetimes());
+    }
+
+    #[test]
+    fn is_empty_returns_false_for_where_clause_predicates() {
+        let ast: DeriveInput = parse_quote!(
+            struct Struct
+            where
+                Self: Sized;
+        );
+
+        assert!(!ast.generics.is_empty());
+    }
+}
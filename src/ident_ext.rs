@@ -21,6 +21,20 @@ pub trait IdentExt {
     fn prepend<S>(&self, prefix: S) -> Ident
     where
         S: quote::IdentFragment;
+
+    /// Returns a new `Ident` by joining this Ident and `other` with the
+    /// specified separator.
+    ///
+    /// For example, joining `my_struct` and `builder` with `"_"` returns
+    /// `my_struct_builder`.
+    ///
+    /// # Parameters
+    ///
+    /// * `separator`: Separator to insert between the two parts.
+    /// * `other`: Part to append after the separator.
+    fn join<S>(&self, separator: &str, other: S) -> Ident
+    where
+        S: quote::IdentFragment;
 }
 
 impl IdentExt for Ident {
@@ -37,6 +51,13 @@ impl IdentExt for Ident {
     {
         format_ident!("{}{}", suffix, self)
     }
+
+    fn join<S>(&self, separator: &str, other: S) -> Ident
+    where
+        S: quote::IdentFragment,
+    {
+        format_ident!("{}{}{}", self, separator, other)
+    }
 }
 
 #[cfg(test)]
@@ -91,4 +112,25 @@ mod tests {
 
         assert_eq!(Ident::new("TwoOne", Span::call_site()), one.prepend(two));
     }
+
+    #[test]
+    fn join_str_returns_joined_ident() {
+        let my_struct = Ident::new("my_struct", Span::call_site());
+
+        assert_eq!(
+            Ident::new("my_struct_builder", Span::call_site()),
+            my_struct.join("_", "builder")
+        );
+    }
+
+    #[test]
+    fn join_ident_returns_joined_ident() {
+        let my_struct = Ident::new("my_struct", Span::call_site());
+        let builder = Ident::new("builder", Span::call_site());
+
+        assert_eq!(
+            Ident::new("my_struct__builder", Span::call_site()),
+            my_struct.join("__", &builder)
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Path, PathArguments, PathSegment};
+
+/// Collects the paths that generated code refers to, and emits their `use`
+/// statements.
+///
+/// Generation code registers each path with [`add`], and uses the returned
+/// path in the generated code. The `use` statements are then emitted once by
+/// [`use_statements`], so that generated code does not refer to items that
+/// are not in scope.
+///
+/// If two paths have the same name, e.g. `core::fmt::Result` and
+/// `std::io::Result`, the first is imported, and the second is returned
+/// fully qualified.
+///
+/// The `use` statements conflict with items of the same name in the scope that
+/// they are emitted in, such as a user's `struct Display` (`E0255`). To avoid
+/// this, do one of:
+///
+/// * Emit the statements and the code using them within a generated block or
+///   module, e.g. `const _: () = { .. };` or [`wrap_in_module`].
+/// * [`reserve`] the names declared in the scope, such as from
+///   [`idents_in_input`], so that paths with those names are returned fully
+///   qualified.
+/// * Use [`Imports::fully_qualified`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::Imports;
+/// use quote::quote;
+/// use syn::parse_quote;
+///
+/// let mut imports = Imports::new();
+/// let display = imports.add(parse_quote!(::core::fmt::Display));
+/// let fmt_result = imports.add(parse_quote!(::core::fmt::Result));
+/// let io_result = imports.add(parse_quote!(::std::io::Result<()>));
+/// let display_again = imports.add(parse_quote!(::core::fmt::Display));
+///
+/// let use_statements = imports.use_statements();
+/// let tokens = quote! {
+///     #use_statements
+///     struct Types(#display, #fmt_result, #io_result, #display_again);
+/// };
+///
+/// let tokens_expected = quote! {
+///     use ::core::fmt::Display;
+///     use ::core::fmt::Result;
+///     struct Types(Display, Result, ::std::io::Result<()>, Display);
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+///
+/// [`add`]: Self::add
+/// [`idents_in_input`]: crate::idents_in_input
+/// [`reserve`]: Self::reserve
+/// [`use_statements`]: Self::use_statements
+/// [`wrap_in_module`]: crate::wrap_in_module
+#[derive(Clone, Debug, Default)]
+pub struct Imports {
+    /// Whether paths are returned fully qualified instead of imported.
+    fully_qualified: bool,
+    /// Paths to import, without generic arguments.
+    imports: Vec<Path>,
+    /// Names that are not imported, as they are declared in the scope.
+    reserved: HashSet<String>,
+}
+
+impl Imports {
+    /// Returns a new `Imports` that imports each path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `Imports` that returns each path fully qualified, and
+    /// emits no `use` statements.
+    ///
+    /// This is useful when generated code is placed where `use` statements
+    /// are not permitted, or may conflict with the caller's imports.
+    pub fn fully_qualified() -> Self {
+        Self {
+            fully_qualified: true,
+            ..Self::default()
+        }
+    }
+
+    /// Reserves names that are declared in the scope the `use` statements are
+    /// emitted in.
+    ///
+    /// Paths with a reserved name are returned fully qualified instead of
+    /// imported, so that the `use` statements do not conflict with the
+    /// scope's items.
+    ///
+    /// # Parameters
+    ///
+    /// * `names`: Names of items declared in the scope, e.g. from
+    ///   [`idents_in_input`](crate::idents_in_input).
+    pub fn reserve<I>(&mut self, names: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.reserved.extend(names.into_iter().map(Into::into));
+    }
+
+    /// Registers a path, and returns the path to use in generated code.
+    ///
+    /// The returned path is the last segment of `path`, including its generic
+    /// arguments, unless the path is returned fully qualified.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path of the item, e.g. `::core::fmt::Display`.
+    pub fn add(&mut self, path: Path) -> Path {
+        let last_segment = match path.segments.last() {
+            Some(last_segment)
+                if !self.fully_qualified
+                    && path.segments.len() > 1
+                    && !self.reserved.contains(&last_segment.ident.to_string()) =>
+            {
+                last_segment.clone()
+            }
+            _ => return path,
+        };
+
+        let mut import = path.clone();
+        import
+            .segments
+            .iter_mut()
+            .for_each(|segment| segment.arguments = PathArguments::None);
+
+        let import_existing = self.imports.iter().find(|import_existing| {
+            import_existing
+                .segments
+                .last()
+                .map(|segment| &segment.ident)
+                == Some(&last_segment.ident)
+        });
+        match import_existing {
+            Some(import_existing) if *import_existing != import => return path,
+            Some(_) => {}
+            None => self.imports.push(import),
+        }
+
+        Path::from(PathSegment {
+            ident: last_segment.ident,
+            arguments: last_segment.arguments,
+        })
+    }
+
+    /// Returns the `use` statements for the registered paths.
+    ///
+    /// The statements are in the order that the paths were first registered.
+    /// See [`Imports`] for avoiding conflicts with items in the scope that
+    /// they are emitted in.
+    pub fn use_statements(&self) -> TokenStream {
+        let imports = self.imports.iter();
+        quote!(#(use #imports;)*)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, Path};
+
+    use super::Imports;
+
+    #[test]
+    fn add_returns_single_segment_paths_unchanged() {
+        let mut imports = Imports::new();
+
+        let path = imports.add(parse_quote!(u32));
+
+        assert_eq!(quote!(u32).to_string(), quote!(#path).to_string());
+        assert!(imports.use_statements().is_empty());
+    }
+
+    #[test]
+    fn add_deduplicates_imports() {
+        let mut imports = Imports::new();
+
+        imports.add(parse_quote!(std::vec::Vec<u8>));
+        imports.add(parse_quote!(std::vec::Vec<u16>));
+
+        assert_eq!(
+            quote!(
+                use std::vec::Vec;
+            )
+            .to_string(),
+            imports.use_statements().to_string()
+        );
+    }
+
+    #[test]
+    fn add_strips_generic_args_from_every_segment_of_import() {
+        let mut imports = Imports::new();
+
+        let path = imports.add(parse_quote!(crate::Wrapper::<u8>::Inner<u16>));
+
+        let path_expected: Path = parse_quote!(Inner<u16>);
+        assert_eq!(path_expected, path);
+        assert_eq!(
+            quote!(
+                use crate::Wrapper::Inner;
+            )
+            .to_string(),
+            imports.use_statements().to_string()
+        );
+    }
+
+    #[test]
+    fn add_returns_reserved_names_fully_qualified() {
+        let mut imports = Imports::new();
+        imports.reserve(["Display"]);
+
+        let display = imports.add(parse_quote!(::core::fmt::Display));
+        let debug = imports.add(parse_quote!(::core::fmt::Debug));
+
+        let use_statements = imports.use_statements();
+
+        // A user's `struct Display;` in the same scope would conflict with
+        // `use ::core::fmt::Display;` (E0255), so it is not imported.
+        let tokens = quote! {
+            struct Display;
+            #use_statements
+            struct Types(#display, #debug);
+        };
+        let tokens_expected = quote! {
+            struct Display;
+            use ::core::fmt::Debug;
+            struct Types(::core::fmt::Display, Debug);
+        };
+        assert_eq!(tokens_expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn fully_qualified_returns_paths_unchanged_and_emits_no_imports() {
+        let mut imports = Imports::fully_qualified();
+
+        let path = imports.add(parse_quote!(::core::fmt::Display));
+
+        let path_expected: Path = parse_quote!(::core::fmt::Display);
+        assert_eq!(path_expected, path);
+        assert!(imports.use_statements().is_empty());
+    }
+}
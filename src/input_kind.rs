@@ -0,0 +1,40 @@
+/// The shape of a `DeriveInput`.
+///
+/// Returned by [`DeriveInputExt::input_kind`], so that macros can match on the
+/// shape instead of chaining `is_*` checks.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{DeriveInputExt, InputKind};
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote!(
+///     struct Meters(f64);
+/// );
+///
+/// let description = match ast.input_kind() {
+///     InputKind::Newtype => "newtype",
+///     InputKind::UnitStruct | InputKind::NamedStruct | InputKind::TupleStruct => "struct",
+///     InputKind::Enum | InputKind::Union => "other",
+/// };
+/// assert_eq!("newtype", description);
+/// ```
+///
+/// [`DeriveInputExt::input_kind`]: crate::DeriveInputExt::input_kind
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// A struct with no fields, e.g. `struct Unit;`.
+    UnitStruct,
+    /// A struct with named fields, e.g. `struct Named { a: u32 }`.
+    NamedStruct,
+    /// A struct with zero, or more than one unnamed fields, e.g.
+    /// `struct Tuple(u32, u32);`.
+    TupleStruct,
+    /// A struct with exactly one unnamed field, e.g. `struct Newtype(u32);`.
+    Newtype,
+    /// An enum.
+    Enum,
+    /// A union.
+    Union,
+}
@@ -0,0 +1,222 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
+use syn::{
+    Attribute, DeriveInput, Error, Ident, Item, ItemEnum, ItemFn, ItemImpl, ItemStruct, ItemTrait,
+    ItemUnion,
+};
+
+const ERR_UNSUPPORTED_ITEM: &str =
+    "This macro must be used on a struct, enum, union, function, impl block, or trait.";
+
+/// An item that an attribute macro is applied to.
+///
+/// Each variant holds the parsed item, so the matching extension traits may be
+/// used:
+///
+/// * `Struct`, `Enum`, `Union`: [`DeriveInputExt`] and related traits through
+///   [`ItemDispatch::to_derive_input`].
+/// * `Fn`: [`SignatureExt`] on the function's `sig`.
+/// * `Trait`: [`ItemTraitExt`].
+///
+/// [`DeriveInputExt`]: crate::DeriveInputExt
+/// [`SignatureExt`]: crate::SignatureExt
+/// [`ItemTraitExt`]: crate::ItemTraitExt
+#[derive(Clone, Debug, PartialEq)]
+pub enum ItemDispatch {
+    /// A struct item.
+    Struct(ItemStruct),
+    /// An enum item.
+    Enum(ItemEnum),
+    /// A union item.
+    Union(ItemUnion),
+    /// A free-standing function item.
+    Fn(ItemFn),
+    /// An impl block.
+    Impl(ItemImpl),
+    /// A trait definition.
+    Trait(ItemTrait),
+}
+
+impl ItemDispatch {
+    /// Returns the attributes on the item.
+    pub fn attrs(&self) -> &[Attribute] {
+        match self {
+            ItemDispatch::Struct(item) => &item.attrs,
+            ItemDispatch::Enum(item) => &item.attrs,
+            ItemDispatch::Union(item) => &item.attrs,
+            ItemDispatch::Fn(item) => &item.attrs,
+            ItemDispatch::Impl(item) => &item.attrs,
+            ItemDispatch::Trait(item) => &item.attrs,
+        }
+    }
+
+    /// Returns the name of the item.
+    ///
+    /// Returns `None` for impl blocks.
+    pub fn ident(&self) -> Option<&Ident> {
+        match self {
+            ItemDispatch::Struct(item) => Some(&item.ident),
+            ItemDispatch::Enum(item) => Some(&item.ident),
+            ItemDispatch::Union(item) => Some(&item.ident),
+            ItemDispatch::Fn(item) => Some(&item.sig.ident),
+            ItemDispatch::Impl(_) => None,
+            ItemDispatch::Trait(item) => Some(&item.ident),
+        }
+    }
+
+    /// Returns the item as a `DeriveInput`, if it is a struct, enum, or union.
+    pub fn to_derive_input(&self) -> Option<DeriveInput> {
+        match self {
+            ItemDispatch::Struct(item) => Some(DeriveInput::from(item.clone())),
+            ItemDispatch::Enum(item) => Some(DeriveInput::from(item.clone())),
+            ItemDispatch::Union(item) => Some(DeriveInput::from(item.clone())),
+            ItemDispatch::Fn(_) | ItemDispatch::Impl(_) | ItemDispatch::Trait(_) => None,
+        }
+    }
+}
+
+impl ToTokens for ItemDispatch {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            ItemDispatch::Struct(item) => item.to_tokens(tokens),
+            ItemDispatch::Enum(item) => item.to_tokens(tokens),
+            ItemDispatch::Union(item) => item.to_tokens(tokens),
+            ItemDispatch::Fn(item) => item.to_tokens(tokens),
+            ItemDispatch::Impl(item) => item.to_tokens(tokens),
+            ItemDispatch::Trait(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+impl TryFrom<Item> for ItemDispatch {
+    type Error = Error;
+
+    fn try_from(item: Item) -> Result<Self, Error> {
+        match item {
+            Item::Struct(item) => Ok(ItemDispatch::Struct(item)),
+            Item::Enum(item) => Ok(ItemDispatch::Enum(item)),
+            Item::Union(item) => Ok(ItemDispatch::Union(item)),
+            Item::Fn(item) => Ok(ItemDispatch::Fn(item)),
+            Item::Impl(item) => Ok(ItemDispatch::Impl(item)),
+            Item::Trait(item) => Ok(ItemDispatch::Trait(item)),
+            item => Err(Error::new_spanned(item, ERR_UNSUPPORTED_ITEM)),
+        }
+    }
+}
+
+/// Parses the tokens of an item that an attribute macro is applied to.
+///
+/// This accepts either `proc_macro::TokenStream` or `proc_macro2::TokenStream`.
+///
+/// # Parameters
+///
+/// * `item`: The tokens of the item the attribute is applied to.
+///
+/// # Errors
+///
+/// Returns an error if the tokens are not a struct, enum, union, function,
+/// impl block, or trait.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{parse_item_dispatch, ItemDispatch, ItemTraitExt};
+/// use quote::quote;
+///
+/// let item = quote! {
+///     trait Named {
+///         fn name(&self) -> String;
+///     }
+/// };
+///
+/// match parse_item_dispatch(item).unwrap() {
+///     ItemDispatch::Trait(item_trait) => assert_eq!(1, item_trait.method_signatures().len()),
+///     _ => panic!("Expected trait."),
+/// }
+/// ```
+pub fn parse_item_dispatch<TS>(item: TS) -> syn::Result<ItemDispatch>
+where
+    TS: Into<TokenStream2>,
+{
+    syn::parse2::<Item>(item.into()).and_then(ItemDispatch::try_from)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, DeriveInput, Ident};
+
+    use super::{parse_item_dispatch, ItemDispatch};
+    use crate::DeriveInputExt;
+
+    #[test]
+    fn parses_struct_as_derive_input() {
+        let item_dispatch = parse_item_dispatch(quote! {
+            #[derive(Debug)]
+            struct Struct;
+        })
+        .expect("Expected struct to be parsed.");
+
+        assert!(matches!(item_dispatch, ItemDispatch::Struct(_)));
+        let ident_expected: Ident = parse_quote!(Struct);
+        assert_eq!(Some(&ident_expected), item_dispatch.ident());
+        assert_eq!(1, item_dispatch.attrs().len());
+
+        let derive_input_expected: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            struct Struct;
+        };
+        let derive_input = item_dispatch
+            .to_derive_input()
+            .expect("Expected struct to convert to a `DeriveInput`.");
+        assert_eq!(derive_input_expected, derive_input);
+        assert!(derive_input.contains_tag(&parse_quote!(derive), &parse_quote!(Debug)));
+    }
+
+    #[test]
+    fn parses_fn_impl_and_trait() {
+        let item_dispatch = parse_item_dispatch(quote!(
+            fn f() {}
+        ))
+        .expect("Expected function to be parsed.");
+        assert!(matches!(item_dispatch, ItemDispatch::Fn(_)));
+        assert!(item_dispatch.to_derive_input().is_none());
+
+        let item_dispatch =
+            parse_item_dispatch(quote!(impl Struct {})).expect("Expected impl block to be parsed.");
+        assert!(matches!(item_dispatch, ItemDispatch::Impl(_)));
+        assert!(item_dispatch.ident().is_none());
+
+        let item_dispatch = parse_item_dispatch(quote!(
+            trait Trait {}
+        ))
+        .expect("Expected trait to be parsed.");
+        assert!(matches!(item_dispatch, ItemDispatch::Trait(_)));
+    }
+
+    #[test]
+    fn to_tokens_emits_item() {
+        let tokens = quote!(
+            enum Enum {
+                A,
+            }
+        );
+        let item_dispatch =
+            parse_item_dispatch(tokens.clone()).expect("Expected enum to be parsed.");
+
+        assert_eq!(tokens.to_string(), quote!(#item_dispatch).to_string());
+    }
+
+    #[test]
+    fn returns_error_for_unsupported_item() {
+        let error = parse_item_dispatch(quote!(
+            const A: u32 = 1;
+        ))
+        .expect_err("Expected const item to be rejected.");
+
+        assert_eq!(
+            "This macro must be used on a struct, enum, union, function, impl block, or trait.",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use syn::{ItemTrait, Signature, TraitItem, TraitItemConst, TraitItemType};
+
+/// Functions to make it ergonomic to inspect `ItemTrait`s.
+pub trait ItemTraitExt {
+    /// Returns the signatures of the trait's methods and associated functions.
+    ///
+    /// Signatures are returned in declaration order.
+    fn method_signatures(&self) -> Vec<&Signature>;
+
+    /// Returns the trait's associated types.
+    fn associated_types(&self) -> Vec<&TraitItemType>;
+
+    /// Returns the trait's associated consts.
+    fn associated_consts(&self) -> Vec<&TraitItemConst>;
+}
+
+impl ItemTraitExt for ItemTrait {
+    fn method_signatures(&self) -> Vec<&Signature> {
+        self.items
+            .iter()
+            .filter_map(|trait_item| {
+                if let TraitItem::Fn(trait_item_fn) = trait_item {
+                    Some(&trait_item_fn.sig)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<&Signature>>()
+    }
+
+    fn associated_types(&self) -> Vec<&TraitItemType> {
+        self.items
+            .iter()
+            .filter_map(|trait_item| {
+                if let TraitItem::Type(trait_item_type) = trait_item {
+                    Some(trait_item_type)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<&TraitItemType>>()
+    }
+
+    fn associated_consts(&self) -> Vec<&TraitItemConst> {
+        self.items
+            .iter()
+            .filter_map(|trait_item| {
+                if let TraitItem::Const(trait_item_const) = trait_item {
+                    Some(trait_item_const)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<&TraitItemConst>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, ItemTrait, Signature, TraitItemConst, TraitItemType};
+
+    use super::ItemTraitExt;
+
+    fn item_trait() -> ItemTrait {
+        parse_quote! {
+            trait Store {
+                type Key: Clone;
+                const CAPACITY: usize = 8;
+                fn new() -> Self;
+                type Value;
+                fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+                    None
+                }
+                const NAME: &'static str;
+            }
+        }
+    }
+
+    #[test]
+    fn method_signatures_returns_signatures_in_order() {
+        let item_trait = item_trait();
+
+        let signatures_expected: [Signature; 2] = [
+            parse_quote!(fn new() -> Self),
+            parse_quote!(fn get(&self, key: &Self::Key) -> Option<&Self::Value>),
+        ];
+        assert_eq!(
+            signatures_expected.iter().collect::<Vec<&Signature>>(),
+            item_trait.method_signatures()
+        );
+    }
+
+    #[test]
+    fn associated_types_returns_types_in_order() {
+        let item_trait = item_trait();
+
+        let types_expected: [TraitItemType; 2] = [
+            parse_quote!(
+                type Key: Clone;
+            ),
+            parse_quote!(
+                type Value;
+            ),
+        ];
+        assert_eq!(
+            types_expected.iter().collect::<Vec<&TraitItemType>>(),
+            item_trait.associated_types()
+        );
+    }
+
+    #[test]
+    fn associated_consts_returns_consts_in_order() {
+        let item_trait = item_trait();
+
+        let consts_expected: [TraitItemConst; 2] = [
+            parse_quote!(
+                const CAPACITY: usize = 8;
+            ),
+            parse_quote!(
+                const NAME: &'static str;
+            ),
+        ];
+        assert_eq!(
+            consts_expected.iter().collect::<Vec<&TraitItemConst>>(),
+            item_trait.associated_consts()
+        );
+    }
+
+    #[test]
+    fn accessors_return_empty_for_marker_trait() {
+        let item_trait: ItemTrait = parse_quote!(
+            trait Marker {}
+        );
+
+        assert!(item_trait.method_signatures().is_empty());
+        assert!(item_trait.associated_types().is_empty());
+        assert!(item_trait.associated_consts().is_empty());
+    }
+}
@@ -14,7 +14,7 @@
 //!
 //! use proc_macro::TokenStream;
 //! use proc_macro2::Span;
-//! use proc_macro_roids::{DeriveInputStructExt, FieldExt, IdentExt};
+//! use proc_macro_roids::prelude::*;
 //! use quote::quote;
 //! use syn::{parse_macro_input, parse_quote, DeriveInput, Ident};
 //!
@@ -358,36 +358,167 @@
 //!
 //! </details>
 //!
+//! # Features
+//!
+//! | Feature   | Description                                                             |
+//! | --------- | ----------------------------------------------------------------------- |
+//! | `items`   | Extensions for non-`DeriveInput` items. Enables `syn`'s `full` feature. |
+//! | `darling` | `darling_interop` conversions for `darling` inputs.                     |
+//!
+//! The `items` feature enables the following:
+//!
+//! * `DeriveInputNewtypeForwardExt`
+//! * `dispatch_methods` and `dispatch_trait`
+//! * `ItemDispatch` and `parse_item_dispatch`
+//! * `ItemTraitExt`
+//! * `RewriteReceiver` and `rewrite_receiver`
+//! * `SignatureExt` and `desugar_async_block`
+//! * `stub_impl` and `stub_impl_with`
+//! * `transform_file` and `transform_module`
+//! * `verify_parses`, which the macro shells run on their output when `verify`
+//!   is `true`
+//!
+//! Derive-only macros do not need `syn`'s `full` feature, so leaving `items`
+//! disabled keeps proc macro build times down.
+//!
 //! ---
 //!
 //! **Note:** The *roids* name is chosen because, although these functions make
 //! it easy to perform certain operations, they may not always be good ideas =D!
 
-#[cfg(test)]
-extern crate proc_macro;
-
 pub use crate::{
+    attr_editor::AttrEditor,
+    attr_validator::AttrValidator,
+    case::Case,
+    clone_strategy::CloneStrategy,
+    data_enum_ext::DataEnumExt,
+    derive_input_editor::DeriveInputEditor,
     derive_input_ext::DeriveInputExt,
     derive_input_newtype_ext::DeriveInputNewtypeExt,
     derive_input_struct_ext::DeriveInputStructExt,
+    derive_input_union_ext::DeriveInputUnionExt,
+    diagnostics::Diagnostics,
+    error_builder::ErrorBuilder,
+    field_bindings::FieldBindings,
+    field_context::FieldContext,
     field_ext::FieldExt,
+    field_ref::FieldRef,
+    fields_compatibility::{Compatibility, Mismatch},
     fields_ext::FieldsExt,
     fields_named_append::FieldsNamedAppend,
     fields_unnamed_append::FieldsUnnamedAppend,
+    gen_context::GenContext,
+    generic_args_ext::GenericArgsExt,
+    generics_ext::GenericsExt,
     ident_ext::IdentExt,
+    imports::Imports,
+    input_kind::InputKind,
+    macro_output::MacroOutput,
+    macro_shell::{attribute_macro, derive_macro, guard},
+    merge_strategy::MergeStrategy,
+    namespace_matcher::NamespaceMatcher,
+    rename_ident::RenameIdent,
+    replace_self::ReplaceSelf,
+    repr_ext::ReprExt,
+    spanned_meta::SpannedMeta,
+    strip_attrs::StripAttrs,
+    tag_spelling::TagSpelling,
+    type_ext::TypeExt,
     util::{
-        contains_namespace, contains_tag, format_path, namespace_nested_metas_iter,
-        namespace_parameter, namespace_parameters, tag_nested_metas_iter, tag_parameter,
-        tag_parameters,
+        allow_attrs, append_tag, apply_folds, assert_unique_namespace, assert_unique_parameters,
+        check_no_collision, contains_namespace, contains_namespace_spanned, contains_tag,
+        contains_tag_checked, contains_tag_spanned, contains_tag_with, duplicate_namespaces,
+        emit_or_fallback, emit_warning, expect_lit_int, expect_lit_str, expect_path,
+        fields_compatible, format_path, gensym, idents_in_input, namespace_meta_lists,
+        namespace_nested_metas_checked, namespace_nested_metas_iter, namespace_parameter,
+        namespace_parameter_checked, namespace_parameter_expr, namespace_parameter_spanned,
+        namespace_parameters, namespace_parameters_strict, namespace_parameters_with,
+        namespace_value, normalize_attrs, normalized_attrs_eq, parse_nested_metas, path_to_ident,
+        remove_tag, set_parameter, source_text, tag_nested_metas_checked, tag_nested_metas_iter,
+        tag_parameter, tag_parameter_checked, tag_parameter_expr, tag_parameter_lit,
+        tag_parameter_strict, tag_parameter_with, tag_parameters, tag_parameters_matching,
+        tag_parameters_spanned, tag_parameters_strict, tag_parameters_with, to_owned_type,
+        to_owned_type_with, wrap_in_module, wrap_with_allows, zip_fields,
     },
+    variant_ext::VariantExt,
+    variant_kind::VariantKind,
+    wrap_field_types::WrapFieldTypes,
 };
 
+#[cfg(feature = "items")]
+pub use crate::{
+    derive_input_newtype_forward_ext::DeriveInputNewtypeForwardExt,
+    enum_dispatch::{dispatch_methods, dispatch_trait},
+    item_dispatch::{parse_item_dispatch, ItemDispatch},
+    item_trait_ext::ItemTraitExt,
+    rewrite_receiver::RewriteReceiver,
+    signature_ext::{desugar_async_block, SignatureExt},
+    util::{
+        rewrite_receiver, stub_impl, stub_impl_with, transform_file, transform_module,
+        verify_parses,
+    },
+};
+
+#[doc(hidden)]
+pub use crate::macros::__private;
+
+pub mod conventions;
+#[cfg(feature = "darling")]
+pub mod darling_interop;
+pub mod prelude;
+pub mod testing;
+
+mod attr_editor;
+mod attr_validator;
+mod case;
+mod clone_strategy;
+mod data_enum_ext;
+mod derive_input_editor;
 mod derive_input_ext;
 mod derive_input_newtype_ext;
+#[cfg(feature = "items")]
+mod derive_input_newtype_forward_ext;
 mod derive_input_struct_ext;
+mod derive_input_union_ext;
+mod diagnostics;
+#[cfg(feature = "items")]
+mod enum_dispatch;
+mod error_builder;
+mod field_bindings;
+mod field_context;
 mod field_ext;
+mod field_ref;
+mod fields_compatibility;
 mod fields_ext;
 mod fields_named_append;
 mod fields_unnamed_append;
+mod gen_context;
+mod generic_args_ext;
+mod generics_ext;
 mod ident_ext;
+mod imports;
+mod input_kind;
+#[cfg(feature = "items")]
+mod item_dispatch;
+#[cfg(feature = "items")]
+mod item_trait_ext;
+mod macro_output;
+mod macro_shell;
+mod macros;
+mod merge_strategy;
+mod namespace_matcher;
+mod rename_ident;
+mod replace_self;
+mod repr_ext;
+#[cfg(feature = "items")]
+mod rewrite_receiver;
+#[cfg(feature = "items")]
+mod signature_ext;
+mod spanned_meta;
+mod strip_attrs;
+mod tag_spelling;
+mod type_ext;
 mod util;
+mod variant_ext;
+mod variant_kind;
+mod wrap_field_types;
@@ -0,0 +1,238 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::Error;
+
+use crate::Diagnostics;
+
+/// Collects the tokens emitted by an attribute macro.
+///
+/// The tokens are emitted in the following order:
+///
+/// 1. The (possibly modified) original item.
+/// 2. Generated impl blocks, in the order they were added.
+/// 3. `compile_error!` invocations for each error.
+///
+/// Errors do not prevent the item and impl blocks from being emitted. This
+/// allows a macro to emit best-effort generated code alongside the errors, so
+/// that uses of the generated items do not produce further errors, and IDEs
+/// can still offer completions for them.
+///
+/// Use [`ToTokens::into_token_stream`] to retrieve the output tokens.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::MacroOutput;
+/// use quote::{quote, ToTokens};
+/// use syn::{parse_quote, DeriveInput, Error};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct;
+/// };
+///
+/// let mut macro_output = MacroOutput::new();
+/// macro_output
+///     .item(&ast)
+///     .impl_block(quote!(impl Struct {}))
+///     .error(Error::new(Span::call_site(), "Something went wrong."));
+///
+/// let expected = quote! {
+///     struct Struct;
+///     impl Struct {}
+///     ::core::compile_error! { "Something went wrong." }
+/// };
+/// assert_eq!(expected.to_string(), macro_output.into_token_stream().to_string());
+/// ```
+#[derive(Debug, Default)]
+pub struct MacroOutput {
+    /// Tokens of the original item.
+    item: TokenStream,
+    /// Generated impl blocks.
+    impl_blocks: Vec<TokenStream>,
+    /// Errors to emit as `compile_error!`s.
+    errors: Vec<Error>,
+}
+
+impl MacroOutput {
+    /// Returns a new, empty `MacroOutput`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the item to emit, replacing any previously set item.
+    pub fn item<T>(&mut self, item: T) -> &mut Self
+    where
+        T: ToTokens,
+    {
+        self.item = item.into_token_stream();
+        self
+    }
+
+    /// Adds a generated impl block to emit after the item.
+    pub fn impl_block<T>(&mut self, impl_block: T) -> &mut Self
+    where
+        T: ToTokens,
+    {
+        self.impl_blocks.push(impl_block.into_token_stream());
+        self
+    }
+
+    /// Adds an error to emit as a `compile_error!`.
+    pub fn error(&mut self, error: Error) -> &mut Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Adds a generated impl block, or an error if generating it failed.
+    ///
+    /// # Parameters
+    ///
+    /// * `result`: The result of generating the impl block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro2::Span;
+    /// use proc_macro_roids::MacroOutput;
+    /// use quote::{quote, ToTokens};
+    /// use syn::Error;
+    ///
+    /// let mut macro_output = MacroOutput::new();
+    /// macro_output
+    ///     .impl_block_result(Ok(quote!(impl Struct {})))
+    ///     .impl_block_result(Err::<proc_macro2::TokenStream, _>(Error::new(
+    ///         Span::call_site(),
+    ///         "Unsupported field type.",
+    ///     )));
+    ///
+    /// let expected = quote! {
+    ///     impl Struct {}
+    ///     ::core::compile_error! { "Unsupported field type." }
+    /// };
+    /// assert_eq!(expected.to_string(), macro_output.into_token_stream().to_string());
+    /// ```
+    pub fn impl_block_result<T>(&mut self, result: syn::Result<T>) -> &mut Self
+    where
+        T: ToTokens,
+    {
+        match result {
+            Ok(impl_block) => self.impl_block(impl_block),
+            Err(error) => self.error(error),
+        }
+    }
+
+    /// Adds the errors accumulated in the given `Diagnostics`.
+    ///
+    /// Combined errors are emitted as separate `compile_error!`s.
+    pub fn diagnostics(&mut self, diagnostics: Diagnostics) -> &mut Self {
+        self.errors.extend(diagnostics);
+        self
+    }
+
+    /// Returns whether any errors have been added.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+impl ToTokens for MacroOutput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let item = &self.item;
+        let impl_blocks = &self.impl_blocks;
+        let compile_errors = self.errors.iter().map(Error::to_compile_error);
+
+        tokens.extend(quote! {
+            #item
+            #(#impl_blocks)*
+            #(#compile_errors)*
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{Span, TokenStream};
+    use quote::{quote, ToTokens};
+    use syn::Error;
+
+    use super::MacroOutput;
+    use crate::Diagnostics;
+
+    #[test]
+    fn into_token_stream_is_empty_when_nothing_added() {
+        assert!(MacroOutput::new().into_token_stream().is_empty());
+    }
+
+    #[test]
+    fn into_token_stream_emits_impl_blocks_in_order_after_item() {
+        let mut macro_output = MacroOutput::new();
+        macro_output
+            .impl_block(quote!(impl A for Struct {}))
+            .item(quote!(
+                struct Struct;
+            ))
+            .impl_block(quote!(impl B for Struct {}));
+
+        let expected = quote! {
+            struct Struct;
+            impl A for Struct {}
+            impl B for Struct {}
+        };
+        assert!(!macro_output.has_errors());
+        assert_eq!(
+            expected.to_string(),
+            macro_output.into_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn into_token_stream_emits_each_error() {
+        let mut macro_output = MacroOutput::new();
+        macro_output
+            .error(Error::new(Span::call_site(), "one"))
+            .error(Error::new(Span::call_site(), "two"));
+
+        let expected = quote! {
+            ::core::compile_error! { "one" }
+            ::core::compile_error! { "two" }
+        };
+        assert!(macro_output.has_errors());
+        assert_eq!(
+            expected.to_string(),
+            macro_output.into_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn into_token_stream_emits_partial_expansion_with_errors() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Error::new(Span::call_site(), "first"));
+        diagnostics.push(Error::new(Span::call_site(), "second"));
+
+        let mut macro_output = MacroOutput::new();
+        macro_output
+            .item(quote!(
+                struct Struct;
+            ))
+            .impl_block_result(Ok(quote!(impl A for Struct {})))
+            .impl_block_result(Err::<TokenStream, _>(Error::new(
+                Span::call_site(),
+                "failed",
+            )))
+            .diagnostics(diagnostics);
+
+        let expected = quote! {
+            struct Struct;
+            impl A for Struct {}
+            ::core::compile_error! { "failed" }
+            ::core::compile_error! { "first" }
+            ::core::compile_error! { "second" }
+        };
+        assert!(macro_output.has_errors());
+        assert_eq!(
+            expected.to_string(),
+            macro_output.into_token_stream().to_string()
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse::Parser, punctuated::Punctuated, DeriveInput, Error, Meta, Token};
+
+/// Runs the body of an attribute macro, handling parsing and errors.
+///
+/// This accepts and returns either `proc_macro::TokenStream` or
+/// `proc_macro2::TokenStream`, so macro bodies may be unit tested using
+/// `proc_macro2`.
+///
+/// * The attribute arguments are parsed as comma separated `Meta`s.
+/// * The item is parsed as a `DeriveInput`.
+/// * If parsing fails or `f` returns an error, the error is emitted as a
+///   `compile_error!` alongside the original item, so that uses of the item do
+///   not produce further errors.
+/// * Otherwise, the tokens returned by `f` are emitted. These should include
+///   the item if it is to be kept.
+/// * If `verify` is `true`, the tokens returned by `f` are checked to parse,
+///   and a `compile_error!` is emitted if they do not.
+///
+/// # Parameters
+///
+/// * `args`: The attribute arguments, i.e. the tokens in `#[attr(..)]`.
+/// * `item`: The tokens of the item the attribute is applied to.
+/// * `verify`: Whether to check that the generated tokens parse, see
+///   `verify_parses`. This requires the `items` feature, and has no effect
+///   without it.
+/// * `f`: The body of the attribute macro.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// extern crate proc_macro;
+///
+/// use proc_macro::TokenStream;
+/// use proc_macro_roids::DeriveInputExt;
+/// use quote::quote;
+///
+/// // #[proc_macro_attribute]
+/// pub fn copy(args: TokenStream, item: TokenStream) -> TokenStream {
+///     proc_macro_roids::attribute_macro(args, item, false, |_args_metas, mut ast| {
+///         ast.append_derives(syn::parse_quote!(Clone, Copy));
+///         Ok(quote!(#ast))
+///     })
+/// }
+/// ```
+pub fn attribute_macro<TS, F>(args: TS, item: TS, verify: bool, f: F) -> TS
+where
+    TS: Into<TokenStream2> + From<TokenStream2>,
+    F: FnOnce(Punctuated<Meta, Token![,]>, DeriveInput) -> syn::Result<TokenStream2>,
+{
+    let item = item.into();
+    let result = Punctuated::<Meta, Token![,]>::parse_terminated
+        .parse2(args.into())
+        .and_then(|args_metas| {
+            let derive_input = syn::parse2::<DeriveInput>(item.clone())?;
+            f(args_metas, derive_input)
+        })
+        .and_then(|token_stream| verified(token_stream, verify));
+
+    let token_stream = match result {
+        Ok(token_stream) => token_stream,
+        Err(error) => {
+            let compile_error = error.to_compile_error();
+            quote! {
+                #item
+                #compile_error
+            }
+        }
+    };
+
+    TS::from(token_stream)
+}
+
+/// Runs the body of a derive macro, handling parsing and errors.
+///
+/// This accepts and returns either `proc_macro::TokenStream` or
+/// `proc_macro2::TokenStream`, so macro bodies may be unit tested using
+/// `proc_macro2`.
+///
+/// * The input is parsed as a `DeriveInput`.
+/// * If parsing fails or `f` returns an error, the error is emitted as a
+///   `compile_error!` at the error's span.
+/// * Otherwise, the tokens returned by `f` are emitted.
+/// * If `verify` is `true`, the tokens returned by `f` are checked to parse,
+///   and a `compile_error!` is emitted if they do not.
+///
+/// # Parameters
+///
+/// * `input`: The tokens of the item the derive is applied to.
+/// * `verify`: Whether to check that the generated tokens parse, see
+///   `verify_parses`. This requires the `items` feature, and has no effect
+///   without it.
+/// * `f`: The body of the derive macro.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// extern crate proc_macro;
+///
+/// use proc_macro::TokenStream;
+/// use proc_macro_roids::DeriveInputNewtypeExt;
+/// use quote::quote;
+/// use syn::Error;
+///
+/// // #[proc_macro_derive(Deref)]
+/// pub fn derive_deref(input: TokenStream) -> TokenStream {
+///     proc_macro_roids::derive_macro(input, cfg!(debug_assertions), |ast| {
+///         if !ast.is_newtype() {
+///             return Err(Error::new_spanned(&ast.ident, "Expected a newtype."));
+///         }
+///
+///         let type_name = &ast.ident;
+///         let inner_type = &ast.inner_type().ty;
+///         Ok(quote! {
+///             impl std::ops::Deref for #type_name {
+///                 type Target = #inner_type;
+///                 fn deref(&self) -> &Self::Target {
+///                     &self.0
+///                 }
+///             }
+///         })
+///     })
+/// }
+/// ```
+pub fn derive_macro<TS, F>(input: TS, verify: bool, f: F) -> TS
+where
+    TS: Into<TokenStream2> + From<TokenStream2>,
+    F: FnOnce(DeriveInput) -> syn::Result<TokenStream2>,
+{
+    let token_stream = syn::parse2::<DeriveInput>(input.into())
+        .and_then(f)
+        .and_then(|token_stream| verified(token_stream, verify))
+        .unwrap_or_else(|error| error.to_compile_error());
+
+    TS::from(token_stream)
+}
+
+/// Runs a macro body, converting panics into a `compile_error!`.
+///
+/// The panicking functions in this crate, such as
+/// [`DeriveInputStructExt::fields`] and the `assert_*` methods, are ergonomic
+/// to use, but a panic in a proc macro is reported as "proc macro panicked"
+/// without a precise location. This catches the panic, and emits its message
+/// as a `compile_error!` at the given span.
+///
+/// The panic message is still printed to stderr by the panic hook.
+///
+/// # Parameters
+///
+/// * `span`: Span to report a panic at.
+/// * `f`: The body of the macro.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::{guard, DeriveInputStructExt};
+/// use quote::quote;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote!(
+///     struct Tuple(u32);
+/// );
+///
+/// let tokens = guard(ast.ident.span(), || {
+///     let fields_named = ast.fields_named();
+///     quote!(#fields_named)
+/// });
+///
+/// let tokens_expected = quote! {
+///     ::core::compile_error! { "This macro must be used on a struct with named fields." }
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+///
+/// [`DeriveInputStructExt::fields`]: crate::DeriveInputStructExt::fields
+pub fn guard<F>(span: Span, f: F) -> TokenStream2
+where
+    F: FnOnce() -> TokenStream2,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.as_str()
+        } else {
+            "proc macro panicked"
+        };
+
+        Error::new(span, message).to_compile_error()
+    })
+}
+
+/// Returns the generated tokens, verifying that they parse if `verify` is
+/// `true`.
+///
+/// See [`verify_parses`](crate::verify_parses).
+fn verified(token_stream: TokenStream2, verify: bool) -> syn::Result<TokenStream2> {
+    #[cfg(feature = "items")]
+    if verify {
+        crate::util::verify_parses(&token_stream)?;
+    }
+    #[cfg(not(feature = "items"))]
+    let _ = verify;
+
+    Ok(token_stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, Meta};
+
+    use super::{attribute_macro, derive_macro, guard};
+
+    #[test]
+    fn emits_tokens_from_body() {
+        let tokens = attribute_macro(
+            quote!(tag),
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |args_metas, ast| {
+                let meta_expected: Meta = parse_quote!(tag);
+                assert_eq!(
+                    vec![meta_expected],
+                    args_metas.into_iter().collect::<Vec<_>>()
+                );
+
+                let ident = &ast.ident;
+                Ok(quote! {
+                    #ast
+                    impl #ident {}
+                })
+            },
+        );
+
+        let expected = quote! {
+            struct Struct;
+            impl Struct {}
+        };
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn emits_item_and_compile_error_when_body_fails() {
+        let tokens = attribute_macro(
+            quote!(),
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |_, _| Err(Error::new(Span::call_site(), "Body failed.")),
+        );
+
+        let expected = quote! {
+            struct Struct;
+            ::core::compile_error! { "Body failed." }
+        };
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn emits_item_and_compile_error_when_item_fails_to_parse() {
+        let tokens = attribute_macro(
+            quote!(),
+            quote!(
+                fn function() {}
+            ),
+            false,
+            |_, _| {
+                unreachable!("Body should not be called when item fails to parse.")
+                // kcov-ignore
+            },
+        );
+
+        let tokens = tokens.to_string();
+        assert!(tokens.starts_with(
+            &quote!(
+                fn function() {}
+            )
+            .to_string()
+        ));
+        assert!(tokens.contains("compile_error"));
+    }
+
+    #[test]
+    fn emits_item_and_compile_error_when_args_fail_to_parse() {
+        let tokens = attribute_macro(
+            quote!(a = ),
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |_, _| {
+                unreachable!("Body should not be called when args fail to parse.")
+                // kcov-ignore
+            },
+        );
+
+        let tokens = tokens.to_string();
+        assert!(tokens.starts_with(
+            &quote!(
+                struct Struct;
+            )
+            .to_string()
+        ));
+        assert!(tokens.contains("compile_error"));
+    }
+
+    #[test]
+    fn guard_returns_tokens_when_body_does_not_panic() {
+        let tokens = guard(Span::call_site(), || quote!(impl Struct {}));
+
+        assert_eq!(quote!(impl Struct {}).to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn guard_emits_compile_error_with_formatted_panic_message() {
+        let tokens = guard(Span::call_site(), || panic!("Field `{}` is invalid.", "a"));
+
+        let expected = quote!(::core::compile_error! { "Field `a` is invalid." });
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn guard_emits_compile_error_when_panic_payload_is_not_a_string() {
+        let tokens = guard(Span::call_site(), || std::panic::panic_any(1u32));
+
+        let expected = quote!(::core::compile_error! { "proc macro panicked" });
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn derive_emits_tokens_from_body() {
+        let tokens = derive_macro(
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |ast| {
+                let ident = &ast.ident;
+                Ok(quote!(impl #ident {}))
+            },
+        );
+
+        assert_eq!(quote!(impl Struct {}).to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn derive_emits_compile_error_when_body_fails() {
+        let tokens = derive_macro(
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |_| Err(Error::new(Span::call_site(), "Body failed.")),
+        );
+
+        let expected = quote! {
+            ::core::compile_error! { "Body failed." }
+        };
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[cfg(feature = "items")]
+    #[test]
+    fn derive_emits_compile_error_when_verified_output_fails_to_parse() {
+        let tokens = derive_macro(
+            quote!(
+                struct Struct;
+            ),
+            true,
+            |ast| {
+                let ident = &ast.ident;
+                Ok(quote!(impl #ident))
+            },
+        );
+
+        let tokens = tokens.to_string();
+        assert!(tokens.starts_with(":: core :: compile_error !"));
+        assert!(tokens.contains("Generated tokens failed to parse"));
+    }
+
+    #[test]
+    fn derive_emits_unverified_output_that_fails_to_parse() {
+        let tokens = derive_macro(
+            quote!(
+                struct Struct;
+            ),
+            false,
+            |ast| {
+                let ident = &ast.ident;
+                Ok(quote!(impl #ident))
+            },
+        );
+
+        assert_eq!(quote!(impl Struct).to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn derive_emits_compile_error_when_input_fails_to_parse() {
+        let tokens = derive_macro(
+            quote!(
+                fn function() {}
+            ),
+            false,
+            |_| {
+                unreachable!("Body should not be called when input fails to parse.")
+                // kcov-ignore
+            },
+        );
+
+        assert!(tokens.to_string().starts_with(":: core :: compile_error !"));
+    }
+}
@@ -0,0 +1,156 @@
+/// Returns a `syn::Error` spanned at the given tokens, with a formatted
+/// message.
+///
+/// The first argument is anything that implements [`ToTokens`], such as a
+/// `Field`, `Ident`, or `Attribute`. The remaining arguments are passed to
+/// [`format!`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::err_span;
+/// use syn::{parse_quote, Ident};
+///
+/// let ident: Ident = parse_quote!(field);
+/// let error = err_span!(ident, "Unknown field `{}`.", ident);
+///
+/// assert_eq!("Unknown field `field`.", error.to_string());
+/// ```
+///
+/// [`ToTokens`]: quote::ToTokens
+#[macro_export]
+macro_rules! err_span {
+    ($tokens:expr, $($fmt:tt)+) => {
+        $crate::__private::Error::new_spanned(&$tokens, ::std::format!($($fmt)+))
+    };
+}
+
+/// Returns early with a `syn::Error` spanned at the given tokens.
+///
+/// This expands to `return Err(err_span!(..).into())`, so it can be used in
+/// functions that return `syn::Result<T>`, or a `Result` whose error type
+/// implements `From<syn::Error>`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::bail;
+/// use syn::{parse_quote, Field, Fields, FieldsNamed};
+///
+/// fn first_field(fields: &Fields) -> syn::Result<&Field> {
+///     match fields.iter().next() {
+///         Some(field) => Ok(field),
+///         None => bail!(fields, "Expected at least one field."),
+///     }
+/// }
+///
+/// let fields_named: FieldsNamed = parse_quote!({});
+/// let error = first_field(&Fields::Named(fields_named)).unwrap_err();
+///
+/// assert_eq!("Expected at least one field.", error.to_string());
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($tokens:expr, $($fmt:tt)+) => {
+        return ::core::result::Result::Err(
+            ::core::convert::From::from($crate::err_span!($tokens, $($fmt)+)),
+        )
+    };
+}
+
+/// Returns early with `compile_error!` tokens spanned at the given tokens.
+///
+/// This expands to `return err_span!(..).to_compile_error().into()`, so it can
+/// be used in functions that return a `proc_macro::TokenStream` or a
+/// `proc_macro2::TokenStream`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::TokenStream;
+/// use proc_macro_roids::{bail_tokens, DeriveInputStructExt};
+/// use quote::quote;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// fn derive(ast: &DeriveInput) -> TokenStream {
+///     if !ast.is_named() {
+///         bail_tokens!(ast.ident, "`{}` must have named fields.", ast.ident);
+///     }
+///
+///     quote!()
+/// }
+///
+/// let ast: DeriveInput = parse_quote!(
+///     struct Unit;
+/// );
+///
+/// let expected = quote!(::core::compile_error! { "`Unit` must have named fields." });
+/// assert_eq!(expected.to_string(), derive(&ast).to_string());
+/// ```
+#[macro_export]
+macro_rules! bail_tokens {
+    ($tokens:expr, $($fmt:tt)+) => {
+        return ::core::convert::Into::into($crate::err_span!($tokens, $($fmt)+).to_compile_error())
+    };
+}
+
+/// Items used by the exported macros.
+pub mod __private {
+    pub use syn::Error;
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{Span, TokenStream};
+    use quote::quote;
+    use syn::{Error, Ident};
+
+    #[test]
+    fn err_span_formats_message() {
+        let ident = Ident::new("field", Span::call_site());
+
+        let error = err_span!(
+            ident,
+            "Unknown field `{ident}`, expected one of {:?}.",
+            ["a"]
+        );
+
+        assert_eq!(
+            "Unknown field `field`, expected one of [\"a\"].",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn bail_returns_error_converted_into_return_type() {
+        #[derive(Debug)]
+        struct WrappedError(Error);
+
+        impl From<Error> for WrappedError {
+            fn from(error: Error) -> Self {
+                Self(error)
+            }
+        }
+
+        fn check(ident: &Ident) -> Result<(), WrappedError> {
+            bail!(ident, "`{}` is not allowed.", ident);
+        }
+
+        let ident = Ident::new("field", Span::call_site());
+        let WrappedError(error) = check(&ident).unwrap_err();
+
+        assert_eq!("`field` is not allowed.", error.to_string());
+    }
+
+    #[test]
+    fn bail_tokens_returns_compile_error() {
+        fn generate(ident: &Ident) -> TokenStream {
+            bail_tokens!(ident, "`{}` is not allowed.", ident);
+        }
+
+        let ident = Ident::new("field", Span::call_site());
+
+        let expected = quote!(::core::compile_error! { "`field` is not allowed." });
+        assert_eq!(expected.to_string(), generate(&ident).to_string());
+    }
+}
@@ -0,0 +1,22 @@
+/// How parameters repeated across `#[namespace(..)]` attributes are combined.
+///
+/// Parameters are compared by their path, so `rename = "a"` and `rename = "b"`
+/// are the same parameter.
+///
+/// Used by [`namespace_parameters_with`].
+///
+/// [`namespace_parameters_with`]: crate::namespace_parameters_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep every parameter, in order. This is what [`namespace_parameters`]
+    /// does.
+    ///
+    /// [`namespace_parameters`]: crate::namespace_parameters
+    Merge,
+    /// Keep the first occurrence of each parameter.
+    FirstWins,
+    /// Keep the last occurrence of each parameter.
+    LastWins,
+    /// Return an error if a parameter occurs more than once.
+    ErrorOnDuplicateKey,
+}
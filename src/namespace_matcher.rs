@@ -0,0 +1,185 @@
+use syn::{Attribute, Meta, Path, PathSegment};
+
+use crate::util;
+
+/// Matches attribute namespaces, tolerating different spellings of the same
+/// path.
+///
+/// Path equality treats `::my::derive` and `my::derive` as different paths.
+/// This matcher ignores leading colons, and may also ignore a prefix such as
+/// the crate name, so that each of the following match the `my::derive`
+/// namespace with the `my_crate` prefix:
+///
+/// * `#[my::derive(..)]`
+/// * `#[::my::derive(..)]`
+/// * `#[my_crate::my::derive(..)]`
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::NamespaceMatcher;
+/// use syn::{parse_quote, DeriveInput, Meta};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[::my::derive(a)]
+///     #[my_crate::my::derive(b)]
+///     pub struct MyStruct;
+/// };
+///
+/// let namespace_matcher =
+///     NamespaceMatcher::new(parse_quote!(my::derive)).prefix(parse_quote!(my_crate));
+///
+/// assert!(namespace_matcher.contains_tag(&ast.attrs, &parse_quote!(a)));
+///
+/// let nested_metas = namespace_matcher
+///     .nested_metas_iter(&ast.attrs)
+///     .collect::<Vec<Meta>>();
+/// let meta_a: Meta = parse_quote!(a);
+/// let meta_b: Meta = parse_quote!(b);
+/// assert_eq!(vec![meta_a, meta_b], nested_metas);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NamespaceMatcher {
+    /// The namespace to match.
+    namespace: Path,
+    /// Prefixes that may precede the namespace.
+    prefixes: Vec<Path>,
+}
+
+impl NamespaceMatcher {
+    /// Returns a new `NamespaceMatcher` for the given namespace.
+    pub fn new(namespace: Path) -> Self {
+        Self {
+            namespace,
+            prefixes: Vec::new(),
+        }
+    }
+
+    /// Also matches the namespace when preceded by `prefix`.
+    ///
+    /// This may be called multiple times, e.g. for the crate name and its
+    /// commonly used rename.
+    pub fn prefix(mut self, prefix: Path) -> Self {
+        self.prefixes.push(prefix);
+        self
+    }
+
+    /// Returns whether `path` matches the namespace.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The path to compare, e.g. an attribute's `path()`.
+    pub fn matches(&self, path: &Path) -> bool {
+        let segments = path.segments.iter().collect::<Vec<&PathSegment>>();
+        let namespace_len = self.namespace.segments.len();
+
+        if segments.len() < namespace_len {
+            return false;
+        }
+
+        let (segments_prefix, segments_namespace) =
+            segments.split_at(segments.len() - namespace_len);
+        let namespace_matches = segments_namespace
+            .iter()
+            .copied()
+            .eq(self.namespace.segments.iter());
+
+        namespace_matches
+            && (segments_prefix.is_empty()
+                || self
+                    .prefixes
+                    .iter()
+                    .any(|prefix| segments_prefix.iter().copied().eq(prefix.segments.iter())))
+    }
+
+    /// Returns whether the attributes contain the namespace.
+    ///
+    /// # Parameters
+    ///
+    /// * `attrs`: The attributes on the item.
+    pub fn contains_namespace(&self, attrs: &[Attribute]) -> bool {
+        attrs.iter().any(|attr| self.matches(attr.path()))
+    }
+
+    /// Returns whether the attributes contain `#[namespace(tag)]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `attrs`: The attributes on the item.
+    /// * `tag`: The `path()` of the second-level attribute.
+    pub fn contains_tag(&self, attrs: &[Attribute], tag: &Path) -> bool {
+        self.nested_metas_iter(attrs)
+            .any(|tag_meta| tag_meta.path() == tag)
+    }
+
+    /// Returns the nested metas from each matching `#[namespace(..)]`
+    /// attribute.
+    ///
+    /// Attributes whose arguments cannot be parsed are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `attrs`: The attributes on the item.
+    pub fn nested_metas_iter<'f>(
+        &'f self,
+        attrs: &'f [Attribute],
+    ) -> impl Iterator<Item = Meta> + 'f {
+        attrs
+            .iter()
+            .filter(move |attr| self.matches(attr.path()))
+            .filter_map(|attr| util::parse_nested_metas(attr).ok())
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Path};
+
+    use super::NamespaceMatcher;
+
+    #[test]
+    fn matches_ignores_leading_colon() {
+        let namespace_matcher = NamespaceMatcher::new(parse_quote!(my::derive));
+
+        assert!(namespace_matcher.matches(&parse_quote!(my::derive)));
+        assert!(namespace_matcher.matches(&parse_quote!(::my::derive)));
+    }
+
+    #[test]
+    fn matches_returns_false_for_different_path() {
+        let namespace_matcher = NamespaceMatcher::new(parse_quote!(my::derive));
+
+        assert!(!namespace_matcher.matches(&parse_quote!(derive)));
+        assert!(!namespace_matcher.matches(&parse_quote!(other::derive)));
+        assert!(!namespace_matcher.matches(&parse_quote!(my::derive::tag)));
+    }
+
+    #[test]
+    fn matches_prefixed_path_when_prefix_is_registered() {
+        let namespace_matcher = NamespaceMatcher::new(parse_quote!(derive))
+            .prefix(parse_quote!(my))
+            .prefix(parse_quote!(my_renamed));
+
+        let paths: [Path; 4] = [
+            parse_quote!(derive),
+            parse_quote!(my::derive),
+            parse_quote!(::my_renamed::derive),
+            parse_quote!(other::derive),
+        ];
+        let matches = paths
+            .iter()
+            .map(|path| namespace_matcher.matches(path))
+            .collect::<Vec<bool>>();
+
+        assert_eq!(vec![true, true, true, false], matches);
+    }
+
+    #[test]
+    fn contains_namespace_returns_false_when_absent() {
+        let namespace_matcher = NamespaceMatcher::new(parse_quote!(my::derive));
+
+        assert!(!namespace_matcher.contains_namespace(&[parse_quote!(#[other])]));
+        assert!(namespace_matcher.contains_namespace(&[parse_quote!(#[::my::derive])]));
+    }
+}
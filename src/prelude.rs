@@ -0,0 +1,23 @@
+//! Re-exports the extension traits, so they can be imported together.
+//!
+//! ```rust,edition2021
+//! use proc_macro_roids::prelude::*;
+//! use syn::{parse_quote, DeriveInput};
+//!
+//! let ast: DeriveInput = parse_quote! {
+//!     struct Struct {
+//!         field: u32,
+//!     }
+//! };
+//!
+//! assert_eq!(1, ast.fields().len());
+//! ```
+
+pub use crate::{
+    DataEnumExt, DeriveInputExt, DeriveInputNewtypeExt, DeriveInputStructExt, DeriveInputUnionExt,
+    FieldExt, FieldsExt, FieldsNamedAppend, FieldsUnnamedAppend, GenericArgsExt, GenericsExt,
+    IdentExt, ReprExt, TypeExt, VariantExt,
+};
+
+#[cfg(feature = "items")]
+pub use crate::{DeriveInputNewtypeForwardExt, ItemTraitExt, SignatureExt};
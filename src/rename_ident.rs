@@ -0,0 +1,95 @@
+use syn::{fold::Fold, Ident};
+
+/// Renames every occurrence of an `Ident`.
+///
+/// The renamed `Ident` keeps the span of the original occurrence.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::RenameIdent;
+/// use syn::{fold::Fold, parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct { inner: Inner }
+/// };
+///
+/// let mut rename_ident = RenameIdent::new(parse_quote!(Inner), parse_quote!(Renamed));
+/// let ast = rename_ident.fold_derive_input(ast);
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     struct Struct { inner: Renamed }
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RenameIdent {
+    /// The `Ident` to rename.
+    from: Ident,
+    /// The `Ident` to rename to.
+    to: Ident,
+}
+
+impl RenameIdent {
+    /// Returns a new `RenameIdent` fold.
+    ///
+    /// # Parameters
+    ///
+    /// * `from`: The `Ident` to rename.
+    /// * `to`: The `Ident` to rename to.
+    pub fn new(from: Ident, to: Ident) -> Self {
+        Self { from, to }
+    }
+}
+
+impl Fold for RenameIdent {
+    fn fold_ident(&mut self, ident: Ident) -> Ident {
+        if ident == self.from {
+            let mut ident_renamed = self.to.clone();
+            ident_renamed.set_span(ident.span());
+            ident_renamed
+        } else {
+            ident
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{fold::Fold, parse_quote, DeriveInput};
+
+    use super::RenameIdent;
+
+    #[test]
+    fn renames_type_ident_and_field_type() {
+        let ast: DeriveInput = parse_quote! {
+            enum Node {
+                Leaf,
+                Branch(Box<Node>, Box<Node>),
+            }
+        };
+
+        let mut rename_ident = RenameIdent::new(parse_quote!(Node), parse_quote!(Tree));
+        let ast = rename_ident.fold_derive_input(ast);
+
+        let ast_expected: DeriveInput = parse_quote! {
+            enum Tree {
+                Leaf,
+                Branch(Box<Tree>, Box<Tree>),
+            }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn leaves_other_idents_unchanged() {
+        let ast: DeriveInput = parse_quote! {
+            struct Struct { node_count: u32 }
+        };
+
+        let mut rename_ident = RenameIdent::new(parse_quote!(node), parse_quote!(tree));
+        let ast_folded = rename_ident.fold_derive_input(ast.clone());
+
+        assert_eq!(ast, ast_folded);
+    }
+}
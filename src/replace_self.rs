@@ -0,0 +1,121 @@
+use syn::{
+    fold::{self, Fold},
+    parse_quote,
+    punctuated::Punctuated,
+    Token, Type, TypePath,
+};
+
+/// Replaces `Self` in types with a concrete type.
+///
+/// * `Self` is replaced with the type.
+/// * `Self::Assoc` is replaced with `<Type>::Assoc`.
+///
+/// This is useful when moving field types out of a type definition, such as
+/// when generating a separate struct from an existing one.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::ReplaceSelf;
+/// use syn::{fold::Fold, parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Node { children: Vec<Box<Self>>, id: Self::Id }
+/// };
+///
+/// let mut replace_self = ReplaceSelf::new(parse_quote!(Node));
+/// let ast = replace_self.fold_derive_input(ast);
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     struct Node { children: Vec<Box<Node>>, id: <Node>::Id }
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplaceSelf {
+    /// The type to replace `Self` with.
+    replacement: Type,
+}
+
+impl ReplaceSelf {
+    /// Returns a new `ReplaceSelf` fold.
+    ///
+    /// # Parameters
+    ///
+    /// * `replacement`: The type to replace `Self` with.
+    pub fn new(replacement: Type) -> Self {
+        Self { replacement }
+    }
+}
+
+impl Fold for ReplaceSelf {
+    fn fold_type(&mut self, ty: Type) -> Type {
+        match ty {
+            Type::Path(TypePath { qself: None, path })
+                if path
+                    .segments
+                    .first()
+                    .map(|segment| segment.ident == "Self")
+                    .unwrap_or(false) =>
+            {
+                let segments_rest = path
+                    .segments
+                    .into_iter()
+                    .skip(1)
+                    .map(|segment| fold::fold_path_segment(self, segment))
+                    .collect::<Punctuated<_, Token![::]>>();
+
+                let replacement = &self.replacement;
+                if segments_rest.is_empty() {
+                    replacement.clone()
+                } else {
+                    parse_quote!(<#replacement>::#segments_rest)
+                }
+            }
+            ty => fold::fold_type(self, ty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{fold::Fold, parse_quote, DeriveInput};
+
+    use super::ReplaceSelf;
+
+    #[test]
+    fn replaces_self_in_enum_variant_fields() {
+        let ast: DeriveInput = parse_quote! {
+            enum List<T> {
+                Nil,
+                Cons(T, Box<Self>),
+            }
+        };
+
+        let mut replace_self = ReplaceSelf::new(parse_quote!(List<T>));
+        let ast = replace_self.fold_derive_input(ast);
+
+        let ast_expected: DeriveInput = parse_quote! {
+            enum List<T> {
+                Nil,
+                Cons(T, Box<List<T>>),
+            }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn replaces_self_in_associated_type_generic_args() {
+        let ast: DeriveInput = parse_quote! {
+            struct Struct { a: Self::Assoc<Self> }
+        };
+
+        let mut replace_self = ReplaceSelf::new(parse_quote!(Struct));
+        let ast = replace_self.fold_derive_input(ast);
+
+        let ast_expected: DeriveInput = parse_quote! {
+            struct Struct { a: <Struct>::Assoc<Struct> }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+}
@@ -0,0 +1,284 @@
+use proc_macro2::Ident;
+use syn::{parse_quote, punctuated::Punctuated, Attribute, DeriveInput, Meta, Path, Token};
+
+use crate::{util, AttrEditor};
+
+/// Primitive integer types that may be used in `#[repr(..)]`.
+const REPR_INTS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Functions to read and edit the `#[repr(..)]` attributes of a type.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::ReprExt;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[repr(C, u8)]
+///     enum Flag {
+///         On = 1,
+///         Off = 0,
+///     }
+/// };
+///
+/// assert!(ast.is_repr_c());
+/// assert!(!ast.is_repr_transparent());
+/// assert_eq!(Some(parse_quote!(u8)), ast.repr_int());
+/// ```
+pub trait ReprExt {
+    /// Returns the entries of all `#[repr(..)]` attributes.
+    ///
+    /// For example, `C` and `align(8)` for `#[repr(C, align(8))]`.
+    fn reprs(&self) -> Vec<Meta>;
+
+    /// Returns the primitive integer type in `#[repr(..)]`, if any.
+    ///
+    /// For example, `u8` for `#[repr(u8)]`.
+    fn repr_int(&self) -> Option<Ident>;
+
+    /// Returns whether the type is `#[repr(transparent)]`.
+    fn is_repr_transparent(&self) -> bool;
+
+    /// Returns whether the type is `#[repr(C)]`.
+    fn is_repr_c(&self) -> bool;
+
+    /// Appends entries to the `#[repr(..)]` attribute.
+    ///
+    /// * Entries that are already present are not appended again.
+    /// * If there is a `#[repr(..)]` attribute, the entries are appended to it,
+    ///   so the existing entries keep their order and the attribute keeps its
+    ///   position.
+    /// * Otherwise, a `#[repr(..)]` attribute is added.
+    ///
+    /// # Parameters
+    ///
+    /// * `reprs`: Entries to append, e.g. `C, align(8)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::ReprExt;
+    /// use syn::{parse_quote, DeriveInput};
+    ///
+    /// let mut ast: DeriveInput = parse_quote! {
+    ///     #[derive(Clone)]
+    ///     #[repr(C)]
+    ///     struct Point(f32, f32);
+    /// };
+    ///
+    /// ast.append_repr(parse_quote!(C, align(8)));
+    ///
+    /// let ast_expected: DeriveInput = parse_quote! {
+    ///     #[derive(Clone)]
+    ///     #[repr(C, align(8))]
+    ///     struct Point(f32, f32);
+    /// };
+    /// assert_eq!(ast_expected, ast);
+    /// ```
+    fn append_repr(&mut self, reprs: Punctuated<Meta, Token![,]>);
+}
+
+impl ReprExt for DeriveInput {
+    fn reprs(&self) -> Vec<Meta> {
+        util::namespace_parameters(&self.attrs, &repr_path())
+    }
+
+    fn repr_int(&self) -> Option<Ident> {
+        self.reprs().into_iter().find_map(|meta| match meta {
+            Meta::Path(path) => path
+                .get_ident()
+                .filter(|ident| REPR_INTS.iter().any(|repr_int| ident == repr_int))
+                .cloned(),
+            Meta::List(..) | Meta::NameValue(..) => None,
+        })
+    }
+
+    fn is_repr_transparent(&self) -> bool {
+        contains_repr(self, "transparent")
+    }
+
+    fn is_repr_c(&self) -> bool {
+        contains_repr(self, "C")
+    }
+
+    fn append_repr(&mut self, reprs: Punctuated<Meta, Token![,]>) {
+        let reprs_existing = self.reprs();
+        let reprs = reprs
+            .into_iter()
+            .fold(Vec::<Meta>::new(), |mut reprs, repr| {
+                if !reprs_existing.contains(&repr) && !reprs.contains(&repr) {
+                    reprs.push(repr);
+                }
+                reprs
+            });
+        if reprs.is_empty() {
+            return;
+        }
+
+        let repr_path = repr_path();
+        let attr_editor = self
+            .attrs
+            .iter_mut()
+            .filter(|attr| attr.path() == &repr_path && matches!(attr.meta, Meta::List(..)))
+            .find_map(|attr| {
+                AttrEditor::new(attr.clone())
+                    .ok()
+                    .map(|editor| (attr, editor))
+            });
+        match attr_editor {
+            Some((attr_repr, attr_editor)) => {
+                *attr_repr = reprs
+                    .into_iter()
+                    .fold(attr_editor, AttrEditor::push)
+                    .finish();
+            }
+            // Also used when the existing `#[repr(..)]` cannot be parsed, so the entries are
+            // not lost.
+            None => {
+                let attr_repr: Attribute = parse_quote!(#[repr(#(#reprs),*)]);
+                self.attrs.push(attr_repr);
+            }
+        }
+    }
+}
+
+/// Returns the `repr` path.
+fn repr_path() -> Path {
+    parse_quote!(repr)
+}
+
+/// Returns whether `#[repr(..)]` contains the given entry.
+fn contains_repr(derive_input: &DeriveInput, repr: &str) -> bool {
+    derive_input
+        .reprs()
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(repr)))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput, Meta};
+
+    use super::ReprExt;
+
+    #[test]
+    fn reprs_returns_entries_of_all_repr_attributes() {
+        let ast: DeriveInput = parse_quote! {
+            #[repr(C)]
+            #[derive(Clone)]
+            #[repr(align(8))]
+            struct Struct;
+        };
+
+        let reprs_expected: Vec<Meta> = vec![parse_quote!(C), parse_quote!(align(8))];
+        assert_eq!(reprs_expected, ast.reprs());
+    }
+
+    #[test]
+    fn repr_int_returns_none_when_no_int_repr() {
+        let ast: DeriveInput = parse_quote! {
+            #[repr(C, align(8))]
+            enum Enum {
+                A,
+            }
+        };
+
+        assert_eq!(None, ast.repr_int());
+    }
+
+    #[test]
+    fn repr_int_returns_signed_int() {
+        let ast: DeriveInput = parse_quote! {
+            #[repr(isize)]
+            enum Enum {
+                A = -1,
+            }
+        };
+
+        assert_eq!(Some(parse_quote!(isize)), ast.repr_int());
+    }
+
+    #[test]
+    fn is_repr_transparent_returns_true_only_for_transparent() {
+        let ast_transparent: DeriveInput = parse_quote! {
+            #[repr(transparent)]
+            struct Meters(f64);
+        };
+        let ast_plain: DeriveInput = parse_quote! {
+            struct Meters(f64);
+        };
+
+        assert!(ast_transparent.is_repr_transparent());
+        assert!(!ast_transparent.is_repr_c());
+        assert!(!ast_plain.is_repr_transparent());
+    }
+
+    #[test]
+    fn append_repr_adds_attribute_when_absent() {
+        let mut ast: DeriveInput = parse_quote! {
+            #[derive(Clone)]
+            enum Enum {
+                A,
+            }
+        };
+
+        ast.append_repr(parse_quote!(u8));
+
+        let ast_expected: DeriveInput = parse_quote! {
+            #[derive(Clone)]
+            #[repr(u8)]
+            enum Enum {
+                A,
+            }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn append_repr_does_not_change_attributes_when_already_present() {
+        let mut ast: DeriveInput = parse_quote! {
+            #[repr(C)]
+            struct Struct;
+        };
+        let ast_expected = ast.clone();
+
+        ast.append_repr(parse_quote!(C));
+
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn append_repr_appends_repeated_entries_once() {
+        let mut ast: DeriveInput = parse_quote! {
+            struct Struct;
+        };
+
+        ast.append_repr(parse_quote!(C, C));
+
+        let ast_expected: DeriveInput = parse_quote! {
+            #[repr(C)]
+            struct Struct;
+        };
+        assert_eq!(ast_expected, ast);
+    }
+
+    #[test]
+    fn append_repr_adds_attribute_when_existing_cannot_be_parsed() {
+        let mut ast: DeriveInput = parse_quote! {
+            #[repr(C +)]
+            struct Struct;
+        };
+
+        ast.append_repr(parse_quote!(align(8)));
+
+        let ast_expected: DeriveInput = parse_quote! {
+            #[repr(C +)]
+            #[repr(align(8))]
+            struct Struct;
+        };
+        assert_eq!(ast_expected, ast);
+    }
+}
@@ -0,0 +1,132 @@
+use syn::{
+    fold::{self, Fold},
+    token, Expr, ExprParen, ExprPath, Item,
+};
+
+/// Replaces `self` in expressions with another expression.
+///
+/// For example, with the replacement `self.inner`, `self.len()` is rewritten to
+/// `self.inner.len()`. This is useful for delegation and wrapper generating
+/// macros.
+///
+/// * `self` within closures is replaced, as closures capture `self` from the
+///   enclosing function.
+/// * `self` within nested items such as `fn`s and `impl` blocks is not
+///   replaced, as those refer to a different `self`.
+/// * Paths such as `self::module::function` are not replaced.
+/// * Tokens within macro invocations are not replaced.
+///
+/// The replacement is wrapped in parentheses unless it is a path, field
+/// access, method call, function call, index, or parenthesized expression.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::RewriteReceiver;
+/// use syn::{fold::Fold, parse_quote, Expr};
+///
+/// let expr: Expr = parse_quote!(self.values.iter().map(|value| value + self.offset));
+///
+/// let mut rewrite_receiver = RewriteReceiver::new(parse_quote!(self.inner));
+/// let expr = rewrite_receiver.fold_expr(expr);
+///
+/// let expr_expected: Expr = parse_quote!(self
+///     .inner
+///     .values
+///     .iter()
+///     .map(|value| value + self.inner.offset));
+/// assert_eq!(expr_expected, expr);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RewriteReceiver {
+    /// The expression to replace `self` with.
+    replacement: Expr,
+}
+
+impl RewriteReceiver {
+    /// Returns a new `RewriteReceiver` fold.
+    ///
+    /// # Parameters
+    ///
+    /// * `replacement`: The expression to replace `self` with.
+    pub fn new(replacement: Expr) -> Self {
+        let replacement = match replacement {
+            Expr::Path(..)
+            | Expr::Field(..)
+            | Expr::MethodCall(..)
+            | Expr::Call(..)
+            | Expr::Index(..)
+            | Expr::Paren(..) => replacement,
+            replacement => Expr::Paren(ExprParen {
+                attrs: Vec::new(),
+                paren_token: token::Paren::default(),
+                expr: Box::new(replacement),
+            }),
+        };
+
+        Self { replacement }
+    }
+}
+
+impl Fold for RewriteReceiver {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Path(ExprPath {
+                qself: None,
+                ref path,
+                ..
+            }) if path.is_ident("self") => self.replacement.clone(),
+            expr => fold::fold_expr(self, expr),
+        }
+    }
+
+    fn fold_item(&mut self, item: Item) -> Item {
+        // Nested items have their own `self`.
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{fold::Fold, parse_quote, Block, Expr};
+
+    use super::RewriteReceiver;
+
+    #[test]
+    fn replaces_self_in_closures_but_not_nested_items() {
+        let block: Block = parse_quote!({
+            let f = move || self.value;
+            impl Other {
+                fn value(&self) -> u32 {
+                    self.value
+                }
+            }
+            self::helper(self, f())
+        });
+
+        let mut rewrite_receiver = RewriteReceiver::new(parse_quote!(self.0));
+        let block = rewrite_receiver.fold_block(block);
+
+        let block_expected: Block = parse_quote!({
+            let f = move || self.0.value;
+            impl Other {
+                fn value(&self) -> u32 {
+                    self.value
+                }
+            }
+            self::helper(self.0, f())
+        });
+        assert_eq!(block_expected, block);
+    }
+
+    #[test]
+    fn wraps_replacement_in_parentheses_when_not_postfix_expression() {
+        let expr: Expr = parse_quote!(self.len());
+
+        let mut rewrite_receiver = RewriteReceiver::new(parse_quote!(*self.inner));
+        let expr = rewrite_receiver.fold_expr(expr);
+
+        let expr_expected: Expr = parse_quote!((*self.inner).len());
+        assert_eq!(expr_expected, expr);
+    }
+}
@@ -0,0 +1,351 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    fold::{self, Fold},
+    parse_quote, Block, FnArg, GenericParam, Lifetime, LifetimeParam, Receiver, ReturnType,
+    Signature, Type, TypeReference,
+};
+
+/// Functions to make it ergonomic to work with function `Signature`s.
+pub trait SignatureExt {
+    /// Rewrites an `async fn` signature into a function returning a boxed
+    /// future.
+    ///
+    /// `async fn foo(&self, x: &str) -> T` is rewritten to:
+    ///
+    /// ```text
+    /// fn foo<'life0, 'life1, 'async_trait>(&'life0 self, x: &'life1 str)
+    ///     -> Pin<Box<dyn Future<Output = T> + Send + 'async_trait>>
+    /// where
+    ///     'life0: 'async_trait,
+    ///     'life1: 'async_trait
+    /// ```
+    ///
+    /// The future captures the arguments, so it is bound by a new
+    /// `'async_trait` lifetime that each argument outlives, the same way as
+    /// [`async-trait`]:
+    ///
+    /// * Elided lifetimes in the arguments are named `'life0`, `'life1`, ...
+    /// * Each lifetime in the arguments is bound by `'async_trait`.
+    /// * Each type parameter is bound by `'async_trait`.
+    /// * `Self` is bound by `'async_trait` when `self` is taken by value.
+    /// * The `Send` bound is only added when `send` is `true`.
+    ///
+    /// The function body should be wrapped using [`desugar_async_block`].
+    ///
+    /// Signatures that are not `async` are left unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `send`: Whether the returned future must be `Send`.
+    ///
+    /// [`async-trait`]: https://docs.rs/async-trait
+    fn desugar_async(&mut self, send: bool);
+}
+
+impl SignatureExt for Signature {
+    fn desugar_async(&mut self, send: bool) {
+        if self.asyncness.take().is_none() {
+            return;
+        }
+
+        let output: Type = match &self.output {
+            ReturnType::Default => parse_quote!(()),
+            ReturnType::Type(_, ty) => Type::clone(ty),
+        };
+        let send_bound = if send {
+            Some(quote!(+ ::core::marker::Send))
+        } else {
+            None
+        };
+
+        let mut input_lifetimes = InputLifetimes::default();
+        self.inputs = std::mem::take(&mut self.inputs)
+            .into_iter()
+            .map(|input| input_lifetimes.fold_fn_arg(input))
+            .collect();
+        let takes_self_by_value = self.inputs.iter().any(|input| {
+            matches!(
+                input,
+                FnArg::Receiver(Receiver {
+                    reference: None,
+                    colon_token: None,
+                    ..
+                })
+            )
+        });
+
+        let lifetime_future = Lifetime::new("'async_trait", Span::call_site());
+        let lifetime_params_count = self
+            .generics
+            .params
+            .iter()
+            .take_while(|param| matches!(param, GenericParam::Lifetime(..)))
+            .count();
+        input_lifetimes
+            .elided
+            .iter()
+            .chain(std::iter::once(&lifetime_future))
+            .enumerate()
+            .for_each(|(index, lifetime)| {
+                self.generics.params.insert(
+                    lifetime_params_count + index,
+                    GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+                )
+            });
+        self.generics.lt_token.get_or_insert_with(Default::default);
+        self.generics.gt_token.get_or_insert_with(Default::default);
+
+        let type_params = self
+            .generics
+            .type_params()
+            .map(|type_param| type_param.ident.clone())
+            .collect::<Vec<_>>();
+        let where_clause = self.generics.make_where_clause();
+        input_lifetimes
+            .elided
+            .iter()
+            .chain(input_lifetimes.named.iter())
+            .for_each(|lifetime| {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#lifetime: #lifetime_future))
+            });
+        type_params.iter().for_each(|type_param| {
+            where_clause
+                .predicates
+                .push(parse_quote!(#type_param: #lifetime_future))
+        });
+        if takes_self_by_value {
+            where_clause
+                .predicates
+                .push(parse_quote!(Self: #lifetime_future));
+        }
+        if where_clause.predicates.is_empty() {
+            self.generics.where_clause = None;
+        }
+
+        self.output = parse_quote! {
+            -> ::core::pin::Pin<
+                ::std::boxed::Box<
+                    dyn ::core::future::Future<Output = #output> #send_bound + #lifetime_future
+                >
+            >
+        };
+    }
+}
+
+/// Names the elided lifetimes in function arguments, and records the named
+/// lifetimes.
+#[derive(Debug, Default)]
+struct InputLifetimes {
+    /// Lifetimes that were elided, in order, i.e. `'life0`, `'life1`, ...
+    elided: Vec<Lifetime>,
+    /// Lifetimes that were named, excluding `'static`.
+    named: Vec<Lifetime>,
+}
+
+impl InputLifetimes {
+    /// Returns a new lifetime for an elided lifetime.
+    fn next_elided(&mut self, span: Span) -> Lifetime {
+        let lifetime = Lifetime::new(&format!("'life{}", self.elided.len()), span);
+        self.elided.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl Fold for InputLifetimes {
+    fn fold_receiver(&mut self, mut receiver: Receiver) -> Receiver {
+        // `&self` stores the lifetime in both `reference` and `ty`, which must
+        // be named the same.
+        if let Some((and_token, lifetime)) = &mut receiver.reference {
+            let lifetime_named = match lifetime.take() {
+                Some(lifetime) => self.fold_lifetime(lifetime),
+                None => self.next_elided(and_token.span),
+            };
+            if let Type::Reference(type_reference) = &mut *receiver.ty {
+                type_reference.lifetime = Some(lifetime_named.clone());
+            }
+            *lifetime = Some(lifetime_named);
+            receiver
+        } else {
+            fold::fold_receiver(self, receiver)
+        }
+    }
+
+    fn fold_type_reference(&mut self, mut type_reference: TypeReference) -> TypeReference {
+        if type_reference.lifetime.is_none() {
+            type_reference.lifetime = Some(self.next_elided(type_reference.and_token.span));
+        }
+        fold::fold_type_reference(self, type_reference)
+    }
+
+    fn fold_lifetime(&mut self, lifetime: Lifetime) -> Lifetime {
+        if lifetime.ident == "_" {
+            self.next_elided(lifetime.apostrophe)
+        } else {
+            if lifetime.ident != "static"
+                && !self.elided.contains(&lifetime)
+                && !self.named.contains(&lifetime)
+            {
+                self.named.push(lifetime.clone());
+            }
+            lifetime
+        }
+    }
+}
+
+/// Returns the function body wrapped in a boxed `async move` block.
+///
+/// `{ body }` is rewritten to `{ Box::pin(async move { body }) }`, which is
+/// the body for a signature rewritten by [`SignatureExt::desugar_async`].
+///
+/// # Parameters
+///
+/// * `block`: The original function body.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{desugar_async_block, SignatureExt};
+/// use syn::{parse_quote, ItemFn};
+///
+/// let mut item_fn: ItemFn = parse_quote! {
+///     async fn value(&self) -> u32 { self.value }
+/// };
+/// item_fn.sig.desugar_async(true);
+/// *item_fn.block = desugar_async_block(&item_fn.block);
+///
+/// let item_fn_expected: ItemFn = parse_quote! {
+///     fn value<'life0, 'async_trait>(&'life0 self) -> ::core::pin::Pin<
+///         ::std::boxed::Box<
+///             dyn ::core::future::Future<Output = u32>
+///                 + ::core::marker::Send
+///                 + 'async_trait
+///         >
+///     >
+///     where
+///         'life0: 'async_trait
+///     {
+///         ::std::boxed::Box::pin(async move { self.value })
+///     }
+/// };
+/// assert_eq!(item_fn_expected, item_fn);
+/// ```
+pub fn desugar_async_block(block: &Block) -> Block {
+    parse_quote!({
+        ::std::boxed::Box::pin(async move #block)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Signature};
+
+    use super::SignatureExt;
+
+    #[test]
+    fn desugar_async_boxes_future_with_send_bound() {
+        let mut sig: Signature = parse_quote!(async fn value(&mut self) -> u32);
+
+        sig.desugar_async(true);
+
+        let sig_expected: Signature = parse_quote! {
+            fn value<'life0, 'async_trait>(&'life0 mut self) -> ::core::pin::Pin<
+                ::std::boxed::Box<
+                    dyn ::core::future::Future<Output = u32> + ::core::marker::Send + 'async_trait
+                >
+            >
+            where
+                'life0: 'async_trait
+        };
+        assert_eq!(sig_expected, sig);
+    }
+
+    #[test]
+    fn desugar_async_uses_unit_output_without_send() {
+        let mut sig: Signature = parse_quote!(async fn run(value: u32));
+
+        sig.desugar_async(false);
+
+        let sig_expected: Signature = parse_quote! {
+            fn run<'async_trait>(value: u32) -> ::core::pin::Pin<
+                ::std::boxed::Box<dyn ::core::future::Future<Output = ()> + 'async_trait>
+            >
+        };
+        assert_eq!(sig_expected, sig);
+    }
+
+    #[test]
+    fn desugar_async_bounds_borrowed_arguments_without_self() {
+        let mut sig: Signature = parse_quote!(async fn len(x: &str) -> usize);
+
+        sig.desugar_async(true);
+
+        let sig_expected: Signature = parse_quote! {
+            fn len<'life0, 'async_trait>(x: &'life0 str) -> ::core::pin::Pin<
+                ::std::boxed::Box<
+                    dyn ::core::future::Future<Output = usize>
+                        + ::core::marker::Send
+                        + 'async_trait
+                >
+            >
+            where
+                'life0: 'async_trait
+        };
+        assert_eq!(sig_expected, sig);
+    }
+
+    #[test]
+    fn desugar_async_bounds_borrowed_arguments_with_self() {
+        let mut sig: Signature = parse_quote! {
+            async fn find<'a, T>(&self, x: &str, y: Wrapper<'_>, z: &'a T) -> bool
+        };
+
+        sig.desugar_async(false);
+
+        let sig_expected: Signature = parse_quote! {
+            fn find<'a, 'life0, 'life1, 'life2, 'async_trait, T>(
+                &'life0 self,
+                x: &'life1 str,
+                y: Wrapper<'life2>,
+                z: &'a T
+            ) -> ::core::pin::Pin<
+                ::std::boxed::Box<dyn ::core::future::Future<Output = bool> + 'async_trait>
+            >
+            where
+                'life0: 'async_trait,
+                'life1: 'async_trait,
+                'life2: 'async_trait,
+                'a: 'async_trait,
+                T: 'async_trait
+        };
+        assert_eq!(sig_expected, sig);
+    }
+
+    #[test]
+    fn desugar_async_bounds_self_when_taken_by_value() {
+        let mut sig: Signature = parse_quote!(async fn into_value(self) -> u32);
+
+        sig.desugar_async(false);
+
+        let sig_expected: Signature = parse_quote! {
+            fn into_value<'async_trait>(self) -> ::core::pin::Pin<
+                ::std::boxed::Box<dyn ::core::future::Future<Output = u32> + 'async_trait>
+            >
+            where
+                Self: 'async_trait
+        };
+        assert_eq!(sig_expected, sig);
+    }
+
+    #[test]
+    fn desugar_async_does_not_change_non_async_signature() {
+        let mut sig: Signature = parse_quote!(fn value(&self) -> u32);
+        let sig_expected = sig.clone();
+
+        sig.desugar_async(true);
+
+        assert_eq!(sig_expected, sig);
+    }
+}
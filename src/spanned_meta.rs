@@ -0,0 +1,88 @@
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{spanned::Spanned, Meta};
+
+/// A parameter along with the span of the attribute it was written in.
+///
+/// This is returned by [`namespace_parameter_spanned`] and
+/// [`tag_parameters_spanned`], so that errors can point at the parameter, its
+/// value, or the whole attribute.
+///
+/// [`namespace_parameter_spanned`]: crate::namespace_parameter_spanned
+/// [`tag_parameters_spanned`]: crate::tag_parameters_spanned
+#[derive(Clone, Debug)]
+pub struct SpannedMeta {
+    /// The parameter.
+    pub meta: Meta,
+    /// Span of the enclosing `#[namespace(..)]` attribute.
+    pub attr_span: Span,
+}
+
+impl SpannedMeta {
+    /// Returns the span of the parameter.
+    pub fn span(&self) -> Span {
+        self.meta.span()
+    }
+
+    /// Returns the span of the parameter's value, if it is a `name = value`
+    /// parameter.
+    pub fn value_span(&self) -> Option<Span> {
+        match &self.meta {
+            Meta::NameValue(meta_name_value) => Some(meta_name_value.value.span()),
+            Meta::Path(..) | Meta::List(..) => None,
+        }
+    }
+}
+
+impl ToTokens for SpannedMeta {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.meta.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::parse_quote;
+
+    use super::SpannedMeta;
+
+    #[test]
+    fn value_span_is_some_for_name_value() {
+        let spanned_meta = SpannedMeta {
+            meta: parse_quote!(rename = "a"),
+            attr_span: Span::call_site(),
+        };
+
+        assert!(spanned_meta.value_span().is_some());
+    }
+
+    #[test]
+    fn value_span_is_none_for_path_and_list() {
+        let spanned_meta_path = SpannedMeta {
+            meta: parse_quote!(skip),
+            attr_span: Span::call_site(),
+        };
+        let spanned_meta_list = SpannedMeta {
+            meta: parse_quote!(bound(Clone)),
+            attr_span: Span::call_site(),
+        };
+
+        assert!(spanned_meta_path.value_span().is_none());
+        assert!(spanned_meta_list.value_span().is_none());
+    }
+
+    #[test]
+    fn to_tokens_outputs_meta() {
+        let spanned_meta = SpannedMeta {
+            meta: parse_quote!(rename = "a"),
+            attr_span: Span::call_site(),
+        };
+
+        assert_eq!(
+            quote!(rename = "a").to_string(),
+            quote!(#spanned_meta).to_string()
+        );
+    }
+}
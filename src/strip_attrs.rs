@@ -0,0 +1,118 @@
+use syn::{
+    fold::{self, Fold},
+    Attribute, ConstParam, DeriveInput, Field, LifetimeParam, Path, TypeParam, Variant,
+};
+
+/// Removes `#[namespace..]` attributes from a type, its fields, variants, and
+/// generic parameters.
+///
+/// This is useful for attribute macros to remove their helper attributes
+/// before re-emitting the type.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::StripAttrs;
+/// use syn::{fold::Fold, parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(tag)]
+///     #[derive(Debug)]
+///     struct Struct {
+///         #[my::derive(skip)]
+///         a: u32,
+///     }
+/// };
+///
+/// let mut strip_attrs = StripAttrs::new(parse_quote!(my::derive));
+/// let ast = strip_attrs.fold_derive_input(ast);
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[derive(Debug)]
+///     struct Struct {
+///         a: u32,
+///     }
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StripAttrs {
+    /// The `path()` of the attributes to remove.
+    namespace: Path,
+}
+
+impl StripAttrs {
+    /// Returns a new `StripAttrs` fold.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the attributes to remove.
+    pub fn new(namespace: Path) -> Self {
+        Self { namespace }
+    }
+
+    fn strip(&self, attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| attr.path() != &self.namespace);
+    }
+}
+
+impl Fold for StripAttrs {
+    fn fold_derive_input(&mut self, mut derive_input: DeriveInput) -> DeriveInput {
+        self.strip(&mut derive_input.attrs);
+        fold::fold_derive_input(self, derive_input)
+    }
+
+    fn fold_variant(&mut self, mut variant: Variant) -> Variant {
+        self.strip(&mut variant.attrs);
+        fold::fold_variant(self, variant)
+    }
+
+    fn fold_field(&mut self, mut field: Field) -> Field {
+        self.strip(&mut field.attrs);
+        fold::fold_field(self, field)
+    }
+
+    fn fold_type_param(&mut self, mut type_param: TypeParam) -> TypeParam {
+        self.strip(&mut type_param.attrs);
+        fold::fold_type_param(self, type_param)
+    }
+
+    fn fold_lifetime_param(&mut self, mut lifetime_param: LifetimeParam) -> LifetimeParam {
+        self.strip(&mut lifetime_param.attrs);
+        fold::fold_lifetime_param(self, lifetime_param)
+    }
+
+    fn fold_const_param(&mut self, mut const_param: ConstParam) -> ConstParam {
+        self.strip(&mut const_param.attrs);
+        fold::fold_const_param(self, const_param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{fold::Fold, parse_quote, DeriveInput};
+
+    use super::StripAttrs;
+
+    #[test]
+    fn strips_variant_and_generic_param_attrs() {
+        let ast: DeriveInput = parse_quote! {
+            enum Enum<#[my::derive(bound)] T> {
+                #[my::derive(skip)]
+                #[doc = "Variant."]
+                Tuple(#[my::derive(skip)] T),
+            }
+        };
+
+        let mut strip_attrs = StripAttrs::new(parse_quote!(my::derive));
+        let ast = strip_attrs.fold_derive_input(ast);
+
+        let ast_expected: DeriveInput = parse_quote! {
+            enum Enum<T> {
+                #[doc = "Variant."]
+                Tuple(T),
+            }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+}
@@ -0,0 +1,14 @@
+/// Which spellings of a tag to recognize.
+///
+/// Used by [`contains_tag_with`] and [`tag_parameters_with`].
+///
+/// [`contains_tag_with`]: crate::contains_tag_with
+/// [`tag_parameters_with`]: crate::tag_parameters_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagSpelling {
+    /// Only the nested spelling, e.g. `#[namespace(tag)]`.
+    Nested,
+    /// Both the nested spelling, and the path spelling, e.g.
+    /// `#[namespace::tag]`.
+    NestedOrPath,
+}
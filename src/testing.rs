@@ -0,0 +1,558 @@
+//! Functions to make it easier to test proc macros.
+//!
+//! `assert_eq!` on syn AST types prints the entire `Debug` output of both
+//! values on failure, which is difficult to read. The functions in this module
+//! report the differences in a more readable form.
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Fields, Ident};
+
+use crate::util;
+
+/// Returns a description of where two `DeriveInput`s differ.
+///
+/// Returns `None` if the `DeriveInput`s are equal. Otherwise, each difference
+/// is described on its own line, e.g.:
+///
+/// ```text
+/// field `b`: attribute #2: expected `#[serde(skip)]`, found `#[serde(rename = "c")]`
+/// ```
+///
+/// # Parameters
+///
+/// * `expected`: The expected AST.
+/// * `actual`: The actual AST.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::testing;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let expected: DeriveInput = parse_quote! {
+///     struct Struct {
+///         a: u32,
+///         #[my::derive(skip)]
+///         b: u32,
+///     }
+/// };
+/// let actual: DeriveInput = parse_quote! {
+///     struct Struct {
+///         a: u32,
+///         #[my::derive(rename = "c")]
+///         b: u64,
+///     }
+/// };
+///
+/// assert_eq!(None, testing::diff_derive_input(&expected, &expected));
+/// assert_eq!(
+///     Some(String::from(
+///         "field `b`: attribute #1: \
+///          expected `# [my :: derive (skip)]`, found `# [my :: derive (rename = \"c\")]`\n\
+///          field `b`: type: expected `u32`, found `u64`"
+///     )),
+///     testing::diff_derive_input(&expected, &actual)
+/// );
+/// ```
+pub fn diff_derive_input(expected: &DeriveInput, actual: &DeriveInput) -> Option<String> {
+    let mut diffs = Vec::new();
+
+    diff_attrs(&mut diffs, "", &expected.attrs, &actual.attrs);
+    diff_tokens(&mut diffs, "", "visibility", &expected.vis, &actual.vis);
+    diff_tokens(&mut diffs, "", "ident", &expected.ident, &actual.ident);
+    diff_tokens(
+        &mut diffs,
+        "",
+        "generic parameters",
+        &expected.generics.params,
+        &actual.generics.params,
+    );
+    diff_tokens(
+        &mut diffs,
+        "",
+        "where clause",
+        &expected.generics.where_clause,
+        &actual.generics.where_clause,
+    );
+
+    match (&expected.data, &actual.data) {
+        (
+            Data::Struct(DataStruct {
+                fields: fields_expected,
+                ..
+            }),
+            Data::Struct(DataStruct {
+                fields: fields_actual,
+                ..
+            }),
+        ) => diff_fields(&mut diffs, "", fields_expected, fields_actual),
+        (
+            Data::Enum(DataEnum {
+                variants: variants_expected,
+                ..
+            }),
+            Data::Enum(DataEnum {
+                variants: variants_actual,
+                ..
+            }),
+        ) => {
+            let variant_count = variants_expected.len().max(variants_actual.len());
+            (0..variant_count).for_each(|index| {
+                match (variants_expected.get(index), variants_actual.get(index)) {
+                    (Some(variant_expected), Some(variant_actual)) => {
+                        let context = format!("variant `{}`: ", variant_expected.ident);
+                        diff_attrs(
+                            &mut diffs,
+                            &context,
+                            &variant_expected.attrs,
+                            &variant_actual.attrs,
+                        );
+                        diff_tokens(
+                            &mut diffs,
+                            &context,
+                            "ident",
+                            &variant_expected.ident,
+                            &variant_actual.ident,
+                        );
+                        diff_fields(
+                            &mut diffs,
+                            &context,
+                            &variant_expected.fields,
+                            &variant_actual.fields,
+                        );
+                        diff_tokens(
+                            &mut diffs,
+                            &context,
+                            "discriminant",
+                            &variant_expected.discriminant.as_ref().map(|(_, expr)| expr),
+                            &variant_actual.discriminant.as_ref().map(|(_, expr)| expr),
+                        );
+                    }
+                    (Some(variant_expected), None) => diffs.push(format!(
+                        "variant #{}: expected `{}`, found none",
+                        index + 1,
+                        variant_expected.ident
+                    )),
+                    (None, Some(variant_actual)) => diffs.push(format!(
+                        "variant #{}: expected none, found `{}`",
+                        index + 1,
+                        variant_actual.ident
+                    )),
+                    (None, None) => {}
+                }
+            });
+        }
+        (
+            Data::Union(DataUnion {
+                fields: fields_expected,
+                ..
+            }),
+            Data::Union(DataUnion {
+                fields: fields_actual,
+                ..
+            }),
+        ) => diff_fields(
+            &mut diffs,
+            "",
+            &Fields::Named(fields_expected.clone()),
+            &Fields::Named(fields_actual.clone()),
+        ),
+        (data_expected, data_actual) => diffs.push(format!(
+            "data: expected {}, found {}",
+            data_kind(data_expected),
+            data_kind(data_actual)
+        )),
+    }
+
+    if diffs.is_empty() {
+        None
+    } else {
+        Some(diffs.join("\n"))
+    }
+}
+
+/// Returns the tokens rendered in a canonical form for comparison.
+///
+/// The following differences are normalized:
+///
+/// * Whitespace between tokens.
+/// * Spacing between punctuation, e.g. `= >` and `=>`.
+/// * Trailing commas in braces and brackets, and in parentheses that contain
+///   more than one element. `(a,)` is kept as is, as it is a tuple.
+///
+/// With the `items` feature, the tokens are first parsed as a `syn::File`
+/// where possible, so that the rendering matches `syn`'s.
+///
+/// # Parameters
+///
+/// * `tokens`: The tokens to normalize, either a `proc_macro::TokenStream` or a
+///   `proc_macro2::TokenStream`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::testing;
+/// use quote::quote;
+///
+/// let expected = quote! {
+///     impl Trait for Struct {
+///         fn f(&self) -> (u32, u32) { (self.a, self.b) }
+///     }
+/// };
+/// let actual = quote! {
+///     impl Trait for Struct {
+///         fn f(&self) -> (u32, u32,) { (self.a, self.b,) }
+///     }
+/// };
+///
+/// assert_eq!(testing::normalize(expected), testing::normalize(actual));
+/// ```
+pub fn normalize<TS>(tokens: TS) -> String
+where
+    TS: Into<TokenStream>,
+{
+    let tokens = tokens.into();
+    #[cfg(feature = "items")]
+    let tokens = match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(file) => file.into_token_stream(),
+        Err(_) => tokens,
+    };
+
+    normalize_tokens(tokens).to_string()
+}
+
+/// Punctuation pairs that form a single operator when joined.
+const JOINED_PUNCTS: &[(char, char)] = &[
+    (':', ':'),
+    ('=', '>'),
+    ('-', '>'),
+    ('<', '-'),
+    ('=', '='),
+    ('!', '='),
+    ('<', '='),
+    ('>', '='),
+    ('&', '&'),
+    ('|', '|'),
+    ('.', '.'),
+    ('.', '='),
+    ('+', '='),
+    ('-', '='),
+    ('*', '='),
+    ('/', '='),
+    ('%', '='),
+    ('^', '='),
+    ('&', '='),
+    ('|', '='),
+    ('<', '<'),
+    ('>', '>'),
+];
+
+/// Returns the tokens with punctuation spacing and trailing commas normalized.
+fn normalize_tokens(tokens: TokenStream) -> TokenStream {
+    let token_trees = tokens.into_iter().collect::<Vec<TokenTree>>();
+    let mut token_trees_normalized = Vec::with_capacity(token_trees.len());
+
+    token_trees
+        .iter()
+        .enumerate()
+        .for_each(|(index, token_tree)| match token_tree {
+            TokenTree::Group(group) => {
+                let mut stream = normalize_tokens(group.stream())
+                    .into_iter()
+                    .collect::<Vec<TokenTree>>();
+                let is_comma =
+                    |token_tree: &TokenTree| matches!(token_tree, TokenTree::Punct(punct) if punct.as_char() == ',');
+                if stream.last().map(is_comma).unwrap_or(false) {
+                    let trailing_comma_significant = group.delimiter() == Delimiter::Parenthesis
+                        && stream.iter().filter(|token_tree| is_comma(token_tree)).count() == 1;
+                    if !trailing_comma_significant {
+                        stream.pop();
+                    }
+                }
+
+                let mut group_normalized =
+                    Group::new(group.delimiter(), stream.into_iter().collect());
+                group_normalized.set_span(group.span());
+                token_trees_normalized.push(TokenTree::Group(group_normalized));
+            }
+            TokenTree::Punct(punct) => {
+                let next_char = match token_trees.get(index + 1) {
+                    Some(TokenTree::Punct(punct_next)) => Some(punct_next.as_char()),
+                    _ => None,
+                };
+                let joint = punct.as_char() == '\''
+                    || next_char
+                        .map(|next_char| JOINED_PUNCTS.contains(&(punct.as_char(), next_char)))
+                        .unwrap_or(false);
+                let spacing = if joint { Spacing::Joint } else { Spacing::Alone };
+
+                let mut punct_normalized = Punct::new(punct.as_char(), spacing);
+                punct_normalized.set_span(punct.span());
+                token_trees_normalized.push(TokenTree::Punct(punct_normalized));
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                token_trees_normalized.push(token_tree.clone())
+            }
+        });
+
+    token_trees_normalized.into_iter().collect()
+}
+
+/// Records a difference if the tokens are not equal.
+fn diff_tokens<T>(diffs: &mut Vec<String>, context: &str, what: &str, expected: &T, actual: &T)
+where
+    T: PartialEq + ToTokens,
+{
+    if expected != actual {
+        diffs.push(format!(
+            "{}{}: expected `{}`, found `{}`",
+            context,
+            what,
+            util::source_text(expected),
+            util::source_text(actual)
+        ));
+    }
+}
+
+/// Records differences between two lists of attributes.
+fn diff_attrs(
+    diffs: &mut Vec<String>,
+    context: &str,
+    attrs_expected: &[Attribute],
+    attrs_actual: &[Attribute],
+) {
+    let attr_count = attrs_expected.len().max(attrs_actual.len());
+    (0..attr_count).for_each(
+        |index| match (attrs_expected.get(index), attrs_actual.get(index)) {
+            (Some(attr_expected), Some(attr_actual)) => diff_tokens(
+                diffs,
+                context,
+                &format!("attribute #{}", index + 1),
+                attr_expected,
+                attr_actual,
+            ),
+            (Some(attr_expected), None) => diffs.push(format!(
+                "{}attribute #{}: expected `{}`, found none",
+                context,
+                index + 1,
+                util::source_text(attr_expected)
+            )),
+            (None, Some(attr_actual)) => diffs.push(format!(
+                "{}attribute #{}: expected none, found `{}`",
+                context,
+                index + 1,
+                util::source_text(attr_actual)
+            )),
+            (None, None) => {}
+        },
+    );
+}
+
+/// Records differences between two sets of fields.
+fn diff_fields(
+    diffs: &mut Vec<String>,
+    context: &str,
+    fields_expected: &Fields,
+    fields_actual: &Fields,
+) {
+    let fields_kind_expected = fields_kind(fields_expected);
+    let fields_kind_actual = fields_kind(fields_actual);
+    if fields_kind_expected != fields_kind_actual {
+        diffs.push(format!(
+            "{}fields: expected {} fields, found {} fields",
+            context, fields_kind_expected, fields_kind_actual
+        ));
+        return;
+    }
+
+    let field_count = fields_expected.len().max(fields_actual.len());
+    let field_label = |index: usize, ident: &Option<Ident>| match ident {
+        Some(ident) => ident.to_string(),
+        None => index.to_string(),
+    };
+    (0..field_count).for_each(|index| {
+        let field_expected = fields_expected.iter().nth(index);
+        let field_actual = fields_actual.iter().nth(index);
+        match (field_expected, field_actual) {
+            (Some(field_expected), Some(field_actual)) => {
+                let context = format!(
+                    "{}field `{}`: ",
+                    context,
+                    field_label(index, &field_expected.ident)
+                );
+                diff_attrs(diffs, &context, &field_expected.attrs, &field_actual.attrs);
+                diff_tokens(
+                    diffs,
+                    &context,
+                    "visibility",
+                    &field_expected.vis,
+                    &field_actual.vis,
+                );
+                diff_tokens(
+                    diffs,
+                    &context,
+                    "ident",
+                    &field_expected.ident,
+                    &field_actual.ident,
+                );
+                diff_tokens(
+                    diffs,
+                    &context,
+                    "type",
+                    &field_expected.ty,
+                    &field_actual.ty,
+                );
+            }
+            (Some(field_expected), None) => diffs.push(format!(
+                "{}field #{}: expected `{}`, found none",
+                context,
+                index + 1,
+                field_label(index, &field_expected.ident)
+            )),
+            (None, Some(field_actual)) => diffs.push(format!(
+                "{}field #{}: expected none, found `{}`",
+                context,
+                index + 1,
+                field_label(index, &field_actual.ident)
+            )),
+            (None, None) => {}
+        }
+    });
+}
+
+/// Returns the kind of data for use in messages.
+fn data_kind(data: &Data) -> &'static str {
+    match data {
+        Data::Struct(_) => "struct",
+        Data::Enum(_) => "enum",
+        Data::Union(_) => "union",
+    }
+}
+
+/// Returns the kind of fields for use in messages.
+fn fields_kind(fields: &Fields) -> &'static str {
+    match fields {
+        Fields::Unit => "unit",
+        Fields::Named(_) => "named",
+        Fields::Unnamed(_) => "tuple",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{Punct, Spacing, TokenStream, TokenTree};
+    use quote::quote;
+    use syn::{parse_quote, DeriveInput};
+
+    use super::{diff_derive_input, normalize};
+
+    #[test]
+    fn normalize_removes_insignificant_trailing_commas() {
+        assert_eq!(
+            normalize(quote!(f([a, b], { c }, (d, e)))),
+            normalize(quote!(f([a, b,], { c, }, (d, e,),)))
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_single_element_tuple_trailing_comma() {
+        assert_ne!(
+            normalize(quote!(let a = (b,);)),
+            normalize(quote!(let a = (b);))
+        );
+    }
+
+    #[test]
+    fn normalize_joins_operator_punctuation() {
+        let tokens_alone = [
+            TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+            TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+        ]
+        .into_iter()
+        .collect::<TokenStream>();
+
+        assert_eq!(normalize(quote!(=>)), normalize(tokens_alone));
+    }
+
+    #[test]
+    fn diff_derive_input_returns_none_for_equal_inputs() {
+        let ast: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            enum Enum<T> {
+                A(T),
+                B { b: u32 } = 2,
+            }
+        };
+
+        assert_eq!(None, diff_derive_input(&ast, &ast.clone()));
+    }
+
+    #[test]
+    fn diff_derive_input_reports_item_level_differences() {
+        let expected: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            pub struct Struct<T>(T);
+        };
+        let actual: DeriveInput = parse_quote! {
+            struct Other<T>(T) where T: Clone;
+        };
+
+        assert_eq!(
+            Some(
+                [
+                    "attribute #1: expected `# [derive (Debug)]`, found none",
+                    "visibility: expected `pub`, found ``",
+                    "ident: expected `Struct`, found `Other`",
+                    "where clause: expected ``, found `where T : Clone`",
+                ]
+                .join("\n")
+            ),
+            diff_derive_input(&expected, &actual)
+        );
+    }
+
+    #[test]
+    fn diff_derive_input_reports_variant_differences() {
+        let expected: DeriveInput = parse_quote! {
+            enum Enum {
+                A(u32),
+                B { b: u32 },
+            }
+        };
+        let actual: DeriveInput = parse_quote! {
+            enum Enum {
+                A(u32, u64),
+                B,
+                C,
+            }
+        };
+
+        assert_eq!(
+            Some(
+                [
+                    "variant `A`: field #2: expected none, found `1`",
+                    "variant `B`: fields: expected named fields, found unit fields",
+                    "variant #3: expected none, found `C`",
+                ]
+                .join("\n")
+            ),
+            diff_derive_input(&expected, &actual)
+        );
+    }
+
+    #[test]
+    fn diff_derive_input_reports_data_kind_difference() {
+        let expected: DeriveInput = parse_quote!(
+            struct A;
+        );
+        let actual: DeriveInput = parse_quote!(
+            enum A {}
+        );
+
+        assert_eq!(
+            Some(String::from("data: expected struct, found enum")),
+            diff_derive_input(&expected, &actual)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use syn::{
+    punctuated::Punctuated, Expr, Path, Token, TraitBound, Type, TypeArray, TypeParamBound,
+    TypeTraitObject, TypeTuple,
+};
+
+/// Functions to make it ergonomic to inspect `Type`s.
+pub trait TypeExt {
+    /// Returns the element type and length expression of an array type.
+    ///
+    /// For example, `u8` and `N` for `[u8; N]`.
+    ///
+    /// Returns `None` if the type is not an array.
+    fn array_parts(&self) -> Option<(&Type, &Expr)>;
+
+    /// Returns the element types of a tuple type.
+    ///
+    /// For example, `A, B` for `(A, B)`. The unit type `()` returns an empty
+    /// list.
+    ///
+    /// Returns `None` if the type is not a tuple.
+    fn tuple_elems(&self) -> Option<&Punctuated<Type, Token![,]>>;
+
+    /// Returns whether the type is a trait object, e.g. `dyn Trait + Send`.
+    fn is_trait_object(&self) -> bool;
+
+    /// Returns whether the type is an `impl Trait` type.
+    fn is_impl_trait(&self) -> bool;
+
+    /// Returns the path of the first trait bound of a trait object.
+    ///
+    /// For example, `Trait` for `dyn Trait + Send + 'a`.
+    ///
+    /// Returns `None` if the type is not a trait object.
+    fn dyn_trait_path(&self) -> Option<&Path>;
+}
+
+impl TypeExt for Type {
+    fn array_parts(&self) -> Option<(&Type, &Expr)> {
+        if let Type::Array(TypeArray { elem, len, .. }) = self {
+            Some((elem, len))
+        } else {
+            None
+        }
+    }
+
+    fn tuple_elems(&self) -> Option<&Punctuated<Type, Token![,]>> {
+        if let Type::Tuple(TypeTuple { elems, .. }) = self {
+            Some(elems)
+        } else {
+            None
+        }
+    }
+
+    fn is_trait_object(&self) -> bool {
+        matches!(self, Type::TraitObject(..))
+    }
+
+    fn is_impl_trait(&self) -> bool {
+        matches!(self, Type::ImplTrait(..))
+    }
+
+    fn dyn_trait_path(&self) -> Option<&Path> {
+        if let Type::TraitObject(TypeTraitObject { bounds, .. }) = self {
+            bounds.iter().find_map(|bound| {
+                if let TypeParamBound::Trait(TraitBound { path, .. }) = bound {
+                    Some(path)
+                } else {
+                    None
+                }
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, punctuated::Punctuated, Expr, Path, Token, Type};
+
+    use super::TypeExt;
+
+    #[test]
+    fn array_parts_returns_elem_and_len_for_array() {
+        let ty: Type = parse_quote!([u8; N * 2]);
+
+        let elem_expected: Type = parse_quote!(u8);
+        let len_expected: Expr = parse_quote!(N * 2);
+        assert_eq!(Some((&elem_expected, &len_expected)), ty.array_parts());
+    }
+
+    #[test]
+    fn array_parts_returns_none_for_non_array() {
+        let slice: Type = parse_quote!([u8]);
+        let path: Type = parse_quote!(Vec<u8>);
+
+        assert_eq!(None, slice.array_parts());
+        assert_eq!(None, path.array_parts());
+    }
+
+    #[test]
+    fn tuple_elems_returns_elems_for_tuple() {
+        let ty: Type = parse_quote!((u32, String));
+
+        let elems_expected: Punctuated<Type, Token![,]> = parse_quote!(u32, String);
+        assert_eq!(Some(&elems_expected), ty.tuple_elems());
+    }
+
+    #[test]
+    fn tuple_elems_returns_empty_for_unit() {
+        let ty: Type = parse_quote!(());
+
+        assert_eq!(Some(&Punctuated::new()), ty.tuple_elems());
+    }
+
+    #[test]
+    fn tuple_elems_returns_none_for_non_tuple() {
+        let ty: Type = parse_quote!([u8; 4]);
+
+        assert_eq!(None, ty.tuple_elems());
+    }
+
+    #[test]
+    fn is_trait_object_returns_true_for_dyn_trait() {
+        let ty: Type = parse_quote!(dyn Trait + Send);
+
+        assert!(ty.is_trait_object());
+        assert!(!ty.is_impl_trait());
+    }
+
+    #[test]
+    fn is_impl_trait_returns_true_for_impl_trait() {
+        let ty: Type = parse_quote!(impl Iterator<Item = u32>);
+
+        assert!(ty.is_impl_trait());
+        assert!(!ty.is_trait_object());
+    }
+
+    #[test]
+    fn dyn_trait_path_returns_first_trait_bound_path() {
+        let ty: Type = parse_quote!(dyn 'a + std::fmt::Debug + Send);
+
+        let path_expected: Path = parse_quote!(std::fmt::Debug);
+        assert_eq!(Some(&path_expected), ty.dyn_trait_path());
+    }
+
+    #[test]
+    fn dyn_trait_path_returns_none_for_non_trait_object() {
+        let ty: Type = parse_quote!(Box<dyn Trait>);
+
+        assert_eq!(None, ty.dyn_trait_path());
+    }
+}
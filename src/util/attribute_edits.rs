@@ -0,0 +1,410 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    punctuated::Punctuated, AttrStyle, Attribute, Expr, MacroDelimiter, Meta, MetaList,
+    MetaNameValue, Path, Token,
+};
+
+use super::{format_path, tokens_may_contain};
+
+/// Removes `tag` from `#[namespace(..)]` attributes, retaining other tags.
+///
+/// `#[namespace(a, tag, b)]` is rewritten to `#[namespace(a, b)]`. If the
+/// attribute has no tags remaining, the attribute is removed.
+///
+/// Tags of any form are removed, i.e. `tag`, `tag(..)`, and `tag = ..`.
+///
+/// Returns whether any tag was removed.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to modify.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::remove_tag;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let mut ast: DeriveInput = parse_quote! {
+///     #[namespace(a, skip, b)]
+///     #[namespace(skip)]
+///     pub struct MyStruct;
+/// };
+///
+/// let removed = remove_tag(
+///     &mut ast.attrs,
+///     &parse_quote!(namespace),
+///     &parse_quote!(skip),
+/// );
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[namespace(a, b)]
+///     pub struct MyStruct;
+/// };
+/// assert!(removed);
+/// assert_eq!(ast_expected, ast);
+/// ```
+pub fn remove_tag(attrs: &mut Vec<Attribute>, namespace: &Path, tag: &Path) -> bool {
+    let mut removed = false;
+
+    attrs.retain_mut(|attr| {
+        if attr.path() != namespace {
+            return true;
+        }
+
+        let nested_metas = match &attr.meta {
+            Meta::List(meta_list) if tokens_may_contain(&meta_list.tokens, tag) => {
+                meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            }
+            _ => return true,
+        };
+
+        match nested_metas {
+            Ok(nested_metas) if nested_metas.iter().any(|meta| meta.path() == tag) => {
+                removed = true;
+
+                let nested_metas = nested_metas
+                    .into_iter()
+                    .filter(|meta| meta.path() != tag)
+                    .collect::<Vec<Meta>>();
+                if nested_metas.is_empty() {
+                    false
+                } else {
+                    if let Meta::List(meta_list) = &mut attr.meta {
+                        meta_list.tokens = quote!(#(#nested_metas),*);
+                    }
+                    true
+                }
+            }
+            _ => true,
+        }
+    });
+
+    removed
+}
+
+/// Appends `tag` to the first `#[namespace(..)]` attribute.
+///
+/// * If the `#[namespace(..)]` attribute does not exist, one will be created.
+/// * If the `#[namespace]` attribute exists without a list, the tag is added as
+///   its list.
+/// * If the `#[namespace(..)]` attribute exists, the tag is appended to the end
+///   of its list.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to modify.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The nested meta to append.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::append_tag;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let mut ast: DeriveInput = parse_quote! {
+///     #[namespace(a)]
+///     pub struct MyStruct;
+/// };
+///
+/// append_tag(
+///     &mut ast.attrs,
+///     &parse_quote!(namespace),
+///     parse_quote!(b = 1),
+/// );
+/// append_tag(&mut ast.attrs, &parse_quote!(other), parse_quote!(c));
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[namespace(a, b = 1)]
+///     #[other(c)]
+///     pub struct MyStruct;
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+pub fn append_tag(attrs: &mut Vec<Attribute>, namespace: &Path, tag: Meta) {
+    let attr_existing = attrs
+        .iter_mut()
+        .filter(|attr| attr.path() == namespace)
+        .find_map(|attr| match &attr.meta {
+            Meta::Path(_) => Some((attr, Punctuated::<Meta, Token![,]>::new())),
+            Meta::List(meta_list) => meta_list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+                .map(|nested_metas| (attr, nested_metas)),
+            Meta::NameValue(_) => None,
+        });
+
+    if let Some((attr, mut nested_metas)) = attr_existing {
+        nested_metas.push(tag);
+
+        match &mut attr.meta {
+            Meta::List(meta_list) => meta_list.tokens = nested_metas.into_token_stream(),
+            meta => *meta = meta_list(namespace.clone(), nested_metas.into_token_stream()),
+        }
+    } else {
+        attrs.push(attribute_list(namespace.clone(), tag.into_token_stream()));
+    }
+}
+
+/// Sets the value of `key` in `#[namespace(key = value)]` attributes.
+///
+/// * If `key` exists in any `#[namespace(..)]` attribute, each occurrence is
+///   replaced with `key = value`.
+/// * Otherwise, `key = value` is appended using [`append_tag`].
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to modify.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `key`: The `path()` of the parameter.
+/// * `value`: The value to set the parameter to.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::set_parameter;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let mut ast: DeriveInput = parse_quote! {
+///     #[serde(rename_all = "snake_case", crate = "serde")]
+///     pub struct MyStruct;
+/// };
+///
+/// set_parameter(
+///     &mut ast.attrs,
+///     &parse_quote!(serde),
+///     &parse_quote!(crate),
+///     parse_quote!("my_crate::serde"),
+/// );
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[serde(rename_all = "snake_case", crate = "my_crate::serde")]
+///     pub struct MyStruct;
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+pub fn set_parameter(attrs: &mut Vec<Attribute>, namespace: &Path, key: &Path, value: Expr) {
+    let mut key_exists = false;
+
+    attrs
+        .iter_mut()
+        .filter(|attr| attr.path() == namespace)
+        .for_each(|attr| {
+            if let Meta::List(meta_list) = &mut attr.meta {
+                if !tokens_may_contain(&meta_list.tokens, key) {
+                    return;
+                }
+
+                let nested_metas =
+                    meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated);
+
+                if let Ok(mut nested_metas) = nested_metas {
+                    let mut replaced = false;
+                    nested_metas
+                        .iter_mut()
+                        .filter(|meta| meta.path() == key)
+                        .for_each(|meta| {
+                            *meta = Meta::NameValue(MetaNameValue {
+                                path: key.clone(),
+                                eq_token: Default::default(),
+                                value: value.clone(),
+                            });
+                            replaced = true;
+                        });
+
+                    if replaced {
+                        meta_list.tokens = nested_metas.into_token_stream();
+                        key_exists = true;
+                    }
+                }
+            }
+        });
+
+    if !key_exists {
+        let tag = Meta::NameValue(MetaNameValue {
+            path: key.clone(),
+            eq_token: Default::default(),
+            value,
+        });
+        append_tag(attrs, namespace, tag);
+    }
+}
+
+/// Normalizes attributes so that equivalent attributes compare equal.
+///
+/// This is intended for comparing attributes in tests, where formatting
+/// differences in macro output should not cause spurious failures:
+///
+/// * `#[namespace(..)]` attributes with the same path and style are merged into
+///   the first occurrence.
+/// * Nested metas are sorted, recursively for nested lists.
+/// * Trailing commas are removed, and whitespace is canonicalized by
+///   re-rendering each list from its parsed form.
+///
+/// Attributes that are not list attributes, or whose arguments are not a
+/// comma separated list of `Meta`s, are left untouched.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes to normalize.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::normalize_attrs;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let mut ast: DeriveInput = parse_quote! {
+///     #[namespace(b = 2, a = 1,)]
+///     #[doc = "Docs."]
+///     #[namespace(One)]
+///     pub struct MyStruct;
+/// };
+/// normalize_attrs(&mut ast.attrs);
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     #[namespace(One, a = 1, b = 2)]
+///     #[doc = "Docs."]
+///     pub struct MyStruct;
+/// };
+/// assert_eq!(ast_expected.attrs, ast.attrs);
+/// ```
+pub fn normalize_attrs(attrs: &mut Vec<Attribute>) {
+    let mut attrs_normalized = Vec::<(Attribute, Option<Vec<Meta>>)>::with_capacity(attrs.len());
+
+    attrs.drain(..).for_each(|attr| {
+        let nested_metas = match &attr.meta {
+            Meta::List(meta_list) => meta_list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok(),
+            Meta::Path(_) | Meta::NameValue(_) => None,
+        };
+
+        match nested_metas {
+            Some(nested_metas) => {
+                let attr_existing = attrs_normalized.iter_mut().find_map(
+                    |(attr_existing, nested_metas_existing)| {
+                        if attr_existing.style == attr.style && attr_existing.path() == attr.path()
+                        {
+                            nested_metas_existing.as_mut()
+                        } else {
+                            None
+                        }
+                    },
+                );
+
+                if let Some(nested_metas_existing) = attr_existing {
+                    nested_metas_existing.extend(nested_metas);
+                } else {
+                    attrs_normalized.push((attr, Some(nested_metas.into_iter().collect())));
+                }
+            }
+            None => attrs_normalized.push((attr, None)),
+        }
+    });
+
+    attrs.extend(
+        attrs_normalized
+            .into_iter()
+            .map(|(mut attr, nested_metas)| {
+                if let (Some(nested_metas), Meta::List(meta_list)) = (nested_metas, &mut attr.meta)
+                {
+                    meta_list.tokens = normalize_nested_metas(nested_metas);
+                }
+                attr
+            }),
+    );
+}
+
+/// Returns whether two lists of attributes are equal after normalization.
+///
+/// See [`normalize_attrs`] for the normalization that is applied.
+///
+/// # Parameters
+///
+/// * `attrs_a`: The first list of attributes.
+/// * `attrs_b`: The second list of attributes.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::normalized_attrs_eq;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast_a: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     #[namespace(two = "")]
+///     pub struct MyStruct;
+/// };
+/// let ast_b: DeriveInput = parse_quote! {
+///     #[namespace(two = "", One,)]
+///     pub struct MyStruct;
+/// };
+///
+/// assert!(normalized_attrs_eq(&ast_a.attrs, &ast_b.attrs));
+/// ```
+pub fn normalized_attrs_eq(attrs_a: &[Attribute], attrs_b: &[Attribute]) -> bool {
+    let mut attrs_a = attrs_a.to_vec();
+    let mut attrs_b = attrs_b.to_vec();
+    normalize_attrs(&mut attrs_a);
+    normalize_attrs(&mut attrs_b);
+
+    attrs_a == attrs_b
+}
+
+/// Returns an outer `#[path(tokens)]` attribute.
+///
+/// This constructs the attribute directly, avoiding the cost of tokenizing and
+/// parsing the attribute through `parse_quote!`.
+pub(crate) fn attribute_list(path: Path, tokens: TokenStream) -> Attribute {
+    Attribute {
+        pound_token: Default::default(),
+        style: AttrStyle::Outer,
+        bracket_token: Default::default(),
+        meta: meta_list(path, tokens),
+    }
+}
+
+/// Returns a `path(tokens)` meta.
+pub(crate) fn meta_list(path: Path, tokens: TokenStream) -> Meta {
+    Meta::List(MetaList {
+        path,
+        delimiter: MacroDelimiter::Paren(Default::default()),
+        tokens,
+    })
+}
+
+/// Returns the sorted, comma separated tokens for the given nested metas.
+fn normalize_nested_metas(nested_metas: Vec<Meta>) -> TokenStream {
+    let mut nested_metas = nested_metas
+        .into_iter()
+        .map(normalize_meta)
+        .map(|meta| {
+            let sort_key = (format_path(meta.path()), quote!(#meta).to_string());
+            (sort_key, meta)
+        })
+        .collect::<Vec<_>>();
+    nested_metas.sort_by(|(sort_key_a, _), (sort_key_b, _)| sort_key_a.cmp(sort_key_b));
+
+    let nested_metas = nested_metas.into_iter().map(|(_, meta)| meta);
+    quote!(#(#nested_metas),*)
+}
+
+/// Normalizes the nested metas of a `Meta::List`, if they are metas.
+fn normalize_meta(meta: Meta) -> Meta {
+    match meta {
+        Meta::List(mut meta_list) => {
+            if let Ok(nested_metas) =
+                meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            {
+                meta_list.tokens = normalize_nested_metas(nested_metas.into_iter().collect());
+            }
+            Meta::List(meta_list)
+        }
+        Meta::Path(_) | Meta::NameValue(_) => meta,
+    }
+}
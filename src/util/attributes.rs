@@ -0,0 +1,2002 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Error, Expr, ExprLit, ExprPath, Lit,
+    LitInt, LitStr, Meta, MetaList, Path, Token,
+};
+
+use super::{at_most_one, format_path, source_text};
+use crate::{attr_validator, Diagnostics, ErrorBuilder, MergeStrategy, SpannedMeta, TagSpelling};
+
+/// Returns whether an item's attributes contains a given `#[namespace]`
+/// attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+pub fn contains_namespace(attrs: &[Attribute], namespace: &Path) -> bool {
+    attrs.iter().any(|attr| attr.path() == namespace)
+}
+
+/// Returns whether an item's attributes contains a given `#[namespace(tag)]`
+/// attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+pub fn contains_tag(attrs: &[Attribute], namespace: &Path, tag: &Path) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path() == namespace)
+        .filter(|attr| attr_tokens_may_contain(attr, tag))
+        .any(|attr| {
+            parse_nested_metas(attr)
+                .map(|tags| tags.iter().any(|tag_meta| tag_meta.path() == tag))
+                .unwrap_or(false)
+        })
+}
+
+/// Returns whether an item's attributes contains a given tag, in any of the
+/// given spellings.
+///
+/// With [`TagSpelling::NestedOrPath`], both `#[namespace(tag)]` and
+/// `#[namespace::tag]` are recognized.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+/// * `spelling`: Which spellings of the tag to recognize.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{contains_tag_with, TagSpelling};
+/// use syn::{parse_quote, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my_derive::skip]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(my_derive);
+/// let tag: Path = parse_quote!(skip);
+/// assert!(!contains_tag_with(
+///     &ast.attrs,
+///     &ns,
+///     &tag,
+///     TagSpelling::Nested
+/// ));
+/// assert!(contains_tag_with(
+///     &ast.attrs,
+///     &ns,
+///     &tag,
+///     TagSpelling::NestedOrPath
+/// ));
+/// ```
+pub fn contains_tag_with(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+    spelling: TagSpelling,
+) -> bool {
+    contains_tag(attrs, namespace, tag)
+        || (spelling == TagSpelling::NestedOrPath
+            && attrs
+                .iter()
+                .any(|attr| path_is_joined(attr.path(), namespace, tag)))
+}
+
+/// Returns the span of the first `#[namespace]` attribute, if present.
+///
+/// This allows errors and notes to point at the attribute that the user wrote.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+pub fn contains_namespace_spanned(attrs: &[Attribute], namespace: &Path) -> Option<Span> {
+    attrs
+        .iter()
+        .find(|attr| attr.path() == namespace)
+        .map(|attr| attr.span())
+}
+
+/// Returns the spans of each `#[namespace]` attribute after the first.
+///
+/// `namespace_parameters` merges the parameters of repeated attributes, so
+/// this allows a macro to reject them instead.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::duplicate_namespaces;
+/// use syn::{parse_quote, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(skip)]
+///     #[other]
+///     #[my::derive(skip)]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(my::derive);
+/// assert_eq!(1, duplicate_namespaces(&ast.attrs, &ns).len());
+/// ```
+pub fn duplicate_namespaces(attrs: &[Attribute], namespace: &Path) -> Vec<Span> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path() == namespace)
+        .skip(1)
+        .map(|attr| attr.span())
+        .collect()
+}
+
+/// Returns an error if there is more than one `#[namespace]` attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at each `#[namespace]` attribute after the first,
+/// with a note at the first.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::assert_unique_namespace;
+/// use syn::{parse_quote, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(rename = "a")]
+///     #[my::derive(rename = "b")]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(my::derive);
+/// let error = assert_unique_namespace(&ast.attrs, &ns).unwrap_err();
+/// assert_eq!(
+///     "Expected at most one `#[my::derive(..)]` attribute.",
+///     error.to_string()
+/// );
+/// ```
+pub fn assert_unique_namespace(attrs: &[Attribute], namespace: &Path) -> syn::Result<()> {
+    let mut attrs_namespace = attrs.iter().filter(|attr| attr.path() == namespace);
+    let mut diagnostics = Diagnostics::new();
+
+    if let Some(attr_first) = attrs_namespace.next() {
+        attrs_namespace.for_each(|attr| {
+            diagnostics.push(
+                ErrorBuilder::new_spanned(
+                    attr,
+                    format!(
+                        "Expected at most one `#[{}(..)]` attribute.",
+                        format_path(namespace)
+                    ),
+                )
+                .note_spanned(attr_first, "previously specified here")
+                .build(),
+            )
+        });
+    }
+
+    diagnostics.error_or(())
+}
+
+/// Returns an error if a parameter is specified more than once across the
+/// `#[namespace(..)]` attributes.
+///
+/// This catches repeated tags, e.g. `#[namespace(skip)]` written twice, as
+/// well as conflicting values, e.g. `#[namespace(rename = "a", rename = "b")]`.
+/// Parameters are compared by their path.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at each repeated parameter, with a note at its
+/// first occurrence. Attributes that cannot be parsed are also reported.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::assert_unique_parameters;
+/// use syn::{parse_quote, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(skip, rename = "a")]
+///     #[my::derive(rename = "b")]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(my::derive);
+/// let error = assert_unique_parameters(&ast.attrs, &ns).unwrap_err();
+/// assert_eq!(
+///     "Parameter `rename` is specified more than once in `#[my::derive(..)]`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn assert_unique_parameters(attrs: &[Attribute], namespace: &Path) -> syn::Result<()> {
+    let mut diagnostics = Diagnostics::new();
+    let nested_metas = diagnostics
+        .check(namespace_nested_metas_checked(attrs, namespace))
+        .unwrap_or_default();
+
+    nested_metas
+        .iter()
+        .enumerate()
+        .filter_map(|(index, meta)| {
+            nested_metas[..index]
+                .iter()
+                .find(|meta_previous| meta_previous.path() == meta.path())
+                .map(|meta_first| (meta_first, meta))
+        })
+        .for_each(|(meta_first, meta)| {
+            diagnostics.push(
+                ErrorBuilder::new_spanned(
+                    meta,
+                    format!(
+                        "Parameter `{}` is specified more than once in `#[{}(..)]`.",
+                        format_path(meta.path()),
+                        format_path(namespace)
+                    ),
+                )
+                .note_spanned(meta_first, "previously specified here")
+                .build(),
+            )
+        });
+
+    diagnostics.error_or(())
+}
+
+/// Returns the span of the first `tag` in `#[namespace(tag)]` attributes, if
+/// present.
+///
+/// This allows errors and notes to point at the tag that the user wrote.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{contains_tag_spanned, ErrorBuilder};
+/// use syn::{parse_quote, Fields, FieldsNamed, Path};
+///
+/// let fields_named: FieldsNamed = parse_quote!({
+///     #[my_derive(skip, default)]
+///     field: u32,
+/// });
+/// let fields = Fields::from(fields_named);
+/// let field = fields.iter().next().unwrap();
+///
+/// let ns: Path = parse_quote!(my_derive);
+/// let span_skip = contains_tag_spanned(&field.attrs, &ns, &parse_quote!(skip));
+/// let span_default = contains_tag_spanned(&field.attrs, &ns, &parse_quote!(default));
+///
+/// if let (Some(span_skip), Some(span_default)) = (span_skip, span_default) {
+///     let error = ErrorBuilder::new(span_default, "`default` conflicts with `skip`.")
+///         .note(span_skip, "`skip` here")
+///         .build();
+///     assert_eq!("`default` conflicts with `skip`.", error.to_string());
+/// } else {
+///     panic!("Expected both tags to be present.");
+/// }
+/// ```
+pub fn contains_tag_spanned(attrs: &[Attribute], namespace: &Path, tag: &Path) -> Option<Span> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path() == namespace)
+        .filter(|attr| attr_tokens_may_contain(attr, tag))
+        .find_map(|attr| {
+            parse_nested_metas(attr).ok().and_then(|tags| {
+                tags.iter()
+                    .find(|tag_meta| tag_meta.path() == tag)
+                    .map(|tag_meta| tag_meta.span())
+            })
+        })
+}
+
+/// Returns the parameter from `#[namespace(parameter)]`.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameter;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let namespace_param = namespace_parameter(&ast.attrs, &ns);
+///
+/// let meta_one: Path = parse_quote!(One);
+/// let param_one = Meta::Path(meta_one);
+/// assert_eq!(Some(param_one), namespace_param);
+///
+/// let ns_other: Path = parse_quote!(namespace_other);
+/// let namespace_param_other = namespace_parameter(&ast.attrs, &ns_other);
+/// assert_eq!(None, namespace_param_other);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the number of parameters for the tag is more than one.
+#[allow(clippy::let_and_return)] // Needed due to bug in clippy.
+pub fn namespace_parameter(attrs: &[Attribute], namespace: &Path) -> Option<Meta> {
+    let namespace_parameter = at_most_one(namespace_nested_metas_iter(attrs, namespace), || {
+        format!(
+            "Expected at most one parameter for `#[{}(..)]`",
+            format_path(namespace),
+        )
+    });
+
+    namespace_parameter.unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Returns the parameter from `#[namespace(parameter)]`, or an error if there
+/// is more than one.
+///
+/// This is the non-panicking counterpart of [`namespace_parameter`].
+/// Attributes that cannot be parsed are also reported, see
+/// [`namespace_nested_metas_checked`].
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the second parameter, with a note at the first,
+/// if there is more than one parameter.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameter_checked;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     #[other(One, Two)]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let meta_one: Meta = parse_quote!(One);
+/// assert_eq!(
+///     Some(meta_one),
+///     namespace_parameter_checked(&ast.attrs, &ns).unwrap()
+/// );
+///
+/// let ns_other: Path = parse_quote!(other);
+/// let error = namespace_parameter_checked(&ast.attrs, &ns_other).unwrap_err();
+/// assert_eq!(
+///     "Expected at most one parameter for `#[other(..)]`, found `One, Two`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn namespace_parameter_checked(
+    attrs: &[Attribute],
+    namespace: &Path,
+) -> syn::Result<Option<Meta>> {
+    at_most_one(namespace_nested_metas_checked(attrs, namespace)?, || {
+        format!(
+            "Expected at most one parameter for `#[{}(..)]`",
+            format_path(namespace),
+        )
+    })
+}
+
+/// Returns the parameter from `#[namespace(parameter)]`, with the span of the
+/// attribute it was written in.
+///
+/// This allows a macro that rejects the parameter to point at it, its value,
+/// or the attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameter_spanned;
+/// use syn::{parse_quote, DeriveInput, Error, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(rename = 1)]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let spanned_meta = namespace_parameter_spanned(&ast.attrs, &ns).unwrap();
+///
+/// let meta_expected: Meta = parse_quote!(rename = 1);
+/// assert_eq!(meta_expected, spanned_meta.meta);
+///
+/// let value_span = spanned_meta.value_span().unwrap();
+/// let error = Error::new(value_span, "Expected a string literal.");
+/// assert_eq!("Expected a string literal.", error.to_string());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the number of parameters for the tag is more than one.
+pub fn namespace_parameter_spanned(attrs: &[Attribute], namespace: &Path) -> Option<SpannedMeta> {
+    let namespace_parameter = at_most_one(
+        namespace_nested_metas_spanned_iter(attrs, namespace),
+        || {
+            format!(
+                "Expected at most one parameter for `#[{}(..)]`",
+                format_path(namespace),
+            )
+        },
+    );
+
+    namespace_parameter.unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Returns the value from `#[namespace = value]`.
+///
+/// This is for attribute grammars that use the name-value form for a single
+/// value, e.g. `#[namespace = "value"]` or `#[namespace = path::to::item]`.
+/// List-form `#[namespace(..)]` attributes are ignored.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_value;
+/// use syn::{parse_quote, DeriveInput, Expr, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace = "value"]
+///     #[other = path::to::item]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let value_expected: Expr = parse_quote!("value");
+/// assert_eq!(Some(value_expected), namespace_value(&ast.attrs, &ns));
+///
+/// let ns_other: Path = parse_quote!(other);
+/// let value_expected: Expr = parse_quote!(path::to::item);
+/// assert_eq!(Some(value_expected), namespace_value(&ast.attrs, &ns_other));
+///
+/// let ns_missing: Path = parse_quote!(missing);
+/// assert_eq!(None, namespace_value(&ast.attrs, &ns_missing));
+/// ```
+///
+/// # Panics
+///
+/// Panics if there is more than one `#[namespace = value]` attribute.
+pub fn namespace_value(attrs: &[Attribute], namespace: &Path) -> Option<Expr> {
+    let values = attrs.iter().filter_map(|attr| match &attr.meta {
+        Meta::NameValue(meta_name_value) if meta_name_value.path == *namespace => {
+            Some(meta_name_value)
+        }
+        Meta::Path(_) | Meta::List(_) | Meta::NameValue(_) => None,
+    });
+    let value = at_most_one(values, || {
+        format!(
+            "Expected at most one `#[{} = ..]` attribute",
+            format_path(namespace),
+        )
+    });
+
+    value
+        .unwrap_or_else(|error| panic!("{}", error))
+        .map(|meta_name_value| meta_name_value.value.clone())
+}
+
+/// Returns the expression from `#[namespace = expr]` or `#[namespace(expr)]`.
+///
+/// Returns `Ok(None)` if there is no `#[namespace]` attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the attribute if the arguments are not a single
+/// expression, or if the attribute is specified more than once.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameter_expr;
+/// use syn::{parse_quote, DeriveInput, Expr};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[capacity(4 * 1024)]
+///     pub struct MyStruct;
+/// };
+///
+/// let expr_expected: Expr = parse_quote!(4 * 1024);
+/// assert_eq!(
+///     Some(expr_expected),
+///     namespace_parameter_expr(&ast.attrs, &parse_quote!(capacity))?,
+/// );
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn namespace_parameter_expr(
+    attrs: &[Attribute],
+    namespace: &Path,
+) -> syn::Result<Option<Expr>> {
+    let mut namespace_attrs = attrs.iter().filter(|attr| attr.path() == namespace);
+    let namespace_attr = namespace_attrs.next();
+
+    if let Some(namespace_attr_second) = namespace_attrs.next() {
+        return Err(Error::new_spanned(
+            namespace_attr_second,
+            format!(
+                "Expected at most one `#[{}]` attribute.",
+                format_path(namespace)
+            ),
+        ));
+    }
+
+    namespace_attr
+        .map(|namespace_attr| match &namespace_attr.meta {
+            Meta::NameValue(meta_name_value) => Ok(meta_name_value.value.clone()),
+            Meta::List(meta_list) => meta_list.parse_args::<Expr>(),
+            Meta::Path(_) => Err(Error::new_spanned(
+                namespace_attr,
+                format!(
+                    "Expected `#[{namespace} = ..]` or `#[{namespace}(..)]`.",
+                    namespace = format_path(namespace)
+                ),
+            )),
+        })
+        .transpose()
+}
+
+/// Returns the expression from `#[namespace(tag = expr)]`.
+///
+/// Returns `Ok(None)` if there is no `tag` parameter.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// * If a `#[namespace]` attribute cannot be parsed, see
+///   [`namespace_nested_metas_checked`].
+/// * If the parameter is not of the form `tag = expr`, the error is spanned at
+///   the parameter.
+/// * If `tag` is specified more than once.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameter_expr;
+/// use syn::{parse_quote, DeriveInput, Expr};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(bound = T::MAX, skip)]
+///     pub struct MyStruct<T>(T);
+/// };
+///
+/// let expr_expected: Expr = parse_quote!(T::MAX);
+/// assert_eq!(
+///     Some(expr_expected),
+///     tag_parameter_expr(&ast.attrs, &parse_quote!(namespace), &parse_quote!(bound))?,
+/// );
+///
+/// let error =
+///     tag_parameter_expr(&ast.attrs, &parse_quote!(namespace), &parse_quote!(skip)).unwrap_err();
+/// assert_eq!("Expected `skip = ..`.", error.to_string());
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(bound = , skip)]
+///     pub struct MyStruct<T>(T);
+/// };
+///
+/// assert!(
+///     tag_parameter_expr(&ast.attrs, &parse_quote!(namespace), &parse_quote!(bound)).is_err()
+/// );
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn tag_parameter_expr(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<Option<Expr>> {
+    let mut tag_metas = namespace_nested_metas_checked(attrs, namespace)?
+        .into_iter()
+        .filter(|meta| meta.path() == tag);
+    let tag_meta = tag_metas.next();
+
+    if let Some(tag_meta_second) = tag_metas.next() {
+        return Err(Error::new_spanned(
+            tag_meta_second,
+            format!(
+                "Expected at most one `{}` parameter for `#[{}(..)]`.",
+                format_path(tag),
+                format_path(namespace)
+            ),
+        ));
+    }
+
+    tag_meta
+        .map(|tag_meta| match tag_meta {
+            Meta::NameValue(meta_name_value) => Ok(meta_name_value.value),
+            Meta::Path(_) | Meta::List(_) => Err(Error::new_spanned(
+                tag_meta,
+                format!("Expected `{} = ..`.", format_path(tag)),
+            )),
+        })
+        .transpose()
+}
+
+/// Returns the literal from `#[namespace(tag = literal)]`.
+///
+/// This is [`tag_parameter_expr`], with the value restricted to literals.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the value if it is not a literal, as well as
+/// the errors from [`tag_parameter_expr`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameter_lit;
+/// use syn::{parse_quote, DeriveInput, Lit};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(name = "value", other = value)]
+///     pub struct MyStruct;
+/// };
+///
+/// let lit_expected: Lit = parse_quote!("value");
+/// assert_eq!(
+///     Some(lit_expected),
+///     tag_parameter_lit(&ast.attrs, &parse_quote!(namespace), &parse_quote!(name))?,
+/// );
+/// assert!(tag_parameter_lit(&ast.attrs, &parse_quote!(namespace), &parse_quote!(other)).is_err());
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(name = , other)]
+///     pub struct MyStruct;
+/// };
+///
+/// assert!(tag_parameter_lit(&ast.attrs, &parse_quote!(namespace), &parse_quote!(name)).is_err());
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn tag_parameter_lit(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<Option<Lit>> {
+    tag_parameter_expr(attrs, namespace, tag)?
+        .map(|value| match value {
+            Expr::Lit(ExprLit { lit, .. }) => Ok(lit),
+            value => Err(Error::new_spanned(value, "Expected a literal.")),
+        })
+        .transpose()
+}
+
+/// Returns the string literal value of a `name = "value"` parameter.
+///
+/// # Parameters
+///
+/// * `meta`: The parameter, e.g. from [`namespace_parameters`].
+///
+/// # Errors
+///
+/// * If the parameter is not in the `name = value` form, the error is spanned
+///   at the parameter.
+/// * If the value is not a string literal, the error is spanned at the value.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::expect_lit_str;
+/// use syn::{parse_quote, Meta};
+///
+/// let meta: Meta = parse_quote!(rename = "name");
+/// assert_eq!("name", expect_lit_str(&meta).unwrap().value());
+///
+/// let meta: Meta = parse_quote!(rename = 3);
+/// let error = expect_lit_str(&meta).unwrap_err();
+/// assert_eq!(
+///     "Expected a string literal for `rename`, found `3`.",
+///     error.to_string()
+/// );
+///
+/// let meta: Meta = parse_quote!(rename);
+/// let error = expect_lit_str(&meta).unwrap_err();
+/// assert_eq!(
+///     "Expected `rename = \"..\"`, found `rename`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn expect_lit_str(meta: &Meta) -> syn::Result<LitStr> {
+    match meta_value(meta, "\"..\"")? {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => Ok(lit_str.clone()),
+        value => Err(value_mismatch(meta, value, "a string literal")),
+    }
+}
+
+/// Returns the integer literal value of a `name = 123` parameter.
+///
+/// Use [`LitInt::base10_parse`] to parse the value as a particular integer
+/// type.
+///
+/// # Parameters
+///
+/// * `meta`: The parameter, e.g. from [`namespace_parameters`].
+///
+/// # Errors
+///
+/// * If the parameter is not in the `name = value` form, the error is spanned
+///   at the parameter.
+/// * If the value is not an integer literal, the error is spanned at the value.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::expect_lit_int;
+/// use syn::{parse_quote, Meta};
+///
+/// let meta: Meta = parse_quote!(capacity = 16);
+/// let capacity = expect_lit_int(&meta)
+///     .unwrap()
+///     .base10_parse::<usize>()
+///     .unwrap();
+/// assert_eq!(16, capacity);
+///
+/// let meta: Meta = parse_quote!(capacity = "16");
+/// let error = expect_lit_int(&meta).unwrap_err();
+/// assert_eq!(
+///     "Expected an integer literal for `capacity`, found `\"16\"`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn expect_lit_int(meta: &Meta) -> syn::Result<LitInt> {
+    match meta_value(meta, "0")? {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit_int),
+            ..
+        }) => Ok(lit_int.clone()),
+        value => Err(value_mismatch(meta, value, "an integer literal")),
+    }
+}
+
+/// Returns the path value of a `name = path::to::item` parameter.
+///
+/// The path may also be written as a string literal, i.e.
+/// `name = "path::to::item"`.
+///
+/// # Parameters
+///
+/// * `meta`: The parameter, e.g. from [`namespace_parameters`].
+///
+/// # Errors
+///
+/// * If the parameter is not in the `name = value` form, the error is spanned
+///   at the parameter.
+/// * If the value is not a path, or a string literal containing a path, the
+///   error is spanned at the value.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::expect_path;
+/// use syn::{parse_quote, Meta, Path};
+///
+/// let path_expected: Path = parse_quote!(my::module);
+///
+/// let meta: Meta = parse_quote!(with = my::module);
+/// assert_eq!(path_expected, expect_path(&meta).unwrap());
+///
+/// let meta: Meta = parse_quote!(with = "my::module");
+/// assert_eq!(path_expected, expect_path(&meta).unwrap());
+///
+/// let meta: Meta = parse_quote!(with = 1);
+/// let error = expect_path(&meta).unwrap_err();
+/// assert_eq!("Expected a path for `with`, found `1`.", error.to_string());
+/// ```
+pub fn expect_path(meta: &Meta) -> syn::Result<Path> {
+    match meta_value(meta, "path")? {
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) => Ok(path.clone()),
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => lit_str
+            .parse::<Path>()
+            .map_err(|_| value_mismatch(meta, &lit_str.token(), "a path")),
+        value => Err(value_mismatch(meta, value, "a path")),
+    }
+}
+
+/// Returns the parameters from `#[namespace(param1, param2, ..)]`.
+///
+/// Malformed attributes, such as `#[namespace(a = )]`, are ignored. Use
+/// [`namespace_nested_metas_checked`] to report them instead.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameters;
+/// use syn::{parse_quote, DeriveInput, Lit, LitStr, Meta, MetaNameValue, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One, two = "")]
+///     #[namespace(three(Value))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let namespace_parameters = namespace_parameters(&ast.attrs, &ns);
+///
+/// let meta_one: Path = parse_quote!(One);
+/// let param_one = Meta::Path(meta_one);
+/// let meta_two: MetaNameValue = parse_quote!(two = "");
+/// let param_two = Meta::NameValue(meta_two);
+/// let meta_three: LitStr = parse_quote!("three");
+/// let param_three = Meta::List(parse_quote!(three(Value)));
+/// assert_eq!(
+///     vec![param_one, param_two, param_three],
+///     namespace_parameters
+/// );
+/// ```
+pub fn namespace_parameters(attrs: &[Attribute], namespace: &Path) -> Vec<Meta> {
+    let namespace_nested_metas_iter = namespace_nested_metas_iter(attrs, namespace);
+
+    namespace_nested_metas_iter.collect::<Vec<Meta>>()
+}
+
+/// Returns the parameters from `#[namespace(param1, param2, ..)]`, combining
+/// repeated parameters using the given strategy.
+///
+/// Kept parameters are returned in the order they appear.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `merge_strategy`: How to combine repeated parameters.
+///
+/// # Errors
+///
+/// * With [`MergeStrategy::ErrorOnDuplicateKey`], returns an error for each
+///   repeated parameter, see [`assert_unique_parameters`].
+/// * Returns an error for each attribute that cannot be parsed, see
+///   [`namespace_nested_metas_checked`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{namespace_parameters_with, MergeStrategy};
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(rename = "a", skip)]
+///     #[namespace(rename = "b")]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let rename_a: Meta = parse_quote!(rename = "a");
+/// let rename_b: Meta = parse_quote!(rename = "b");
+/// let skip: Meta = parse_quote!(skip);
+///
+/// let parameters = namespace_parameters_with(&ast.attrs, &ns, MergeStrategy::FirstWins);
+/// assert_eq!(vec![rename_a, skip.clone()], parameters.unwrap());
+///
+/// let parameters = namespace_parameters_with(&ast.attrs, &ns, MergeStrategy::LastWins);
+/// assert_eq!(vec![skip, rename_b], parameters.unwrap());
+///
+/// let parameters = namespace_parameters_with(&ast.attrs, &ns, MergeStrategy::ErrorOnDuplicateKey);
+/// assert!(parameters.is_err());
+/// ```
+pub fn namespace_parameters_with(
+    attrs: &[Attribute],
+    namespace: &Path,
+    merge_strategy: MergeStrategy,
+) -> syn::Result<Vec<Meta>> {
+    let nested_metas = namespace_nested_metas_checked(attrs, namespace)?;
+    let is_repeated_in = |metas: &[Meta], meta: &Meta| {
+        metas
+            .iter()
+            .any(|meta_other| meta_other.path() == meta.path())
+    };
+
+    match merge_strategy {
+        MergeStrategy::Merge => Ok(nested_metas),
+        MergeStrategy::FirstWins => Ok(nested_metas
+            .iter()
+            .enumerate()
+            .filter(|(index, meta)| !is_repeated_in(&nested_metas[..*index], meta))
+            .map(|(_, meta)| meta.clone())
+            .collect()),
+        MergeStrategy::LastWins => Ok(nested_metas
+            .iter()
+            .enumerate()
+            .filter(|(index, meta)| !is_repeated_in(&nested_metas[index + 1..], meta))
+            .map(|(_, meta)| meta.clone())
+            .collect()),
+        MergeStrategy::ErrorOnDuplicateKey => {
+            assert_unique_parameters(attrs, namespace).map(|()| nested_metas)
+        }
+    }
+}
+
+/// Returns the parameter from `#[namespace(tag(parameter))]`.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameter;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(One))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(tag);
+/// let tag_param = tag_parameter(&ast.attrs, &ns, &tag);
+///
+/// let meta_one: Path = parse_quote!(One);
+/// let param_one = Meta::Path(meta_one);
+/// assert_eq!(Some(param_one), tag_param);
+///
+/// let tag_other: Path = parse_quote!(tag_other);
+/// let tag_param_other = tag_parameter(&ast.attrs, &ns, &tag_other);
+/// assert_eq!(None, tag_param_other);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the number of parameters for the tag is more than one.
+#[allow(clippy::let_and_return)] // Needed due to bug in clippy.
+pub fn tag_parameter(attrs: &[Attribute], namespace: &Path, tag: &Path) -> Option<Meta> {
+    let namespace_nested_metas_iter = namespace_nested_metas_iter(attrs, namespace);
+    let tag_param = at_most_one(
+        tag_nested_metas_iter(namespace_nested_metas_iter, tag),
+        || {
+            format!(
+                "Expected at most one parameter for `#[{}({}(..))]`",
+                format_path(namespace),
+                format_path(tag),
+            )
+        },
+    );
+
+    tag_param.unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Returns the parameter from `#[namespace(tag(parameter))]`, or an error if
+/// there is more than one.
+///
+/// This is the non-panicking counterpart of [`tag_parameter`]. Attributes that
+/// cannot be parsed are also reported, see [`tag_nested_metas_checked`].
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// Returns an error spanned at the second parameter, with a note at the first,
+/// if there is more than one parameter.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameter_checked;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(One))]
+///     #[namespace(other(One), other(Two))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let meta_one: Meta = parse_quote!(One);
+/// assert_eq!(
+///     Some(meta_one),
+///     tag_parameter_checked(&ast.attrs, &ns, &parse_quote!(tag)).unwrap()
+/// );
+///
+/// let error = tag_parameter_checked(&ast.attrs, &ns, &parse_quote!(other)).unwrap_err();
+/// assert_eq!(
+///     "Expected at most one parameter for `#[namespace(other(..))]`, found `One, Two`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn tag_parameter_checked(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<Option<Meta>> {
+    at_most_one(tag_nested_metas_checked(attrs, namespace, tag)?, || {
+        format!(
+            "Expected at most one parameter for `#[{}({}(..))]`",
+            format_path(namespace),
+            format_path(tag),
+        )
+    })
+}
+
+/// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`.
+///
+/// Malformed attributes and `tag` parameters, such as
+/// `#[namespace(tag(a = ))]`, are ignored. Use [`tag_nested_metas_checked`] to
+/// report them instead.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameters;
+/// use syn::{parse_quote, DeriveInput, Meta, MetaNameValue, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(One))]
+///     #[namespace(tag(two = ""))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(tag);
+/// let tag_parameters = tag_parameters(&ast.attrs, &ns, &tag);
+///
+/// let meta_one: Path = parse_quote!(One);
+/// let param_one = Meta::Path(meta_one);
+/// let meta_two: MetaNameValue = parse_quote!(two = "");
+/// let param_two = Meta::NameValue(meta_two);
+/// assert_eq!(vec![param_one, param_two], tag_parameters);
+/// ```
+pub fn tag_parameters(attrs: &[Attribute], namespace: &Path, tag: &Path) -> Vec<Meta> {
+    let namespace_nested_metas_iter = namespace_nested_metas_iter(attrs, namespace);
+    let parameters = tag_nested_metas_iter(namespace_nested_metas_iter, tag).collect::<Vec<Meta>>();
+
+    parameters
+}
+
+/// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`, each
+/// with the span of the attribute it was written in.
+///
+/// This allows a macro that rejects a parameter to point at it, its value, or
+/// the attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameters_spanned;
+/// use syn::{parse_quote, DeriveInput, Error, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(bound(Clone))]
+///     #[namespace(bound(Debug, Unknown))]
+///     pub struct MyStruct<T>(T);
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(bound);
+/// let spanned_metas = tag_parameters_spanned(&ast.attrs, &ns, &tag);
+///
+/// assert_eq!(3, spanned_metas.len());
+/// let error = Error::new(spanned_metas[2].span(), "Unknown bound.");
+/// assert_eq!("Unknown bound.", error.to_string());
+/// ```
+pub fn tag_parameters_spanned(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> Vec<SpannedMeta> {
+    namespace_nested_metas_spanned_iter(attrs, namespace)
+        .filter(|spanned_meta| spanned_meta.meta.path() == tag)
+        .filter_map(|spanned_meta| {
+            let attr_span = spanned_meta.attr_span;
+            spanned_meta
+                .meta
+                .require_list()
+                .and_then(|meta_list| {
+                    meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                })
+                .ok()
+                .map(move |metas| {
+                    metas
+                        .into_iter()
+                        .map(move |meta| SpannedMeta { meta, attr_span })
+                })
+        })
+        .flatten()
+        .collect()
+}
+
+/// Returns the parameters from `#[namespace(param1, param2, ..)]`, or an error
+/// if any parameter is not one of the allowed keys.
+///
+/// This catches typos such as `#[namespace(skip_seralize)]` where the
+/// parameters are looked up. When an unknown parameter is similar to an
+/// allowed key, the error suggests the allowed key. See [`AttrValidator`] to
+/// validate all attributes of an item at once.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `allowed_keys`: The allowed parameters.
+///
+/// # Errors
+///
+/// Returns an error spanned at each unknown parameter. Attributes that cannot
+/// be parsed are also reported.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_parameters_strict;
+/// use syn::{parse_quote, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(skip_seralize)]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let error =
+///     namespace_parameters_strict(&ast.attrs, &ns, &["skip_serialize", "rename"]).unwrap_err();
+/// assert_eq!(
+///     "Unknown parameter `skip_seralize` for `#[namespace(..)]`, did you mean \
+///      `skip_serialize`?",
+///     error.to_string()
+/// );
+/// ```
+///
+/// [`AttrValidator`]: crate::AttrValidator
+pub fn namespace_parameters_strict(
+    attrs: &[Attribute],
+    namespace: &Path,
+    allowed_keys: &[&str],
+) -> syn::Result<Vec<Meta>> {
+    let nested_metas = namespace_nested_metas_checked(attrs, namespace)?;
+
+    parameters_strict(
+        nested_metas,
+        &format!("#[{}(..)]", format_path(namespace)),
+        allowed_keys,
+    )
+}
+
+/// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`, or an
+/// error if any parameter is not one of the allowed keys.
+///
+/// See [`namespace_parameters_strict`].
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+/// * `allowed_keys`: The allowed parameters.
+///
+/// # Errors
+///
+/// Returns an error spanned at each unknown parameter. Attributes that cannot
+/// be parsed are also reported.
+pub fn tag_parameters_strict(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+    allowed_keys: &[&str],
+) -> syn::Result<Vec<Meta>> {
+    let nested_metas = tag_nested_metas_checked(attrs, namespace, tag)?;
+
+    parameters_strict(
+        nested_metas,
+        &format!("#[{}({}(..))]", format_path(namespace), format_path(tag)),
+        allowed_keys,
+    )
+}
+
+/// Returns the parameter from `#[namespace(tag(parameter))]`, or an error if
+/// the parameter is not one of the allowed keys, or there is more than one.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+/// * `allowed_keys`: The allowed parameters.
+///
+/// # Errors
+///
+/// * Returns an error spanned at each unknown parameter, see
+///   [`tag_parameters_strict`].
+/// * Returns an error spanned at the second parameter if there is more than
+///   one, see [`tag_parameter_checked`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameter_strict;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(case(snake_case))]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(case);
+/// let meta_expected: Meta = parse_quote!(snake_case);
+/// assert_eq!(
+///     Some(meta_expected),
+///     tag_parameter_strict(&ast.attrs, &ns, &tag, &["snake_case", "camelCase"]).unwrap()
+/// );
+///
+/// let error = tag_parameter_strict(&ast.attrs, &ns, &tag, &["lowercase"]).unwrap_err();
+/// assert_eq!(
+///     "Unknown parameter `snake_case` for `#[namespace(case(..))]`, expected one of: \
+///      `lowercase`.",
+///     error.to_string()
+/// );
+/// ```
+pub fn tag_parameter_strict(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+    allowed_keys: &[&str],
+) -> syn::Result<Option<Meta>> {
+    at_most_one(
+        tag_parameters_strict(attrs, namespace, tag, allowed_keys)?,
+        || {
+            format!(
+                "Expected at most one parameter for `#[{}({}(..))]`",
+                format_path(namespace),
+                format_path(tag),
+            )
+        },
+    )
+}
+
+/// Returns the nested metas from a `#[namespace(..)]` attribute.
+///
+/// Unlike `attr.parse_args_with(..)`, this accepts each of the following:
+///
+/// * `#[namespace]`: returns no metas.
+/// * `#[namespace()]`: returns no metas.
+/// * `#[namespace(a, b,)]`: trailing commas are permitted.
+///
+/// # Parameters
+///
+/// * `attr`: The attribute to parse.
+///
+/// # Errors
+///
+/// Returns an error naming the namespace if the attribute is in the
+/// `#[namespace = ..]` form, or if its arguments cannot be parsed as metas.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::parse_nested_metas;
+/// use syn::{parse_quote, Attribute, Meta};
+///
+/// let attr: Attribute = parse_quote!(#[namespace(a, b = 1,)]);
+/// let nested_metas = parse_nested_metas(&attr).unwrap();
+/// let meta_a: Meta = parse_quote!(a);
+/// let meta_b: Meta = parse_quote!(b = 1);
+/// assert_eq!(vec![meta_a, meta_b], nested_metas);
+///
+/// let attr: Attribute = parse_quote!(#[namespace]);
+/// assert!(parse_nested_metas(&attr).unwrap().is_empty());
+///
+/// let attr: Attribute = parse_quote!(#[namespace(a = )]);
+/// let error = parse_nested_metas(&attr).unwrap_err();
+/// assert!(
+///     error
+///         .to_string()
+///         .starts_with("Failed to parse `#[namespace(..)]` arguments:")
+/// );
+/// ```
+pub fn parse_nested_metas(attr: &Attribute) -> syn::Result<Vec<Meta>> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(Vec::new()),
+        Meta::List(meta_list) => meta_list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .map(|nested_metas| nested_metas.into_iter().collect())
+            .map_err(|error| {
+                Error::new(
+                    error.span(),
+                    format!(
+                        "Failed to parse `#[{}(..)]` arguments: {}",
+                        format_path(&meta_list.path),
+                        error
+                    ),
+                )
+            }),
+        Meta::NameValue(meta_name_value) => {
+            let namespace = format_path(&meta_name_value.path);
+            Err(Error::new_spanned(
+                attr,
+                format!(
+                    "Expected `#[{namespace}(..)]` or `#[{namespace}]`, found `{}`.",
+                    source_text(attr)
+                ),
+            ))
+        }
+    }
+}
+
+/// Returns the parameter of a tag, in any of the given spellings.
+///
+/// See [`tag_parameters_with`].
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+/// * `spelling`: Which spellings of the tag to recognize.
+///
+/// # Panics
+///
+/// Panics if the number of parameters for the tag is more than one.
+pub fn tag_parameter_with(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+    spelling: TagSpelling,
+) -> Option<Meta> {
+    let tag_param = at_most_one(tag_parameters_with(attrs, namespace, tag, spelling), || {
+        format!(
+            "Expected at most one parameter for `#[{}({}(..))]`",
+            format_path(namespace),
+            format_path(tag),
+        )
+    });
+
+    tag_param.unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Returns the parameters of a tag, in any of the given spellings.
+///
+/// With [`TagSpelling::NestedOrPath`], parameters are collected from both
+/// `#[namespace(tag(param1, ..))]` and `#[namespace::tag(param1, ..)]`, in the
+/// order of the attributes.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+/// * `spelling`: Which spellings of the tag to recognize.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{tag_parameters_with, TagSpelling};
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my_derive(with(Serialize))]
+///     #[my_derive::with(Deserialize)]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(my_derive);
+/// let tag: Path = parse_quote!(with);
+/// let tag_parameters = tag_parameters_with(&ast.attrs, &ns, &tag, TagSpelling::NestedOrPath);
+///
+/// let meta_ser: Meta = parse_quote!(Serialize);
+/// let meta_de: Meta = parse_quote!(Deserialize);
+/// assert_eq!(vec![meta_ser, meta_de], tag_parameters);
+/// ```
+pub fn tag_parameters_with(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+    spelling: TagSpelling,
+) -> Vec<Meta> {
+    attrs
+        .iter()
+        .flat_map(|attr| {
+            if attr.path() == namespace {
+                let namespace_nested_metas = parse_nested_metas(attr).unwrap_or_default();
+                tag_nested_metas_iter(namespace_nested_metas.into_iter(), tag).collect()
+            } else if spelling == TagSpelling::NestedOrPath
+                && path_is_joined(attr.path(), namespace, tag)
+            {
+                parse_nested_metas(attr).unwrap_or_default()
+            } else {
+                Vec::new()
+            }
+        })
+        .collect()
+}
+
+/// Returns the parameters of each tag whose path satisfies `predicate`.
+///
+/// This allows a family of tags, such as `on_serialize` and
+/// `on_deserialize`, to be collected in one query. Each parameter is returned
+/// with the path of its tag, so the macro may dispatch on the tag.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `predicate`: Returns whether a tag's path should be included.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_parameters_matching;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(on_serialize(log), skip, on_deserialize(validate))]
+///     pub struct MyStruct;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag_parameters = tag_parameters_matching(&ast.attrs, &ns, |path| {
+///     path.get_ident()
+///         .map(|ident| ident.to_string().starts_with("on_"))
+///         .unwrap_or(false)
+/// });
+///
+/// let tag_ser: Path = parse_quote!(on_serialize);
+/// let tag_de: Path = parse_quote!(on_deserialize);
+/// let meta_log: Meta = parse_quote!(log);
+/// let meta_validate: Meta = parse_quote!(validate);
+/// assert_eq!(
+///     vec![(tag_ser, meta_log), (tag_de, meta_validate)],
+///     tag_parameters
+/// );
+/// ```
+pub fn tag_parameters_matching<F>(
+    attrs: &[Attribute],
+    namespace: &Path,
+    mut predicate: F,
+) -> Vec<(Path, Meta)>
+where
+    F: FnMut(&Path) -> bool,
+{
+    namespace_nested_metas_iter(attrs, namespace)
+        .filter(|meta| predicate(meta.path()))
+        .filter_map(|meta| match meta {
+            Meta::List(meta_list) => meta_list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+                .map(|tag_parameters| (meta_list.path, tag_parameters)),
+            Meta::Path(_) | Meta::NameValue(_) => None,
+        })
+        .flat_map(|(tag, tag_parameters)| {
+            tag_parameters
+                .into_iter()
+                .map(move |tag_parameter| (tag.clone(), tag_parameter))
+        })
+        .collect()
+}
+
+/// Returns the `namespace(..)` meta lists of `#[namespace(..)]` attributes.
+///
+/// Unlike [`namespace_nested_metas_iter`], the lists are not parsed, so their
+/// paths, delimiters, and spans are available to macros that re-emit or
+/// minimally rewrite the original attribute.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_meta_lists;
+/// use syn::{parse_quote, DeriveInput, MacroDelimiter, MetaList};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     #[namespace]
+///     #[namespace[two = ""]]
+///     pub struct MyStruct;
+/// };
+///
+/// let meta_lists = namespace_meta_lists(&ast.attrs, &parse_quote!(namespace));
+///
+/// assert_eq!(2, meta_lists.len());
+/// assert_eq!("One", meta_lists[0].tokens.to_string());
+/// assert!(matches!(
+///     meta_lists[1].delimiter,
+///     MacroDelimiter::Bracket(_)
+/// ));
+/// ```
+pub fn namespace_meta_lists<'f>(attrs: &'f [Attribute], namespace: &Path) -> Vec<&'f MetaList> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(meta_list) if meta_list.path == *namespace => Some(meta_list),
+            Meta::Path(_) | Meta::List(_) | Meta::NameValue(_) => None,
+        })
+        .collect()
+}
+
+/// Returns the meta lists of the form: `#[namespace(..)]`.
+///
+/// Each `meta_list` is a `namespace(..)` meta item.
+///
+/// Malformed attributes, such as `#[namespace(a = )]`, are ignored. Use
+/// [`namespace_nested_metas_checked`] to report them instead.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_nested_metas_iter;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     #[namespace(two = "")]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let nested_metas = namespace_nested_metas_iter(&ast.attrs, &ns).collect::<Vec<Meta>>();
+///
+/// let meta_one: Meta = Meta::Path(parse_quote!(One));
+/// let meta_two: Meta = Meta::NameValue(parse_quote!(two = ""));
+/// assert_eq!(vec![meta_one, meta_two], nested_metas);
+/// ```
+pub fn namespace_nested_metas_iter<'f>(
+    attrs: &'f [Attribute],
+    namespace: &'f Path,
+) -> impl Iterator<Item = Meta> + 'f {
+    attrs
+        .iter()
+        .filter_map(move |attr| {
+            if attr.path() == namespace {
+                parse_nested_metas(attr).ok()
+            } else {
+                None
+            }
+        })
+        .flat_map(|nested_metas| nested_metas.into_iter())
+}
+
+/// Returns an iterator over nested metas from `#[namespace(tag(..))]`.
+///
+/// Malformed `tag` parameters, such as `tag(a = )`, are ignored. Use
+/// [`tag_nested_metas_checked`] to report them instead.
+///
+/// # Parameters
+///
+/// * `namespace_nested_metas_iter`: The `#[namespace(..)]` meta lists.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{namespace_nested_metas_iter, tag_nested_metas_iter};
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(One))]
+///     #[namespace(tag(two = ""))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(tag);
+/// let ns_lists = namespace_nested_metas_iter(&ast.attrs, &ns);
+/// let nested_metas = tag_nested_metas_iter(ns_lists, &tag).collect::<Vec<Meta>>();
+///
+/// let meta_one: Meta = Meta::Path(parse_quote!(One));
+/// let meta_two: Meta = Meta::NameValue(parse_quote!(two = ""));
+/// assert_eq!(vec![meta_one, meta_two], nested_metas);
+pub fn tag_nested_metas_iter<'f>(
+    namespace_nested_metas_iter: impl Iterator<Item = Meta> + 'f,
+    tag: &'f Path,
+) -> impl Iterator<Item = Meta> + 'f {
+    namespace_nested_metas_iter
+        .filter_map(move |meta| {
+            if meta.path() == tag {
+                meta.require_list()
+                    .and_then(|meta_list| {
+                        meta_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    })
+                    .ok()
+            } else {
+                None
+            }
+        })
+        .flatten()
+}
+
+/// Returns the nested metas from `#[namespace(..)]` attributes, or an error if
+/// any of them cannot be parsed.
+///
+/// Unlike [`namespace_nested_metas_iter`], which skips attributes that cannot
+/// be parsed, this reports them, so that a malformed attribute such as
+/// `#[namespace(a = )]` is not treated as absent. Errors from every malformed
+/// attribute are combined.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+///
+/// # Errors
+///
+/// Returns an error for each `#[namespace]` attribute that cannot be parsed,
+/// see [`parse_nested_metas`].
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::namespace_nested_metas_checked;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(One)]
+///     #[namespace(two = "")]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let nested_metas = namespace_nested_metas_checked(&ast.attrs, &ns).unwrap();
+///
+/// let meta_one: Meta = Meta::Path(parse_quote!(One));
+/// let meta_two: Meta = Meta::NameValue(parse_quote!(two = ""));
+/// assert_eq!(vec![meta_one, meta_two], nested_metas);
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(one = )]
+///     #[namespace(two = "")]
+///     #[namespace(three = )]
+///     pub struct MyEnum;
+/// };
+///
+/// let error = namespace_nested_metas_checked(&ast.attrs, &ns).unwrap_err();
+/// assert_eq!(2, error.into_iter().count());
+/// ```
+pub fn namespace_nested_metas_checked(
+    attrs: &[Attribute],
+    namespace: &Path,
+) -> syn::Result<Vec<Meta>> {
+    let mut diagnostics = Diagnostics::new();
+    let nested_metas = attrs
+        .iter()
+        .filter(|attr| attr.path() == namespace)
+        .filter_map(|attr| diagnostics.check(parse_nested_metas(attr)))
+        .flatten()
+        .collect::<Vec<Meta>>();
+
+    diagnostics.error_or(nested_metas)
+}
+
+/// Returns the nested metas from `#[namespace(tag(..))]`, or an error if any
+/// of them cannot be parsed.
+///
+/// This is the checked counterpart of [`tag_nested_metas_iter`]:
+///
+/// * `#[namespace(tag)]` is accepted, and has no nested metas.
+/// * `#[namespace(tag = ..)]` is an error.
+/// * `#[namespace(tag(..))]` whose arguments cannot be parsed is an error.
+///
+/// # Parameters
+///
+/// * `attrs`: Attributes of the item to inspect.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// Returns the combined errors of every malformed `#[namespace]` attribute and
+/// `tag`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::tag_nested_metas_checked;
+/// use syn::{parse_quote, DeriveInput, Meta, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(One), tag(two = ""))]
+///     pub struct MyEnum;
+/// };
+///
+/// let ns: Path = parse_quote!(namespace);
+/// let tag: Path = parse_quote!(tag);
+/// let nested_metas = tag_nested_metas_checked(&ast.attrs, &ns, &tag).unwrap();
+///
+/// let meta_one: Meta = Meta::Path(parse_quote!(One));
+/// let meta_two: Meta = Meta::NameValue(parse_quote!(two = ""));
+/// assert_eq!(vec![meta_one, meta_two], nested_metas);
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[namespace(tag(one = ))]
+///     pub struct MyEnum;
+/// };
+///
+/// let error = tag_nested_metas_checked(&ast.attrs, &ns, &tag).unwrap_err();
+/// assert!(error
+///     .to_string()
+///     .starts_with("Failed to parse `#[namespace(tag(..))]` arguments:"));
+/// ```
+pub fn tag_nested_metas_checked(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<Vec<Meta>> {
+    let mut diagnostics = Diagnostics::new();
+    let namespace_nested_metas = diagnostics
+        .check(namespace_nested_metas_checked(attrs, namespace))
+        .unwrap_or_default();
+    let nested_metas = namespace_nested_metas
+        .iter()
+        .filter(|meta| meta.path() == tag)
+        .filter_map(|meta| {
+            let nested_metas = match meta {
+                Meta::Path(_) => Ok(Vec::new()),
+                Meta::List(meta_list) => meta_list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .map(|nested_metas| nested_metas.into_iter().collect())
+                    .map_err(|error| {
+                        Error::new(
+                            error.span(),
+                            format!(
+                                "Failed to parse `#[{}({}(..))]` arguments: {}",
+                                format_path(namespace),
+                                format_path(tag),
+                                error
+                            ),
+                        )
+                    }),
+                Meta::NameValue(_) => Err(Error::new_spanned(
+                    meta,
+                    format!(
+                        "Expected `#[{namespace}({tag}(..))]` or `#[{namespace}({tag})]`, found \
+                         `#[{namespace}({source})]`.",
+                        namespace = format_path(namespace),
+                        tag = format_path(tag),
+                        source = source_text(meta),
+                    ),
+                )),
+            };
+            diagnostics.check(nested_metas)
+        })
+        .flatten()
+        .collect::<Vec<Meta>>();
+
+    diagnostics.error_or(nested_metas)
+}
+
+/// Returns whether an item's attributes contain a given `#[namespace(tag)]`
+/// attribute, or an error if any `#[namespace]` attribute cannot be parsed.
+///
+/// Unlike [`contains_tag`], a malformed attribute is reported instead of
+/// being treated as not containing the tag.
+///
+/// # Parameters
+///
+/// * `attrs`: The attributes on the item.
+/// * `namespace`: The `path()` of the first-level attribute.
+/// * `tag`: The `path()` of the second-level attribute.
+///
+/// # Errors
+///
+/// Returns an error for each `#[namespace]` attribute that cannot be parsed.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::contains_tag_checked;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(skip = )]
+///     pub struct MyStruct;
+/// };
+///
+/// let result = contains_tag_checked(&ast.attrs, &parse_quote!(my::derive), &parse_quote!(skip));
+/// assert!(result.is_err());
+/// ```
+pub fn contains_tag_checked(
+    attrs: &[Attribute],
+    namespace: &Path,
+    tag: &Path,
+) -> syn::Result<bool> {
+    namespace_nested_metas_checked(attrs, namespace)
+        .map(|nested_metas| nested_metas.iter().any(|meta| meta.path() == tag))
+}
+
+/// Returns an iterator over nested metas from `#[namespace(..)]`, with the span
+/// of the attribute each was written in.
+fn namespace_nested_metas_spanned_iter<'f>(
+    attrs: &'f [Attribute],
+    namespace: &'f Path,
+) -> impl Iterator<Item = SpannedMeta> + 'f {
+    attrs
+        .iter()
+        .filter(move |attr| attr.path() == namespace)
+        .filter_map(|attr| {
+            let attr_span = attr.span();
+            parse_nested_metas(attr).ok().map(move |nested_metas| {
+                nested_metas
+                    .into_iter()
+                    .map(move |meta| SpannedMeta { meta, attr_span })
+            })
+        })
+        .flatten()
+}
+
+/// Returns whether the attribute's arguments may contain `path`.
+///
+/// See [`tokens_may_contain`].
+pub(crate) fn attr_tokens_may_contain(attr: &Attribute, path: &Path) -> bool {
+    match &attr.meta {
+        Meta::List(meta_list) => tokens_may_contain(&meta_list.tokens, path),
+        Meta::Path(_) | Meta::NameValue(_) => false,
+    }
+}
+
+/// Returns whether the top level of `tokens` may contain `path`.
+///
+/// This is a cheap check for whether the first segment of `path` appears in
+/// `tokens`, used to skip parsing attribute arguments that cannot contain the
+/// path.
+pub(super) fn tokens_may_contain(tokens: &TokenStream, path: &Path) -> bool {
+    match path.segments.first() {
+        Some(segment) => tokens.clone().into_iter().any(
+            |token_tree| matches!(token_tree, TokenTree::Ident(ident) if ident == segment.ident),
+        ),
+        None => true,
+    }
+}
+
+/// Returns the parameters, or an error for each that is not one of the allowed
+/// keys.
+///
+/// `attribute` is the attribute the parameters are in, e.g.
+/// `#[namespace(..)]`.
+fn parameters_strict(
+    nested_metas: Vec<Meta>,
+    attribute: &str,
+    allowed_keys: &[&str],
+) -> syn::Result<Vec<Meta>> {
+    let mut diagnostics = Diagnostics::new();
+    nested_metas
+        .iter()
+        .filter(|meta| !allowed_keys.contains(&format_path(meta.path()).as_str()))
+        .for_each(|meta| {
+            diagnostics.push(attr_validator::unknown_parameter_error(
+                meta.path(),
+                attribute,
+                allowed_keys,
+            ))
+        });
+
+    diagnostics.error_or(nested_metas)
+}
+
+/// Returns the value of a `name = value` parameter.
+///
+/// `placeholder` is shown as the value in the error message, e.g. `".."`.
+fn meta_value<'m>(meta: &'m Meta, placeholder: &str) -> syn::Result<&'m Expr> {
+    match meta {
+        Meta::NameValue(meta_name_value) => Ok(&meta_name_value.value),
+        Meta::Path(_) | Meta::List(_) => Err(Error::new_spanned(
+            meta,
+            format!(
+                "Expected `{} = {}`, found `{}`.",
+                format_path(meta.path()),
+                placeholder,
+                source_text(meta)
+            ),
+        )),
+    }
+}
+
+/// Returns an error for a parameter value that is not of the expected kind.
+fn value_mismatch<T>(meta: &Meta, value: &T, expected: &str) -> Error
+where
+    T: ToTokens,
+{
+    Error::new_spanned(
+        value,
+        format!(
+            "Expected {} for `{}`, found `{}`.",
+            expected,
+            format_path(meta.path()),
+            source_text(value)
+        ),
+    )
+}
+
+/// Returns whether `path` is `namespace::tag`.
+fn path_is_joined(path: &Path, namespace: &Path, tag: &Path) -> bool {
+    path.segments.len() == namespace.segments.len() + tag.segments.len()
+        && path
+            .segments
+            .iter()
+            .eq(namespace.segments.iter().chain(tag.segments.iter()))
+}
@@ -0,0 +1,205 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{DeriveInput, Generics, Path};
+
+use crate::ErrorBuilder;
+
+/// Returns the generated tokens, or the error and a fallback if generation
+/// failed.
+///
+/// When a derive fails, code that uses the trait it was meant to implement
+/// produces further "trait not implemented" errors, which hide the real
+/// problem. Emitting a minimal fallback impl alongside the `compile_error!`
+/// prevents these cascading errors.
+///
+/// # Parameters
+///
+/// * `ast`: The type the macro is applied to.
+/// * `result`: The result of generating the tokens.
+/// * `fallback`: Function that returns the fallback tokens, given the type's
+///   name and generics.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::emit_or_fallback;
+/// use quote::quote;
+/// use syn::{parse_quote, DeriveInput, Error};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct<T>(T);
+/// };
+/// let result = Err(Error::new(Span::call_site(), "Unsupported field type."));
+///
+/// let tokens = emit_or_fallback(&ast, result, |ident, generics| {
+///     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+///     quote! {
+///         impl #impl_generics my_crate::MyTrait for #ident #ty_generics #where_clause {
+///             fn describe(&self) -> &str {
+///                 unimplemented!()
+///             }
+///         }
+///     }
+/// });
+///
+/// let tokens_expected = quote! {
+///     ::core::compile_error! { "Unsupported field type." }
+///     impl<T> my_crate::MyTrait for Struct<T> {
+///         fn describe(&self) -> &str {
+///             unimplemented!()
+///         }
+///     }
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+pub fn emit_or_fallback<F>(
+    ast: &DeriveInput,
+    result: syn::Result<TokenStream>,
+    fallback: F,
+) -> TokenStream
+where
+    F: FnOnce(&Ident, &Generics) -> TokenStream,
+{
+    match result {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            let compile_errors = error.to_compile_error();
+            let fallback = fallback(&ast.ident, &ast.generics);
+
+            quote! {
+                #compile_errors
+                #fallback
+            }
+        }
+    }
+}
+
+/// Returns tokens that emit a warning at the given span.
+///
+/// Proc macro diagnostics are not yet stable, pending
+/// <https://github.com/rust-lang/rust/issues/54140>. Until then, this emits a
+/// use of a `#[deprecated]` constant, so that the compiler reports a
+/// `deprecated` warning with the message. The message is prefixed by the
+/// compiler with "use of deprecated constant".
+///
+/// The tokens are a `const _: () = { .. };` item, so they may be emitted
+/// wherever items are allowed.
+///
+/// # Parameters
+///
+/// * `span`: Span to report the warning at.
+/// * `message`: The warning message.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::emit_warning;
+/// use quote::quote;
+///
+/// let tokens = emit_warning(Span::call_site(), "`skip` has no effect on unit structs.");
+///
+/// let tokens_expected = quote! {
+///     const _: () = {
+///         #[deprecated(note = "`skip` has no effect on unit structs.")]
+///         #[allow(non_upper_case_globals)]
+///         const warning: () = ();
+///         let _ = warning;
+///     };
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+pub fn emit_warning(span: Span, message: &str) -> TokenStream {
+    let warning = Ident::new("warning", span);
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const warning: () = ();
+            let _ = #warning;
+        };
+    }
+}
+
+/// Returns the source text of the tokens, for quoting in error messages.
+///
+/// Within a proc macro, tokens from the user's code are quoted as written,
+/// using [`Span::source_text`]. Otherwise, such as for generated tokens, or
+/// when the tokens' spans cannot be joined on the stable compiler, this falls
+/// back to `TokenStream::to_string`.
+///
+/// # Parameters
+///
+/// * `tokens`: The tokens to quote.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::source_text;
+/// use syn::{parse_quote, Attribute, Field};
+///
+/// // Outside a proc macro, the tokens have no source text.
+/// let attr: Attribute = parse_quote!(#[my::derive(rename = "a", skip)]);
+/// assert_eq!(
+///     r#"# [my :: derive (rename = "a" , skip)]"#,
+///     source_text(&attr)
+/// );
+///
+/// let field: Field = parse_quote!(pub name: Vec<&'static str>);
+/// assert_eq!("pub name : Vec < & 'static str >", source_text(&field));
+/// ```
+pub fn source_text<T>(tokens: &T) -> String
+where
+    T: ToTokens,
+{
+    let token_stream = tokens.to_token_stream();
+    let mut token_trees = token_stream.clone().into_iter();
+    let span = match (token_trees.next(), token_trees.last()) {
+        (Some(first), Some(last)) => first.span().join(last.span()),
+        (Some(first), None) => Some(first.span()),
+        (None, _) => None,
+    };
+
+    span.and_then(|span| span.source_text())
+        .unwrap_or_else(|| token_stream.to_string())
+}
+
+/// Returns a `Path` as a String without whitespace between tokens.
+pub fn format_path(path: &Path) -> String {
+    quote!(#path)
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+}
+
+/// Returns the only item of the iterator, or `None` if it is empty.
+///
+/// If there is more than one item, the error is spanned at the second item,
+/// with a note at the first. The message is followed by the source text of the
+/// items, e.g. "Expected at most one parameter, found `A, B`."
+pub(crate) fn at_most_one<T, I, F>(items: I, message: F) -> syn::Result<Option<T>>
+where
+    T: ToTokens,
+    I: IntoIterator<Item = T>,
+    F: FnOnce() -> String,
+{
+    let mut items = items.into_iter().collect::<Vec<T>>();
+
+    if items.len() > 1 {
+        let found = items
+            .iter()
+            .map(source_text)
+            .collect::<Vec<String>>()
+            .join(", ");
+        Err(
+            ErrorBuilder::new_spanned(&items[1], format!("{}, found `{}`.", message(), found))
+                .note_spanned(&items[0], "previously specified here")
+                .build(),
+        )
+    } else {
+        Ok(items.pop())
+    }
+}
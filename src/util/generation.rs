@@ -0,0 +1,688 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt as _, fold::Fold, parse_quote, spanned::Spanned, Attribute, Data, DeriveInput,
+    Fields, GenericParam, Path, Type, TypePath, TypeReference, TypeSlice,
+};
+
+use super::attribute_list;
+use crate::{
+    fields_compatibility, Case, Compatibility, Diagnostics, ErrorBuilder, FieldRef, GenericArgsExt,
+    Mismatch,
+};
+
+/// Returns the item after applying each fold in order.
+///
+/// This allows multi-step AST rewrites to be composed from separate [`Fold`]
+/// implementations, instead of interleaving the rewrites by hand.
+///
+/// # Parameters
+///
+/// * `item`: The item to transform.
+/// * `folds`: The folds to apply.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{apply_folds, RenameIdent, StripAttrs};
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     #[my::derive(tag)]
+///     struct Struct;
+/// };
+///
+/// let ast = apply_folds(
+///     ast,
+///     &mut [
+///         &mut StripAttrs::new(parse_quote!(my::derive)),
+///         &mut RenameIdent::new(parse_quote!(Struct), parse_quote!(Renamed)),
+///     ],
+/// );
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     struct Renamed;
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+pub fn apply_folds(item: DeriveInput, folds: &mut [&mut dyn Fold]) -> DeriveInput {
+    folds
+        .iter_mut()
+        .fold(item, |item, fold| fold.fold_derive_input(item))
+}
+
+/// Returns the owned type for a borrowed type.
+///
+/// This is the same as [`to_owned_type_with`] with no additional mappings.
+///
+/// # Parameters
+///
+/// * `ty`: The type to map.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::to_owned_type;
+/// use syn::{parse_quote, Type};
+///
+/// let borrowed: Type = parse_quote!(&'a str);
+/// let owned_expected: Type = parse_quote!(String);
+/// assert_eq!(owned_expected, to_owned_type(&borrowed));
+///
+/// let borrowed: Type = parse_quote!(&'a [u8]);
+/// let owned_expected: Type = parse_quote!(Vec<u8>);
+/// assert_eq!(owned_expected, to_owned_type(&borrowed));
+/// ```
+pub fn to_owned_type(ty: &Type) -> Type {
+    to_owned_type_with(ty, &[])
+}
+
+/// Returns the owned type for a borrowed type, using additional mappings.
+///
+/// The following types are mapped:
+///
+/// * `&T` and `&mut T`: the owned type of the referent `T`.
+/// * `Cow<'_, T>`: the owned type of `T`.
+///
+/// The owned type of a referent `T` is:
+///
+/// 1. The owned type in `mappings` whose borrowed type is `T`, if any.
+/// 2. `String` for `str`.
+/// 3. `Vec<E>` for `[E]`.
+/// 4. For `Cow`, `<T as ::std::borrow::ToOwned>::Owned`.
+/// 5. For references, `T` itself.
+///
+/// Other types are returned unchanged. Only the outermost type is mapped, so
+/// `Vec<&str>` is returned as is.
+///
+/// # Parameters
+///
+/// * `ty`: The type to map.
+/// * `mappings`: Additional `(borrowed, owned)` referent type mappings.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::to_owned_type_with;
+/// use syn::{parse_quote, Type};
+///
+/// let mappings: [(Type, Type); 1] = [(parse_quote!(Path), parse_quote!(PathBuf))];
+///
+/// let borrowed: Type = parse_quote!(&'a Path);
+/// let owned_expected: Type = parse_quote!(PathBuf);
+/// assert_eq!(owned_expected, to_owned_type_with(&borrowed, &mappings));
+///
+/// let borrowed: Type = parse_quote!(Cow<'a, Path>);
+/// assert_eq!(owned_expected, to_owned_type_with(&borrowed, &mappings));
+///
+/// let borrowed: Type = parse_quote!(Cow<'a, T>);
+/// let owned_expected: Type = parse_quote!(<T as ::std::borrow::ToOwned>::Owned);
+/// assert_eq!(owned_expected, to_owned_type_with(&borrowed, &mappings));
+/// ```
+pub fn to_owned_type_with(ty: &Type, mappings: &[(Type, Type)]) -> Type {
+    let referent_to_owned = |referent: &Type| -> Option<Type> {
+        if let Some((_, owned)) = mappings.iter().find(|(borrowed, _)| borrowed == referent) {
+            return Some(owned.clone());
+        }
+
+        match referent {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("str") => {
+                Some(parse_quote!(String))
+            }
+            Type::Slice(TypeSlice { elem, .. }) => Some(parse_quote!(Vec<#elem>)),
+            _ => None,
+        }
+    };
+
+    match ty {
+        Type::Reference(TypeReference { elem, .. }) => {
+            referent_to_owned(elem).unwrap_or_else(|| Type::clone(elem))
+        }
+        Type::Path(TypePath { qself: None, path })
+            if path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "Cow")
+                .unwrap_or(false) =>
+        {
+            match path.type_arg(0) {
+                Some(referent) => referent_to_owned(referent)
+                    .unwrap_or_else(|| parse_quote!(<#referent as ::std::borrow::ToOwned>::Owned)),
+                None => ty.clone(),
+            }
+        }
+        _ => ty.clone(),
+    }
+}
+
+/// Returns pairs of corresponding fields from two sets of fields.
+///
+/// * Named fields are matched by name, in the order of `a`.
+/// * Tuple fields are matched by position.
+/// * Unit fields match unit fields, and return no pairs.
+///
+/// # Parameters
+///
+/// * `a`: The first set of fields, e.g. of the source struct.
+/// * `b`: The second set of fields, e.g. of the target struct.
+///
+/// # Errors
+///
+/// Returns an error if the fields are of different kinds, or if a field does
+/// not have a counterpart. Each unmatched field produces an error spanned at
+/// that field, and all errors are combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::zip_fields;
+/// use quote::quote;
+/// use syn::{parse_quote, Fields, FieldsNamed};
+///
+/// let fields_a: FieldsNamed = parse_quote!({ a: u32, b: u64 });
+/// let fields_b: FieldsNamed = parse_quote!({ b: u64, a: u32 });
+/// let fields_a = Fields::from(fields_a);
+/// let fields_b = Fields::from(fields_b);
+///
+/// let assignments = zip_fields(&fields_a, &fields_b)
+///     .unwrap()
+///     .into_iter()
+///     .map(|(field_a, field_b)| {
+///         let member_a = field_a.member();
+///         let member_b = field_b.member();
+///         quote!(#member_b: source.#member_a,)
+///     });
+/// let tokens = quote!(Target { #(#assignments)* });
+///
+/// let expected = quote!(Target { a: source.a, b: source.b, });
+/// assert_eq!(expected.to_string(), tokens.to_string());
+///
+/// // `b` is missing from the target, and `c` is missing from the source.
+/// let fields_c: FieldsNamed = parse_quote!({ a: u32, c: u64 });
+/// let error = zip_fields(&fields_a, &Fields::from(fields_c)).unwrap_err();
+/// assert_eq!(2, error.into_iter().count());
+/// ```
+pub fn zip_fields<'f>(
+    a: &'f Fields,
+    b: &'f Fields,
+) -> syn::Result<Vec<(FieldRef<'f>, FieldRef<'f>)>> {
+    let (pairs, mismatches) = fields_compatibility::match_fields(a, b);
+
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.extend(mismatches.iter().map(Mismatch::to_error));
+    diagnostics.error_or(pairs)
+}
+
+/// Returns whether two sets of fields are structurally compatible.
+///
+/// Fields are matched as in [`zip_fields`]. With
+/// [`Compatibility::ExactTypes`], corresponding fields must also have the same
+/// type.
+///
+/// # Parameters
+///
+/// * `a`: The source fields.
+/// * `b`: The target fields.
+/// * `compatibility`: How strictly the fields must match.
+///
+/// # Errors
+///
+/// Returns every missing, extra, and mismatched field. Use
+/// [`Mismatch::to_error`] to report them.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{fields_compatible, Compatibility, Mismatch};
+/// use syn::{parse_quote, Fields, FieldsNamed};
+///
+/// let fields_a: FieldsNamed = parse_quote!({ a: u32, b: u64, c: u8 });
+/// let fields_b: FieldsNamed = parse_quote!({ a: u32, b: String, d: u8 });
+/// let fields_a = Fields::from(fields_a);
+/// let fields_b = Fields::from(fields_b);
+///
+/// assert!(fields_compatible(&fields_a, &fields_a, Compatibility::ExactTypes).is_ok());
+///
+/// let mismatches =
+///     fields_compatible(&fields_a, &fields_b, Compatibility::ExactTypes).unwrap_err();
+/// let messages = mismatches
+///     .iter()
+///     .map(|mismatch| mismatch.to_error().to_string())
+///     .collect::<Vec<String>>();
+/// assert_eq!(
+///     vec![
+///         "Field `c` does not exist in the target fields.",
+///         "Field `d` does not exist in the source fields.",
+///         "Field `b` has type `String`, but the source field has type `u64`.",
+///     ],
+///     messages
+/// );
+///
+/// let mismatches =
+///     fields_compatible(&fields_a, &fields_b, Compatibility::NamesOnly).unwrap_err();
+/// assert_eq!(2, mismatches.len());
+/// ```
+pub fn fields_compatible<'f>(
+    a: &'f Fields,
+    b: &'f Fields,
+    compatibility: Compatibility,
+) -> Result<(), Vec<Mismatch<'f>>> {
+    let (pairs, mut mismatches) = fields_compatibility::match_fields(a, b);
+
+    if compatibility == Compatibility::ExactTypes {
+        pairs
+            .into_iter()
+            .filter(|(field_ref_a, field_ref_b)| field_ref_a.field.ty != field_ref_b.field.ty)
+            .for_each(|(field_ref_a, field_ref_b)| {
+                mismatches.push(Mismatch::Type {
+                    a: field_ref_a,
+                    b: field_ref_b,
+                })
+            });
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// Returns an `#[allow(lint)]` attribute for each lint.
+///
+/// # Parameters
+///
+/// * `lints`: The lints to allow, e.g. `"non_camel_case_types"` or
+///   `"clippy::all"`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::allow_attrs;
+/// use syn::{parse_quote, Attribute};
+///
+/// let attrs = allow_attrs(&["non_camel_case_types", "clippy::all"]);
+///
+/// let attrs_expected: Vec<Attribute> = vec![
+///     parse_quote!(#[allow(non_camel_case_types)]),
+///     parse_quote!(#[allow(clippy::all)]),
+/// ];
+/// assert_eq!(attrs_expected, attrs);
+/// ```
+///
+/// # Panics
+///
+/// Panics if a lint is not a valid path.
+pub fn allow_attrs(lints: &[&str]) -> Vec<Attribute> {
+    lints
+        .iter()
+        .map(|lint| {
+            let lint = syn::parse_str::<Path>(lint)
+                .unwrap_or_else(|_| panic!("Expected `{lint}` to be a valid lint name."));
+            attribute_list(parse_quote!(allow), lint.into_token_stream())
+        })
+        .collect()
+}
+
+/// Returns the item tokens preceded by an `#[allow(lint)]` attribute for each
+/// lint.
+///
+/// This allows generated items to consistently suppress lints that macro
+/// output typically triggers.
+///
+/// # Parameters
+///
+/// * `item_tokens`: Tokens of the generated item.
+/// * `lints`: The lints to allow, e.g. `"non_camel_case_types"` or
+///   `"clippy::all"`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::wrap_with_allows;
+/// use quote::quote;
+///
+/// let tokens = wrap_with_allows(
+///     quote!(
+///         struct my_struct;
+///     ),
+///     &["non_camel_case_types"],
+/// );
+///
+/// let tokens_expected = quote! {
+///     #[allow(non_camel_case_types)]
+///     struct my_struct;
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+///
+/// # Panics
+///
+/// Panics if a lint is not a valid path.
+pub fn wrap_with_allows(item_tokens: TokenStream, lints: &[&str]) -> TokenStream {
+    let allow_attrs = allow_attrs(lints);
+
+    quote! {
+        #(#allow_attrs)*
+        #item_tokens
+    }
+}
+
+/// Returns an identifier that is unlikely to collide with other identifiers.
+///
+/// The identifier is of the form `__{prefix}_{hash}`, where `hash` is an
+/// FNV-1a hash of the span's source text and location, which differs between
+/// macro invocations.
+///
+/// The identifier depends only on `prefix` and `span`, so it is the same
+/// across builds, and does not depend on the order that macro invocations are
+/// expanded in. Calls with the same `prefix` and `span` return the same
+/// identifier, so use a different prefix for each identifier generated within
+/// one invocation.
+///
+/// This is intended for temporaries and helper items that may be generated by
+/// multiple invocations in the same module.
+///
+/// # Parameters
+///
+/// * `prefix`: Prefix to make the identifier recognizable.
+/// * `span`: Span of the macro input, e.g. the input type's ident. The
+///   identifier is also spanned here.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::gensym;
+///
+/// let ident_value = gensym("value", Span::call_site());
+/// let ident_other = gensym("other", Span::call_site());
+///
+/// assert!(ident_value.to_string().starts_with("__value_"));
+/// assert_eq!(ident_value, gensym("value", Span::call_site()));
+/// assert_ne!(ident_value, ident_other);
+/// ```
+pub fn gensym(prefix: &str, span: Span) -> Ident {
+    let span_text = span.source_text().unwrap_or_default();
+    let span_location = format!("{span:?}");
+    let hash = fnv1a(span_text.bytes().chain(span_location.bytes()));
+
+    format_ident!("__{}_{:08x}", prefix, hash, span = span)
+}
+
+/// Returns the 32-bit FNV-1a hash of the bytes.
+///
+/// This is used instead of `DefaultHasher`, whose algorithm may change between
+/// Rust releases.
+fn fnv1a<I>(bytes: I) -> u32
+where
+    I: IntoIterator<Item = u8>,
+{
+    const OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const PRIME: u32 = 0x0100_0193;
+
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Returns the names of the identifiers that the input item declares.
+///
+/// This includes the type name, its type and const generic parameters, and
+/// its variant and field names.
+///
+/// # Parameters
+///
+/// * `input`: The item to inspect.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use std::collections::HashSet;
+///
+/// use proc_macro_roids::idents_in_input;
+/// use syn::{parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     enum MyEnum<T> {
+///         A { value: T },
+///         B(u32),
+///     }
+/// };
+///
+/// let idents_expected = ["MyEnum", "T", "A", "value", "B"]
+///     .into_iter()
+///     .map(String::from)
+///     .collect::<HashSet<String>>();
+/// assert_eq!(idents_expected, idents_in_input(&ast));
+/// ```
+pub fn idents_in_input(input: &DeriveInput) -> HashSet<String> {
+    input_idents(input)
+        .into_iter()
+        .map(Ident::to_string)
+        .collect()
+}
+
+/// Returns an error if any candidate identifier is declared by the input item.
+///
+/// This allows macros to verify that generated method or type names do not
+/// collide with the type itself, its generic parameters, or its variant and
+/// field names. See [`idents_in_input`].
+///
+/// # Parameters
+///
+/// * `input`: The item that the macro is applied to.
+/// * `candidates`: The identifiers that the macro will generate.
+///
+/// # Errors
+///
+/// Returns an error for each colliding candidate, spanned at the candidate,
+/// with a note at the existing identifier. All errors are combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::check_no_collision;
+/// use syn::{parse_quote, DeriveInput, Ident};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct {
+///         builder: u32,
+///     }
+/// };
+///
+/// let candidates = [
+///     Ident::new("StructBuilder", Span::call_site()),
+///     Ident::new("builder", Span::call_site()),
+/// ];
+/// let error = check_no_collision(&ast, &candidates).unwrap_err();
+///
+/// assert_eq!(
+///     "Generated identifier `builder` collides with an existing identifier.",
+///     error.to_string()
+/// );
+/// ```
+pub fn check_no_collision(input: &DeriveInput, candidates: &[Ident]) -> syn::Result<()> {
+    let input_idents = input_idents(input);
+    let mut diagnostics = Diagnostics::new();
+
+    candidates.iter().for_each(|candidate| {
+        if let Some(input_ident) = input_idents
+            .iter()
+            .find(|input_ident| **input_ident == candidate)
+        {
+            let error = ErrorBuilder::new(
+                candidate.span(),
+                format!("Generated identifier `{candidate}` collides with an existing identifier."),
+            )
+            .note(
+                input_ident.span(),
+                format!("`{input_ident}` is declared here"),
+            )
+            .build();
+            diagnostics.push(error);
+        }
+    });
+
+    diagnostics.error_or(())
+}
+
+/// Returns the identifiers that the input item declares.
+fn input_idents(input: &DeriveInput) -> Vec<&Ident> {
+    let mut idents = vec![&input.ident];
+
+    idents.extend(
+        input
+            .generics
+            .params
+            .iter()
+            .filter_map(|generic_param| match generic_param {
+                GenericParam::Type(type_param) => Some(&type_param.ident),
+                GenericParam::Const(const_param) => Some(&const_param.ident),
+                GenericParam::Lifetime(_) => None,
+            }),
+    );
+
+    match &input.data {
+        Data::Struct(data_struct) => idents.extend(
+            data_struct
+                .fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref()),
+        ),
+        Data::Enum(data_enum) => data_enum.variants.iter().for_each(|variant| {
+            idents.push(&variant.ident);
+            idents.extend(
+                variant
+                    .fields
+                    .iter()
+                    .filter_map(|field| field.ident.as_ref()),
+            );
+        }),
+        Data::Union(data_union) => idents.extend(
+            data_union
+                .fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref()),
+        ),
+    }
+
+    idents
+}
+
+/// Returns an identifier made from the segments of a path, in the given case.
+///
+/// For example, `my::derive::skip` in [`Case::Snake`] is `my_derive_skip`.
+/// This is useful for generating helper constants, module names, or other
+/// identifiers from attribute paths.
+///
+/// # Parameters
+///
+/// * `path`: The path to flatten. Generic arguments are ignored. The `r#`
+///   prefix of raw identifier segments is dropped.
+/// * `case`: The case of the identifier.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{path_to_ident, Case};
+/// use syn::parse_quote;
+///
+/// let ident = path_to_ident(&parse_quote!(my::derive::skip), Case::Snake);
+/// assert_eq!("my_derive_skip", ident.to_string());
+///
+/// let ident = path_to_ident(&parse_quote!(::my::derive), Case::ScreamingSnake);
+/// assert_eq!("MY_DERIVE", ident.to_string());
+///
+/// let ident = path_to_ident(&parse_quote!(r#type::Foo), Case::Snake);
+/// assert_eq!("type_foo", ident.to_string());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the result is not a valid identifier, e.g. for [`Case::Kebab`].
+pub fn path_to_ident(path: &Path, case: Case) -> Ident {
+    let joined = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.unraw().to_string())
+        .collect::<Vec<String>>()
+        .join("_");
+    let span = path
+        .segments
+        .first()
+        .map(|segment| segment.ident.span())
+        .unwrap_or_else(Span::call_site);
+
+    Ident::new(&case.convert(&joined), span)
+}
+
+/// Returns the items placed in a `#[doc(hidden)]` module, with selected items
+/// re-exported.
+///
+/// This keeps large generated surfaces out of the caller's namespace. The
+/// module begins with `use super::*;`, so the items may refer to items in the
+/// caller's module.
+///
+/// Re-exported items must be declared `pub` within the module. Use [`gensym`]
+/// for the module name if the macro may be invoked multiple times in the same
+/// module.
+///
+/// # Parameters
+///
+/// * `mod_ident`: Name of the module.
+/// * `items`: Tokens of the items to place in the module.
+/// * `reexports`: Names of the items to `pub use` from the module.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro2::Span;
+/// use proc_macro_roids::wrap_in_module;
+/// use quote::quote;
+/// use syn::Ident;
+///
+/// let mod_ident = Ident::new("__my_struct_impl", Span::call_site());
+/// let items = quote! {
+///     pub struct MyStructBuilder;
+///     struct Helper;
+/// };
+/// let reexports = [Ident::new("MyStructBuilder", Span::call_site())];
+///
+/// let tokens = wrap_in_module(&mod_ident, items, &reexports);
+///
+/// let tokens_expected = quote! {
+///     #[doc(hidden)]
+///     mod __my_struct_impl {
+///         use super::*;
+///
+///         pub struct MyStructBuilder;
+///         struct Helper;
+///     }
+///
+///     pub use __my_struct_impl::{MyStructBuilder};
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// ```
+pub fn wrap_in_module(mod_ident: &Ident, items: TokenStream, reexports: &[Ident]) -> TokenStream {
+    let reexport = if reexports.is_empty() {
+        None
+    } else {
+        Some(quote!(pub use #mod_ident::{#(#reexports),*};))
+    };
+
+    quote! {
+        #[doc(hidden)]
+        mod #mod_ident {
+            use super::*;
+
+            #items
+        }
+
+        #reexport
+    }
+}
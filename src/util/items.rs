@@ -0,0 +1,553 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, ToTokens};
+use syn::{
+    ext::IdentExt as _, fold::Fold, parse_quote, Block, ConstParam, DeriveInput, Error, Expr, File,
+    FnArg, GenericParam, Generics, ImplItem, Item, ItemImpl, ItemMod, ItemTrait, Lifetime, Pat,
+    PatIdent, PatType, Path, ReturnType, Signature, TraitItem, TraitItemConst, TraitItemFn,
+    TraitItemType, Type, TypeParam,
+};
+
+use crate::{Diagnostics, RewriteReceiver};
+
+/// Returns the block with `self` replaced by the given expression.
+///
+/// See [`RewriteReceiver`] for how `self` is replaced.
+///
+/// # Parameters
+///
+/// * `block`: The block to rewrite.
+/// * `replacement`: The expression to replace `self` with, e.g. `self.inner`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::rewrite_receiver;
+/// use syn::{parse_quote, Block};
+///
+/// let block: Block = parse_quote!({ self.len() });
+/// let block = rewrite_receiver(block, parse_quote!(self.inner));
+///
+/// let block_expected: Block = parse_quote!({ self.inner.len() });
+/// assert_eq!(block_expected, block);
+/// ```
+pub fn rewrite_receiver(block: Block, replacement: Expr) -> Block {
+    RewriteReceiver::new(replacement).fold_block(block)
+}
+
+/// Returns an impl of the trait for the target type with `todo!()` bodies.
+///
+/// Every method and associated const is implemented as `todo!()`, and every
+/// associated type is implemented as `()`.
+///
+/// See [`stub_impl_with`] to specify the associated types.
+///
+/// # Parameters
+///
+/// * `trait_def`: The trait to implement.
+/// * `target`: The type to implement the trait for.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::stub_impl;
+/// use syn::{parse_quote, ItemImpl, ItemTrait};
+///
+/// let trait_def: ItemTrait = parse_quote! {
+///     trait Store<K> {
+///         type Value;
+///         fn get(&self, key: K) -> Option<Self::Value>;
+///     }
+/// };
+///
+/// let item_impl_expected: ItemImpl = parse_quote! {
+///     #[allow(unused_variables)]
+///     impl<K> Store<K> for MyStore {
+///         type Value = ();
+///         fn get(&self, key: K) -> Option<Self::Value> {
+///             todo!()
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     item_impl_expected,
+///     stub_impl(&trait_def, &parse_quote!(MyStore))
+/// );
+/// ```
+pub fn stub_impl(trait_def: &ItemTrait, target: &Type) -> ItemImpl {
+    stub_impl_with(trait_def, target, &[])
+}
+
+/// Returns an impl of the trait for the target type with `todo!()` bodies,
+/// using the given associated types.
+///
+/// Every method and associated const is implemented as `todo!()`. Associated
+/// types are implemented as the type in `associated_types` with the same
+/// name, or `()` if there is none.
+///
+/// The impl has the trait's generic parameters and where clause, and is
+/// annotated with `#[allow(unused_variables)]` as the stub bodies do not use
+/// the method parameters.
+///
+/// # Parameters
+///
+/// * `trait_def`: The trait to implement.
+/// * `target`: The type to implement the trait for.
+/// * `associated_types`: `(name, type)` pairs for the associated types.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::stub_impl_with;
+/// use syn::{parse_quote, Ident, ItemImpl, ItemTrait, Type};
+///
+/// let trait_def: ItemTrait = parse_quote! {
+///     trait Iterator {
+///         type Item;
+///         fn next(&mut self) -> Option<Self::Item>;
+///     }
+/// };
+/// let associated_types: [(Ident, Type); 1] = [(parse_quote!(Item), parse_quote!(u32))];
+///
+/// let item_impl_expected: ItemImpl = parse_quote! {
+///     #[allow(unused_variables)]
+///     impl Iterator for Counter {
+///         type Item = u32;
+///         fn next(&mut self) -> Option<Self::Item> {
+///             todo!()
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     item_impl_expected,
+///     stub_impl_with(&trait_def, &parse_quote!(Counter), &associated_types)
+/// );
+/// ```
+pub fn stub_impl_with(
+    trait_def: &ItemTrait,
+    target: &Type,
+    associated_types: &[(Ident, Type)],
+) -> ItemImpl {
+    let trait_ident = &trait_def.ident;
+    let (_, generics) = trait_impl_generics(trait_def, &Generics::default());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let impl_items = trait_def
+        .items
+        .iter()
+        .filter_map(|trait_item| -> Option<ImplItem> {
+            match trait_item {
+                TraitItem::Const(TraitItemConst { ident, ty, .. }) => Some(parse_quote! {
+                    const #ident: #ty = todo!();
+                }),
+                TraitItem::Type(TraitItemType {
+                    ident,
+                    generics: type_generics,
+                    ..
+                }) => {
+                    let ty = associated_types
+                        .iter()
+                        .find(|(type_ident, _)| type_ident == ident)
+                        .map(|(_, ty)| ty.clone())
+                        .unwrap_or_else(|| parse_quote!(()));
+                    let type_where_clause = &type_generics.where_clause;
+                    Some(parse_quote! {
+                        type #ident #type_generics = #ty #type_where_clause;
+                    })
+                }
+                TraitItem::Fn(TraitItemFn { sig, .. }) => Some(parse_quote! {
+                    #sig {
+                        todo!()
+                    }
+                }),
+                _ => None,
+            }
+        });
+
+    parse_quote! {
+        #[allow(unused_variables)]
+        impl #impl_generics #trait_ident #ty_generics for #target #where_clause {
+            #(#impl_items)*
+        }
+    }
+}
+
+/// Returns the source file with each struct, enum, and union transformed.
+///
+/// This is for attribute macros applied to whole files or modules. Each
+/// struct, enum, and union is passed to `f` as a `DeriveInput`, and is
+/// replaced by the items that `f` returns, which should include the type if it
+/// is to be kept. Items within inline modules are also transformed. Other
+/// items are left as is.
+///
+/// # Parameters
+///
+/// * `tokens`: Tokens of the source file, either a `proc_macro::TokenStream` or
+///   a `proc_macro2::TokenStream`.
+/// * `f`: The transform to apply to each type.
+///
+/// # Errors
+///
+/// Returns an error if the tokens do not parse as a source file, or if the
+/// tokens returned by `f` do not parse as items. Errors returned by `f` for
+/// each type are combined.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{transform_file, DeriveInputExt};
+/// use quote::quote;
+/// use syn::parse_quote;
+///
+/// let tokens = quote! {
+///     struct A;
+///     mod inner {
+///         enum B {}
+///     }
+///     fn f() {}
+/// };
+///
+/// let tokens = transform_file(tokens, |mut ast| {
+///     ast.append_derives(parse_quote!(Debug));
+///     Ok(quote!(#ast))
+/// })?;
+///
+/// let tokens_expected = quote! {
+///     #[derive(Debug)]
+///     struct A;
+///     mod inner {
+///         #[derive(Debug)]
+///         enum B {}
+///     }
+///     fn f() {}
+/// };
+/// assert_eq!(tokens_expected.to_string(), tokens.to_string());
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn transform_file<TS, F>(tokens: TS, mut f: F) -> syn::Result<TS>
+where
+    TS: Into<TokenStream> + From<TokenStream>,
+    F: FnMut(DeriveInput) -> syn::Result<TokenStream>,
+{
+    let mut file = syn::parse2::<File>(tokens.into())?;
+    file.items = transform_items(std::mem::take(&mut file.items), &mut f)?;
+
+    Ok(TS::from(file.into_token_stream()))
+}
+
+/// Returns the inline module with each struct, enum, and union transformed.
+///
+/// See [`transform_file`] for how items are transformed.
+///
+/// # Parameters
+///
+/// * `item_mod`: The inline module, i.e. `mod name { .. }`.
+/// * `f`: The transform to apply to each type.
+///
+/// # Errors
+///
+/// Returns an error if the module is not inline, as well as the errors from
+/// [`transform_file`].
+pub fn transform_module<F>(mut item_mod: ItemMod, mut f: F) -> syn::Result<ItemMod>
+where
+    F: FnMut(DeriveInput) -> syn::Result<TokenStream>,
+{
+    match item_mod.content.take() {
+        Some((brace, items)) => {
+            let items = transform_items(items, &mut f)?;
+            item_mod.content = Some((brace, items));
+            Ok(item_mod)
+        }
+        None => Err(Error::new_spanned(
+            &item_mod,
+            "Expected an inline module, e.g. `mod name { .. }`.",
+        )),
+    }
+}
+
+/// Returns the items with each struct, enum, and union transformed.
+///
+/// `f` is a trait object, as this recurses into inline modules.
+fn transform_items(
+    items: Vec<Item>,
+    f: &mut dyn FnMut(DeriveInput) -> syn::Result<TokenStream>,
+) -> syn::Result<Vec<Item>> {
+    let mut diagnostics = Diagnostics::new();
+    let mut items_transformed = Vec::with_capacity(items.len());
+
+    items.into_iter().for_each(|item| {
+        let derive_input = match item {
+            Item::Struct(item_struct) => DeriveInput::from(item_struct),
+            Item::Enum(item_enum) => DeriveInput::from(item_enum),
+            Item::Union(item_union) => DeriveInput::from(item_union),
+            Item::Mod(mut item_mod) if item_mod.content.is_some() => {
+                if let Some((brace, items)) = item_mod.content.take() {
+                    if let Some(items) = diagnostics.check(transform_items(items, f)) {
+                        item_mod.content = Some((brace, items));
+                        items_transformed.push(Item::Mod(item_mod));
+                    }
+                }
+                return;
+            }
+            item => {
+                items_transformed.push(item);
+                return;
+            }
+        };
+
+        let items_generated =
+            f(derive_input).and_then(|tokens| syn::parse2::<File>(tokens).map(|file| file.items));
+        if let Some(items_generated) = diagnostics.check(items_generated) {
+            items_transformed.extend(items_generated);
+        }
+    });
+
+    diagnostics.error_or(items_transformed)
+}
+
+/// Replaces each argument pattern in the signature with an identifier, and
+/// returns the identifiers in order.
+///
+/// Arguments that are already bound to an identifier keep it, other patterns
+/// are bound to `arg_{index}`. This allows a forwarding function to pass each
+/// argument on.
+pub(crate) fn forwarding_args(sig: &mut Signature) -> Vec<Ident> {
+    sig.inputs
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, fn_arg)| match fn_arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(PatType { pat, .. }) => {
+                let arg_ident = match &**pat {
+                    Pat::Ident(PatIdent {
+                        by_ref: None,
+                        subpat: None,
+                        ident,
+                        ..
+                    }) => ident.clone(),
+                    _ => format_ident!("arg_{}", index),
+                };
+                **pat = parse_quote!(#arg_ident);
+                Some(arg_ident)
+            }
+        })
+        .collect()
+}
+
+/// Returns whether the signature's return type is `Self`.
+pub(crate) fn returns_self(sig: &Signature) -> bool {
+    matches!(
+        &sig.output,
+        ReturnType::Type(_, ty) if matches!(&**ty, Type::Path(type_path) if type_path.path.is_ident("Self"))
+    )
+}
+
+/// Returns the generics for an impl block of a trait for a type, and the trait
+/// with its generic parameters renamed where they conflict with the type's.
+///
+/// The impl generics are the type's generics with the trait's generics
+/// appended, and the trait's where clause appended to the type's. Defaults are
+/// removed from the trait's generic parameters, as they are not permitted on
+/// the generic parameters of an impl block.
+///
+/// A trait parameter with the same name as a parameter of the type, such as
+/// `T` in `struct Wrapper<T>` and `trait Convert<T>`, is renamed by appending a
+/// number, e.g. `T1`, throughout the trait's generics, supertraits, and items.
+///
+/// # Parameters
+///
+/// * `item_trait`: The trait to implement.
+/// * `generics`: The generics of the type to implement the trait for.
+pub(crate) fn trait_impl_generics(
+    item_trait: &ItemTrait,
+    generics: &Generics,
+) -> (ItemTrait, Generics) {
+    let mut names_used = generics
+        .params
+        .iter()
+        .chain(item_trait.generics.params.iter())
+        .map(generic_param_name)
+        .collect::<HashSet<String>>();
+    let mut rename_generic_params = RenameGenericParams::default();
+    item_trait
+        .generics
+        .params
+        .iter()
+        .filter(|trait_param| {
+            let trait_param_name = generic_param_name(trait_param);
+            generics
+                .params
+                .iter()
+                .any(|param| generic_param_name(param) == trait_param_name)
+        })
+        .for_each(|trait_param| {
+            let ident = match trait_param {
+                GenericParam::Lifetime(lifetime_param) => &lifetime_param.lifetime.ident,
+                GenericParam::Type(type_param) => &type_param.ident,
+                GenericParam::Const(const_param) => &const_param.ident,
+            };
+            let name = generic_param_name(trait_param);
+            let n = (1usize..)
+                .find(|n| !names_used.contains(&format!("{name}{n}")))
+                .expect("Expected an unused generic parameter name to exist.");
+            names_used.insert(format!("{name}{n}"));
+
+            let ident_renamed = format_ident!("{}{}", ident, n, span = ident.span());
+            match trait_param {
+                GenericParam::Lifetime(_) => &mut rename_generic_params.lifetimes,
+                GenericParam::Type(_) | GenericParam::Const(_) => &mut rename_generic_params.idents,
+            }
+            .push((ident.clone(), ident_renamed));
+        });
+    let item_trait = rename_generic_params.fold_item_trait(item_trait.clone());
+
+    let mut generics_impl = generics.clone();
+    item_trait
+        .generics
+        .params
+        .iter()
+        .cloned()
+        .for_each(|mut generic_param| {
+            match &mut generic_param {
+                GenericParam::Type(type_param) => {
+                    type_param.eq_token = None;
+                    type_param.default = None;
+                }
+                GenericParam::Const(const_param) => {
+                    const_param.eq_token = None;
+                    const_param.default = None;
+                }
+                GenericParam::Lifetime(_) => {
+                    // Lifetime parameters must be declared before the other parameters.
+                    let lifetimes_count = generics_impl.lifetimes().count();
+                    generics_impl.params.insert(lifetimes_count, generic_param);
+                    return;
+                }
+            }
+            generics_impl.params.push(generic_param);
+        });
+    if let Some(where_clause) = &item_trait.generics.where_clause {
+        generics_impl
+            .make_where_clause()
+            .predicates
+            .extend(where_clause.predicates.iter().cloned());
+    }
+    if !generics_impl.params.is_empty() {
+        generics_impl.lt_token.get_or_insert_with(Default::default);
+        generics_impl.gt_token.get_or_insert_with(Default::default);
+    }
+
+    (item_trait, generics_impl)
+}
+
+/// Returns the name of a generic parameter, with a leading `'` for lifetimes.
+fn generic_param_name(generic_param: &GenericParam) -> String {
+    match generic_param {
+        GenericParam::Lifetime(lifetime_param) => lifetime_param.lifetime.to_string(),
+        GenericParam::Type(type_param) => type_param.ident.unraw().to_string(),
+        GenericParam::Const(const_param) => const_param.ident.unraw().to_string(),
+    }
+}
+
+/// Renames generic parameters, and the paths and lifetimes that refer to them.
+#[derive(Debug, Default)]
+struct RenameGenericParams {
+    /// Type and const parameter names, and what to rename them to.
+    idents: Vec<(Ident, Ident)>,
+    /// Lifetime names without the `'`, and what to rename them to.
+    lifetimes: Vec<(Ident, Ident)>,
+}
+
+impl RenameGenericParams {
+    /// Returns the renamed type or const parameter name, or `ident` if it is
+    /// not renamed.
+    fn renamed(&self, ident: Ident) -> Ident {
+        self.idents
+            .iter()
+            .find(|(from, _)| *from == ident)
+            .map(|(_, to)| to.clone())
+            .unwrap_or(ident)
+    }
+}
+
+impl Fold for RenameGenericParams {
+    fn fold_type_param(&mut self, type_param: TypeParam) -> TypeParam {
+        let mut type_param = syn::fold::fold_type_param(self, type_param);
+        type_param.ident = self.renamed(type_param.ident);
+        type_param
+    }
+
+    fn fold_const_param(&mut self, const_param: ConstParam) -> ConstParam {
+        let mut const_param = syn::fold::fold_const_param(self, const_param);
+        const_param.ident = self.renamed(const_param.ident);
+        const_param
+    }
+
+    fn fold_path(&mut self, path: Path) -> Path {
+        let mut path = syn::fold::fold_path(self, path);
+        if path.leading_colon.is_none() {
+            if let Some(path_segment) = path.segments.first_mut() {
+                path_segment.ident = self.renamed(path_segment.ident.clone());
+            }
+        }
+        path
+    }
+
+    fn fold_lifetime(&mut self, mut lifetime: Lifetime) -> Lifetime {
+        if let Some((_, to)) = self
+            .lifetimes
+            .iter()
+            .find(|(from, _)| *from == lifetime.ident)
+        {
+            lifetime.ident = to.clone();
+        }
+        lifetime
+    }
+}
+
+/// Returns an error if the tokens do not parse as a Rust source file.
+///
+/// This turns generated tokens that are not valid items into an immediate,
+/// located error during macro development, instead of a confusing error where
+/// the macro is used.
+///
+/// The macro shells, [`attribute_macro`] and [`derive_macro`], run this check
+/// on their output when their `verify` parameter is `true`.
+///
+/// # Parameters
+///
+/// * `tokens`: The generated tokens.
+///
+/// # Errors
+///
+/// Returns the parse error, spanned at the token that failed to parse.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::verify_parses;
+/// use quote::quote;
+///
+/// assert!(verify_parses(&quote!(impl Struct {})).is_ok());
+///
+/// let error = verify_parses(&quote!(impl Struct)).unwrap_err();
+/// assert!(
+///     error
+///         .to_string()
+///         .starts_with("Generated tokens failed to parse:")
+/// );
+/// ```
+///
+/// [`attribute_macro`]: crate::attribute_macro
+/// [`derive_macro`]: crate::derive_macro
+pub fn verify_parses(tokens: &TokenStream) -> syn::Result<()> {
+    syn::parse2::<syn::File>(tokens.clone())
+        .map(|_| ())
+        .map_err(|error| {
+            Error::new(
+                error.span(),
+                format!("Generated tokens failed to parse: {error}"),
+            )
+        })
+}
@@ -0,0 +1,10 @@
+#[cfg(feature = "items")]
+pub use self::items::*;
+pub use self::{attribute_edits::*, attributes::*, diagnostics::*, generation::*};
+
+mod attribute_edits;
+mod attributes;
+mod diagnostics;
+mod generation;
+#[cfg(feature = "items")]
+mod items;
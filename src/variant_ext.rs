@@ -0,0 +1,648 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{
+    punctuated::Punctuated, visit::Visit, Attribute, Data, DataStruct, DeriveInput, Fields,
+    GenericParam, Generics, Lifetime, Meta, Path, Token, TypeParamBound, Variant, Visibility,
+    WherePredicate,
+};
+
+use crate::{util, FieldsExt, VariantKind};
+
+/// Functions to make it ergonomic to inspect `Variant`s and their attributes.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::VariantExt;
+/// use syn::{parse_quote, Data, DeriveInput, Path};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     enum MyEnum {
+///         A,
+///         #[my::derive(skip)]
+///         B(u32),
+///     }
+/// };
+///
+/// let ns: Path = parse_quote!(my::derive);
+/// let skip: Path = parse_quote!(skip);
+/// if let Data::Enum(data_enum) = &ast.data {
+///     let variant_names = data_enum
+///         .variants
+///         .iter()
+///         .filter(|variant| !variant.contains_tag(&ns, &skip))
+///         .map(|variant| variant.ident.to_string())
+///         .collect::<Vec<String>>();
+///
+///     assert_eq!(vec!["A"], variant_names);
+/// }
+/// ```
+pub trait VariantExt {
+    /// Returns the variant's fields.
+    fn fields(&self) -> &Fields;
+
+    /// Returns true if the variant has no fields, e.g. `Variant`.
+    fn is_unit(&self) -> bool;
+
+    /// Returns true if the variant has exactly one unnamed field, e.g.
+    /// `Variant(T)`.
+    fn is_newtype(&self) -> bool;
+
+    /// Returns the shape of the variant.
+    fn kind(&self) -> VariantKind;
+
+    /// Returns a token stream of the construction form of the variant.
+    ///
+    /// The fields are bound the same way as [`FieldsExt::construction_form`]:
+    ///
+    /// * Unit variants: `MyEnum::Variant`
+    /// * Tuple variants: `MyEnum::Variant(_0, _1,)`
+    /// * Named variants: `MyEnum::Variant { a, b, }`
+    ///
+    /// This can also be used as a pattern, to bind the fields to the same
+    /// variables.
+    ///
+    /// # Parameters
+    ///
+    /// * `enum_path`: Path to the enum, e.g. `Self` or `MyEnum`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro2::TokenStream;
+    /// use proc_macro_roids::VariantExt;
+    /// use std::str::FromStr;
+    /// use syn::{parse_quote, Variant};
+    ///
+    /// let variant: Variant = parse_quote!(Variant { a: u32, b: u32 });
+    /// let construction_form = variant.construction_form(&parse_quote!(MyEnum));
+    ///
+    /// let expected = TokenStream::from_str("MyEnum::Variant { a, b, }").unwrap();
+    /// assert_eq!(expected.to_string(), construction_form.to_string());
+    /// ```
+    ///
+    /// [`FieldsExt::construction_form`]: crate::FieldsExt::construction_form
+    fn construction_form(&self, enum_path: &Path) -> TokenStream;
+
+    /// Returns a struct with the same name and fields as this variant.
+    ///
+    /// * Field attributes are kept, so field-level helper attributes still
+    ///   apply to the struct.
+    /// * Fields are given the struct's visibility, as variant fields cannot
+    ///   declare their own.
+    /// * The variant's attributes and discriminant are not kept.
+    /// * Generic parameters of the enum that the fields do not reference are
+    ///   removed, along with bounds and where predicates that reference them.
+    ///
+    /// # Parameters
+    ///
+    /// * `vis`: Visibility of the struct and its fields.
+    /// * `attrs`: Attributes of the struct, e.g. `#[derive(Debug)]`.
+    /// * `generics`: Generics of the enum that the variant belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,edition2021
+    /// use proc_macro_roids::VariantExt;
+    /// use syn::{parse_quote, DeriveInput, Generics, Variant};
+    ///
+    /// let generics: Generics = parse_quote!(<'a, T: Clone, U>);
+    /// let variant: Variant = parse_quote! {
+    ///     Borrowed {
+    ///         #[my::derive(skip)]
+    ///         value: &'a T,
+    ///     }
+    /// };
+    ///
+    /// let derive_input = variant.to_derive_input(
+    ///     parse_quote!(pub),
+    ///     vec![parse_quote!(#[derive(Debug)])],
+    ///     &generics,
+    /// );
+    ///
+    /// let derive_input_expected: DeriveInput = parse_quote! {
+    ///     #[derive(Debug)]
+    ///     pub struct Borrowed<'a, T: Clone> {
+    ///         #[my::derive(skip)]
+    ///         pub value: &'a T,
+    ///     }
+    /// };
+    /// assert_eq!(derive_input_expected, derive_input);
+    /// ```
+    fn to_derive_input(
+        &self,
+        vis: Visibility,
+        attrs: Vec<Attribute>,
+        generics: &Generics,
+    ) -> DeriveInput;
+
+    /// Returns whether a variant contains a given `#[namespace(tag)]`
+    /// attribute.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    fn contains_tag(&self, namespace: &Path, tag: &Path) -> bool;
+
+    /// Returns the parameter from `#[namespace(parameter)]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is more than one parameter for the tag.
+    fn namespace_parameter(&self, namespace: &Path) -> Option<Meta>;
+
+    /// Returns the parameters from `#[namespace(param1, param2, ..)]`.
+    ///
+    /// Malformed attributes are ignored. Use [`namespace_nested_metas_checked`]
+    /// to report them instead.
+    ///
+    /// [`namespace_nested_metas_checked`]: crate::namespace_nested_metas_checked
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    fn namespace_parameters(&self, namespace: &Path) -> Vec<Meta>;
+
+    /// Returns the parameter from `#[namespace(tag(parameter))]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is more than one parameter for the tag.
+    fn tag_parameter(&self, namespace: &Path, tag: &Path) -> Option<Meta>;
+
+    /// Returns the parameters from `#[namespace(tag(param1, param2, ..))]`.
+    ///
+    /// Malformed attributes and `tag` parameters are ignored. Use
+    /// [`tag_nested_metas_checked`] to report them instead.
+    ///
+    /// [`tag_nested_metas_checked`]: crate::tag_nested_metas_checked
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace`: The `path()` of the first-level attribute.
+    /// * `tag`: The `path()` of the second-level attribute.
+    fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta>;
+}
+
+impl VariantExt for Variant {
+    fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    fn is_unit(&self) -> bool {
+        matches!(self.fields, Fields::Unit)
+    }
+
+    fn is_newtype(&self) -> bool {
+        match &self.fields {
+            Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.len() == 1,
+            Fields::Named(..) | Fields::Unit => false,
+        }
+    }
+
+    fn kind(&self) -> VariantKind {
+        match &self.fields {
+            Fields::Unit => VariantKind::Unit,
+            Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
+                VariantKind::Newtype
+            }
+            Fields::Unnamed(..) => VariantKind::Tuple,
+            Fields::Named(..) => VariantKind::Struct,
+        }
+    }
+
+    fn construction_form(&self, enum_path: &Path) -> TokenStream {
+        let variant_ident = &self.ident;
+        let fields_construction_form = self.fields.construction_form();
+
+        quote!(#enum_path::#variant_ident #fields_construction_form)
+    }
+
+    fn to_derive_input(
+        &self,
+        vis: Visibility,
+        attrs: Vec<Attribute>,
+        generics: &Generics,
+    ) -> DeriveInput {
+        let mut fields = self.fields.clone();
+        fields.iter_mut().for_each(|field| {
+            if let Visibility::Inherited = field.vis {
+                field.vis = vis.clone();
+            }
+        });
+        let semi_token = match fields {
+            Fields::Named(..) => None,
+            Fields::Unnamed(..) | Fields::Unit => Some(Default::default()),
+        };
+        let generics = generics_referenced(generics, &fields);
+
+        DeriveInput {
+            attrs,
+            vis,
+            ident: self.ident.clone(),
+            generics,
+            data: Data::Struct(DataStruct {
+                struct_token: Default::default(),
+                fields,
+                semi_token,
+            }),
+        }
+    }
+
+    fn contains_tag(&self, namespace: &Path, tag: &Path) -> bool {
+        util::contains_tag(&self.attrs, namespace, tag)
+    }
+
+    fn namespace_parameter(&self, namespace: &Path) -> Option<Meta> {
+        util::namespace_parameter(&self.attrs, namespace)
+    }
+
+    fn namespace_parameters(&self, namespace: &Path) -> Vec<Meta> {
+        util::namespace_parameters(&self.attrs, namespace)
+    }
+
+    fn tag_parameter(&self, namespace: &Path, tag: &Path) -> Option<Meta> {
+        util::tag_parameter(&self.attrs, namespace, tag)
+    }
+
+    fn tag_parameters(&self, namespace: &Path, tag: &Path) -> Vec<Meta> {
+        util::tag_parameters(&self.attrs, namespace, tag)
+    }
+}
+
+/// Returns the generics with only the parameters that the fields reference.
+///
+/// Bounds and where predicates that reference a removed parameter are removed.
+fn generics_referenced(generics: &Generics, fields: &Fields) -> Generics {
+    let field_references = GenericReferences::of(|references| {
+        fields
+            .iter()
+            .for_each(|field| references.visit_type(&field.ty))
+    });
+    let (mut params, params_removed): (Vec<GenericParam>, Vec<GenericParam>) = generics
+        .params
+        .iter()
+        .cloned()
+        .partition(|param| field_references.contains_param(param));
+    let removed = GenericReferences::params(&params_removed);
+
+    params.iter_mut().for_each(|param| match param {
+        GenericParam::Type(type_param) => {
+            type_param.bounds = type_param
+                .bounds
+                .iter()
+                .filter(|bound| {
+                    !removed.intersects(&GenericReferences::of(|references| {
+                        references.visit_type_param_bound(bound)
+                    }))
+                })
+                .cloned()
+                .collect::<Punctuated<TypeParamBound, Token![+]>>();
+            if type_param.bounds.is_empty() {
+                type_param.colon_token = None;
+            }
+        }
+        GenericParam::Lifetime(lifetime_param) => {
+            lifetime_param.bounds = lifetime_param
+                .bounds
+                .iter()
+                .filter(|lifetime| !removed.lifetimes.contains(&lifetime.ident))
+                .cloned()
+                .collect::<Punctuated<Lifetime, Token![+]>>();
+            if lifetime_param.bounds.is_empty() {
+                lifetime_param.colon_token = None;
+            }
+        }
+        GenericParam::Const(..) => {}
+    });
+
+    let where_clause = generics.where_clause.clone().and_then(|mut where_clause| {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                !removed.intersects(&GenericReferences::of(|references| {
+                    references.visit_where_predicate(predicate)
+                }))
+            })
+            .cloned()
+            .collect::<Punctuated<WherePredicate, Token![,]>>();
+
+        if where_clause.predicates.is_empty() {
+            None
+        } else {
+            Some(where_clause)
+        }
+    });
+
+    if params.is_empty() {
+        Generics {
+            where_clause,
+            ..Generics::default()
+        }
+    } else {
+        Generics {
+            lt_token: Some(Default::default()),
+            params: params.into_iter().collect(),
+            gt_token: Some(Default::default()),
+            where_clause,
+        }
+    }
+}
+
+/// Idents and lifetimes referenced by a syntax tree.
+#[derive(Default)]
+struct GenericReferences {
+    /// Referenced idents, which include type and const parameters.
+    idents: HashSet<Ident>,
+    /// Referenced lifetimes.
+    lifetimes: HashSet<Ident>,
+}
+
+impl GenericReferences {
+    /// Returns the references visited by `visit_fn`.
+    fn of<F>(visit_fn: F) -> Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        let mut references = Self::default();
+        visit_fn(&mut references);
+        references
+    }
+
+    /// Returns the idents and lifetimes declared by the generic parameters.
+    fn params(params: &[GenericParam]) -> Self {
+        let mut references = Self::default();
+        params.iter().for_each(|param| match param {
+            GenericParam::Type(type_param) => {
+                references.idents.insert(type_param.ident.clone());
+            }
+            GenericParam::Lifetime(lifetime_param) => {
+                references
+                    .lifetimes
+                    .insert(lifetime_param.lifetime.ident.clone());
+            }
+            GenericParam::Const(const_param) => {
+                references.idents.insert(const_param.ident.clone());
+            }
+        });
+        references
+    }
+
+    /// Returns whether the generic parameter is referenced.
+    fn contains_param(&self, param: &GenericParam) -> bool {
+        match param {
+            GenericParam::Type(type_param) => self.idents.contains(&type_param.ident),
+            GenericParam::Lifetime(lifetime_param) => {
+                self.lifetimes.contains(&lifetime_param.lifetime.ident)
+            }
+            GenericParam::Const(const_param) => self.idents.contains(&const_param.ident),
+        }
+    }
+
+    /// Returns whether any ident or lifetime is in both sets of references.
+    fn intersects(&self, other: &GenericReferences) -> bool {
+        !self.idents.is_disjoint(&other.idents) || !self.lifetimes.is_disjoint(&other.lifetimes)
+    }
+}
+
+impl<'ast> Visit<'ast> for GenericReferences {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        self.idents.insert(ident.clone());
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.lifetimes.insert(lifetime.ident.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, DeriveInput, Generics, Meta, Variant};
+
+    use super::VariantExt;
+    use crate::VariantKind;
+
+    #[test]
+    fn fields_returns_variant_fields() {
+        let variant: Variant = parse_quote!(A { a: u32, b: u32 });
+
+        assert_eq!(2, variant.fields().len());
+    }
+
+    #[test]
+    fn is_unit_returns_true_only_for_unit_variant() {
+        let variant_unit: Variant = parse_quote!(A);
+        let variant_unnamed: Variant = parse_quote!(A());
+        let variant_named: Variant = parse_quote!(A {});
+
+        assert!(variant_unit.is_unit());
+        assert!(!variant_unnamed.is_unit());
+        assert!(!variant_named.is_unit());
+    }
+
+    #[test]
+    fn is_newtype_returns_true_only_for_single_unnamed_field() {
+        let variant_newtype: Variant = parse_quote!(A(u32));
+        let variant_tuple: Variant = parse_quote!(A(u32, u32));
+        let variant_named: Variant = parse_quote!(A { a: u32 });
+        let variant_unit: Variant = parse_quote!(A);
+
+        assert!(variant_newtype.is_newtype());
+        assert!(!variant_tuple.is_newtype());
+        assert!(!variant_named.is_newtype());
+        assert!(!variant_unit.is_newtype());
+    }
+
+    #[test]
+    fn kind_returns_shape_of_variant() {
+        let variant_unit: Variant = parse_quote!(A);
+        let variant_newtype: Variant = parse_quote!(A(u32));
+        let variant_tuple: Variant = parse_quote!(A(u32, u32));
+        let variant_empty_tuple: Variant = parse_quote!(A());
+        let variant_struct: Variant = parse_quote!(A { a: u32 });
+
+        assert_eq!(VariantKind::Unit, variant_unit.kind());
+        assert_eq!(VariantKind::Newtype, variant_newtype.kind());
+        assert_eq!(VariantKind::Tuple, variant_tuple.kind());
+        assert_eq!(VariantKind::Tuple, variant_empty_tuple.kind());
+        assert_eq!(VariantKind::Struct, variant_struct.kind());
+    }
+
+    #[test]
+    fn construction_form_unit_variant_is_path() {
+        let variant: Variant = parse_quote!(A);
+
+        let construction_form = variant.construction_form(&parse_quote!(Self));
+
+        assert_eq!(quote!(Self::A).to_string(), construction_form.to_string());
+    }
+
+    #[test]
+    fn construction_form_tuple_variant_binds_fields_by_index() {
+        let variant: Variant = parse_quote!(A(u32, u32));
+
+        let construction_form = variant.construction_form(&parse_quote!(my::MyEnum));
+
+        assert_eq!(
+            quote!(my::MyEnum::A(_0, _1,)).to_string(),
+            construction_form.to_string()
+        );
+    }
+
+    #[test]
+    fn to_derive_input_converts_tuple_variant_and_keeps_field_visibility() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(skip)]
+            Pair(pub(crate) u32, #[my::derive(rename = "b")] u64) = 1
+        };
+
+        let derive_input =
+            variant.to_derive_input(parse_quote!(pub), Vec::new(), &Generics::default());
+
+        let derive_input_expected: DeriveInput = parse_quote! {
+            pub struct Pair(pub(crate) u32, #[my::derive(rename = "b")] pub u64);
+        };
+        assert_eq!(derive_input_expected, derive_input);
+    }
+
+    #[test]
+    fn to_derive_input_converts_unit_variant() {
+        let variant: Variant = parse_quote!(Empty);
+        let generics: Generics = parse_quote!(<T>);
+
+        let derive_input = variant.to_derive_input(
+            parse_quote!(),
+            vec![parse_quote!(#[derive(Debug)])],
+            &generics,
+        );
+
+        let derive_input_expected: DeriveInput = parse_quote! {
+            #[derive(Debug)]
+            struct Empty;
+        };
+        assert_eq!(derive_input_expected, derive_input);
+    }
+
+    #[test]
+    fn to_derive_input_removes_bounds_and_predicates_for_unreferenced_params() {
+        let variant: Variant = parse_quote! {
+            Data {
+                values: [T; N],
+                borrowed: &'a str,
+            }
+        };
+        let mut generics: Generics =
+            parse_quote!(<'a: 'b, 'b, T: Into<U> + Clone, U, const N: usize>);
+        generics.where_clause = Some(parse_quote!(where T: Default, U: Copy, Vec<U>: Clone));
+
+        let derive_input = variant.to_derive_input(parse_quote!(), Vec::new(), &generics);
+
+        let derive_input_expected: DeriveInput = parse_quote! {
+            struct Data<'a, T: Clone, const N: usize>
+            where
+                T: Default
+            {
+                values: [T; N],
+                borrowed: &'a str,
+            }
+        };
+        assert_eq!(derive_input_expected, derive_input);
+    }
+
+    #[test]
+    fn contains_tag_returns_whether_tag_present() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(skip)]
+            A
+        };
+
+        assert!(variant.contains_tag(&parse_quote!(my::derive), &parse_quote!(skip)));
+        assert!(!variant.contains_tag(&parse_quote!(my::derive), &parse_quote!(other)));
+    }
+
+    #[test]
+    fn namespace_parameter_returns_meta_when_present() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(rename = "a")]
+            A
+        };
+
+        let meta_expected: Meta = parse_quote!(rename = "a");
+        assert_eq!(
+            Some(meta_expected),
+            variant.namespace_parameter(&parse_quote!(my::derive))
+        );
+        assert_eq!(None, variant.namespace_parameter(&parse_quote!(other)));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected at most one parameter for `#[my::derive(..)]`, found `skip, default`."
+    )]
+    fn namespace_parameter_panics_when_multiple_parameters_present() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(skip, default)]
+            A
+        };
+
+        variant.namespace_parameter(&parse_quote!(my::derive));
+    } // kcov-ignore
+
+    #[test]
+    fn namespace_parameters_returns_all_parameters() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(skip)]
+            #[my::derive(default)]
+            A
+        };
+
+        let metas_expected: Vec<Meta> = vec![parse_quote!(skip), parse_quote!(default)];
+        assert_eq!(
+            metas_expected,
+            variant.namespace_parameters(&parse_quote!(my::derive))
+        );
+    }
+
+    #[test]
+    fn tag_parameter_returns_meta_when_present() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(bound(Clone))]
+            A
+        };
+
+        let meta_expected: Meta = parse_quote!(Clone);
+        assert_eq!(
+            Some(meta_expected),
+            variant.tag_parameter(&parse_quote!(my::derive), &parse_quote!(bound))
+        );
+    }
+
+    #[test]
+    fn tag_parameters_returns_all_parameters() {
+        let variant: Variant = parse_quote! {
+            #[my::derive(bound(Clone))]
+            #[my::derive(bound(Debug))]
+            A
+        };
+
+        let metas_expected: Vec<Meta> = vec![parse_quote!(Clone), parse_quote!(Debug)];
+        assert_eq!(
+            metas_expected,
+            variant.tag_parameters(&parse_quote!(my::derive), &parse_quote!(bound))
+        );
+    }
+}
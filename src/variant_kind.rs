@@ -0,0 +1,29 @@
+/// The shape of an enum `Variant`.
+///
+/// Returned by [`VariantExt::kind`], so that macros can match on the shape
+/// instead of chaining `is_*` checks.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::{VariantExt, VariantKind};
+/// use syn::{parse_quote, Variant};
+///
+/// let variant: Variant = parse_quote!(Circle { radius: f64 });
+///
+/// assert_eq!(VariantKind::Struct, variant.kind());
+/// ```
+///
+/// [`VariantExt::kind`]: crate::VariantExt::kind
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VariantKind {
+    /// A variant with no fields, e.g. `Variant`.
+    Unit,
+    /// A variant with exactly one unnamed field, e.g. `Variant(u32)`.
+    Newtype,
+    /// A variant with zero, or more than one unnamed fields, e.g.
+    /// `Variant(u32, u32)`.
+    Tuple,
+    /// A variant with named fields, e.g. `Variant { a: u32 }`.
+    Struct,
+}
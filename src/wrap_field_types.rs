@@ -0,0 +1,77 @@
+use syn::{fold::Fold, parse_quote, Field, Path};
+
+/// Wraps the type of every field in a generic type, e.g. `T` to `Option<T>`.
+///
+/// # Examples
+///
+/// ```rust,edition2021
+/// use proc_macro_roids::WrapFieldTypes;
+/// use syn::{fold::Fold, parse_quote, DeriveInput};
+///
+/// let ast: DeriveInput = parse_quote! {
+///     struct Struct { a: u32, b: Vec<i32> }
+/// };
+///
+/// let mut wrap_field_types = WrapFieldTypes::new(parse_quote!(Option));
+/// let ast = wrap_field_types.fold_derive_input(ast);
+///
+/// let ast_expected: DeriveInput = parse_quote! {
+///     struct Struct { a: Option<u32>, b: Option<Vec<i32>> }
+/// };
+/// assert_eq!(ast_expected, ast);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WrapFieldTypes {
+    /// Path of the type to wrap field types in.
+    wrapper: Path,
+}
+
+impl WrapFieldTypes {
+    /// Returns a new `WrapFieldTypes` fold.
+    ///
+    /// # Parameters
+    ///
+    /// * `wrapper`: Path of the type to wrap field types in, e.g. `Option`.
+    pub fn new(wrapper: Path) -> Self {
+        Self { wrapper }
+    }
+}
+
+impl Fold for WrapFieldTypes {
+    fn fold_field(&mut self, mut field: Field) -> Field {
+        let wrapper = &self.wrapper;
+        let ty = &field.ty;
+        field.ty = parse_quote!(#wrapper<#ty>);
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{fold::Fold, parse_quote, DeriveInput};
+
+    use super::WrapFieldTypes;
+
+    #[test]
+    fn wraps_enum_variant_field_types() {
+        let ast: DeriveInput = parse_quote! {
+            enum Enum {
+                Unit,
+                Tuple(u32),
+                Named { a: String },
+            }
+        };
+
+        let mut wrap_field_types = WrapFieldTypes::new(parse_quote!(std::cell::Cell));
+        let ast = wrap_field_types.fold_derive_input(ast);
+
+        let ast_expected: DeriveInput = parse_quote! {
+            enum Enum {
+                Unit,
+                Tuple(std::cell::Cell<u32>),
+                Named { a: std::cell::Cell<String> },
+            }
+        };
+        assert_eq!(ast_expected, ast);
+    }
+}